@@ -0,0 +1,842 @@
+use crate::{
+    cli::{BatchArgs, BatchInputFormat, EventCategory, OutputFormat},
+    datetime::{self, TimeContext},
+    error::CliError,
+    fields,
+    output::{self, Printer},
+    reclaim_api::{CreateTaskRequest, Outcome, PlannedRequest, PlannerAction, ReclaimApi, Task},
+    TASK_TIME_FIELDS,
+};
+use futures_util::{stream, StreamExt};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::{
+    cell::Cell,
+    fs,
+    io::{self, IsTerminal, Read},
+    path::Path,
+};
+
+const OPS_HINT: &str = "Each operation needs \"op\" (create, patch, put, delete or done) and, except for create, \"id\", e.g. {\"op\":\"patch\",\"id\":123,\"priority\":\"P1\"}.";
+
+/// One task operation read from the batch input, checked and ready to send.
+#[derive(Debug, Clone)]
+enum Operation {
+    Create(CreateTaskRequest),
+    Patch {
+        task_id: u64,
+        fields: Map<String, Value>,
+        notification_key: Option<String>,
+    },
+    Put {
+        task_id: u64,
+        fields: Map<String, Value>,
+        notification_key: Option<String>,
+    },
+    Delete {
+        task_id: u64,
+        notification_key: Option<String>,
+    },
+    Done {
+        task_id: u64,
+    },
+}
+
+impl Operation {
+    fn name(&self) -> &'static str {
+        match self {
+            Operation::Create(_) => "create",
+            Operation::Patch { .. } => "patch",
+            Operation::Put { .. } => "put",
+            Operation::Delete { .. } => "delete",
+            Operation::Done { .. } => "done",
+        }
+    }
+
+    fn task_id(&self) -> Option<u64> {
+        match self {
+            Operation::Create(_) => None,
+            Operation::Patch { task_id, .. }
+            | Operation::Put { task_id, .. }
+            | Operation::Delete { task_id, .. }
+            | Operation::Done { task_id } => Some(*task_id),
+        }
+    }
+}
+
+/// An input line and the operation on it, or why it could not be read.
+#[derive(Debug)]
+struct BatchLine {
+    line: usize,
+    operation: Result<Operation, CliError>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum BatchStatus {
+    Ok,
+    Failed,
+    Skipped,
+    DryRun,
+}
+
+impl BatchStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            BatchStatus::Ok => "ok",
+            BatchStatus::Failed => "failed",
+            BatchStatus::Skipped => "skipped",
+            BatchStatus::DryRun => "dry_run",
+        }
+    }
+}
+
+/// The report entry for one input line.
+#[derive(Debug, Serialize)]
+struct BatchResult {
+    line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    op: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    task_id: Option<u64>,
+    status: BatchStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint: Option<String>,
+    /// The request `--dry-run` stopped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request: Option<PlannedRequest>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    task: Option<Task>,
+}
+
+/// `reclaim batch`: reads the operations, runs them `--concurrency` at a time and prints a
+/// report line per input line.
+pub async fn run_batch(
+    api: &impl ReclaimApi,
+    args: &BatchArgs,
+    default_category: EventCategory,
+    printer: &Printer,
+    time: &TimeContext,
+) -> Result<(), CliError> {
+    let text = read_input(&args.input)?;
+    let format = args
+        .input_format
+        .unwrap_or_else(|| detect_format(&args.input, &text));
+    let records = match format {
+        BatchInputFormat::Ndjson => ndjson_records(&text),
+        BatchInputFormat::Csv => csv_records(&text)?,
+    };
+    if records.is_empty() {
+        return Err(CliError::InvalidInput {
+            message: "The batch input has no operations.".to_string(),
+            hint: Some(OPS_HINT.to_string()),
+        });
+    }
+
+    let lines = records
+        .into_iter()
+        .map(|(line, record)| BatchLine {
+            line,
+            operation: record.and_then(|record| parse_operation(record, default_category, time)),
+        })
+        .collect::<Vec<_>>();
+
+    let human = printer.format == OutputFormat::Human;
+    let report = execute(
+        api,
+        lines,
+        usize::from(args.concurrency),
+        args.fail_fast,
+        |result| {
+            if human {
+                print_result_human(result);
+            }
+        },
+    )
+    .await;
+
+    if human {
+        println!("{}", summary(&report));
+    } else {
+        printer.print_collection(&report, || report_table(&report))?;
+    }
+
+    match report
+        .iter()
+        .filter(|result| result.status == BatchStatus::Failed)
+        .count()
+    {
+        0 => Ok(()),
+        failed => Err(CliError::BatchFailed {
+            failed,
+            total: report.len(),
+        }),
+    }
+}
+
+/// Runs the lines with at most `concurrency` requests in flight and reports results in
+/// input order. With `fail_fast`, a failure (or any invalid line) stops new operations
+/// from starting.
+async fn execute(
+    api: &impl ReclaimApi,
+    lines: Vec<BatchLine>,
+    concurrency: usize,
+    fail_fast: bool,
+    mut on_result: impl FnMut(&BatchResult),
+) -> Vec<BatchResult> {
+    let stop = Cell::new(fail_fast && lines.iter().any(|line| line.operation.is_err()));
+    let stop = &stop;
+    let mut results = stream::iter(lines)
+        .map(|line| async move {
+            let operation = match line.operation {
+                Ok(operation) => operation,
+                Err(error) => return result(line.line, None, Err(error)),
+            };
+            if stop.get() {
+                return BatchResult {
+                    status: BatchStatus::Skipped,
+                    ..result(line.line, Some(&operation), Ok(Outcome::Sent(None)))
+                };
+            }
+
+            let outcome = run_operation(api, &operation).await;
+            if fail_fast && outcome.is_err() {
+                stop.set(true);
+            }
+            result(line.line, Some(&operation), outcome)
+        })
+        .buffered(concurrency);
+
+    let mut report = Vec::new();
+    while let Some(result) = results.next().await {
+        on_result(&result);
+        report.push(result);
+    }
+    report
+}
+
+async fn run_operation(
+    api: &impl ReclaimApi,
+    operation: &Operation,
+) -> Result<Outcome<Option<Task>>, CliError> {
+    let outcome = match operation {
+        Operation::Create(request) => api.create_task(request.clone()).await?.map(Some),
+        Operation::Patch {
+            task_id,
+            fields,
+            notification_key,
+        } => api
+            .patch_task(
+                *task_id,
+                Value::Object(fields.clone()),
+                notification_key.as_deref(),
+            )
+            .await?
+            .map(Some),
+        Operation::Put {
+            task_id,
+            fields,
+            notification_key,
+        } => {
+            let existing = api.get_task(*task_id).await?;
+            let Ok(Value::Object(mut payload)) = serde_json::to_value(existing) else {
+                return Err(CliError::Output(
+                    "Could not serialize existing task for PUT.".to_string(),
+                ));
+            };
+            fields::deep_merge(&mut payload, fields.clone());
+            api.put_task(
+                *task_id,
+                Value::Object(payload),
+                notification_key.as_deref(),
+            )
+            .await?
+            .map(Some)
+        }
+        Operation::Delete {
+            task_id,
+            notification_key,
+        } => api
+            .delete_task(*task_id, notification_key.as_deref())
+            .await?
+            .map(|_| None),
+        Operation::Done { task_id } => api
+            .run_planner_action(*task_id, PlannerAction::Done)
+            .await?
+            .map(Some),
+    };
+    Ok(outcome)
+}
+
+fn result(
+    line: usize,
+    operation: Option<&Operation>,
+    outcome: Result<Outcome<Option<Task>>, CliError>,
+) -> BatchResult {
+    let mut result = BatchResult {
+        line,
+        op: operation.map(Operation::name),
+        task_id: operation.and_then(Operation::task_id),
+        status: BatchStatus::Ok,
+        error: None,
+        hint: None,
+        request: None,
+        task: None,
+    };
+    match outcome {
+        Ok(Outcome::Sent(task)) => {
+            result.task_id = result.task_id.or(task.as_ref().map(|task| task.id));
+            result.task = task;
+        }
+        Ok(Outcome::Planned(request)) => {
+            result.status = BatchStatus::DryRun;
+            result.request = Some(request);
+        }
+        Err(error) => {
+            result.status = BatchStatus::Failed;
+            result.hint = error.hint().map(str::to_string);
+            result.error = Some(error.to_string());
+        }
+    }
+    result
+}
+
+fn read_input(input: &str) -> Result<String, CliError> {
+    let text = if input == "-" {
+        if io::stdin().is_terminal() {
+            return Err(CliError::InvalidInput {
+                message: "reclaim batch reads operations from stdin, but stdin is a terminal."
+                    .to_string(),
+                hint: Some(
+                    "Pass a file (reclaim batch ops.ndjson) or pipe operations in (reclaim batch < ops.csv)."
+                        .to_string(),
+                ),
+            });
+        }
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|error| CliError::InvalidInput {
+                message: format!("Could not read batch operations from stdin: {error}"),
+                hint: None,
+            })?;
+        text
+    } else {
+        fs::read_to_string(input).map_err(|error| CliError::InvalidInput {
+            message: format!("Could not read batch file {input}: {error}"),
+            hint: Some("Check the path, or pass - to read operations from stdin.".to_string()),
+        })?
+    };
+
+    Ok(text.trim_start_matches('\u{feff}').to_string())
+}
+
+fn detect_format(input: &str, text: &str) -> BatchInputFormat {
+    let csv_file = Path::new(input)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+    let first_line = text.lines().map(str::trim).find(|line| !line.is_empty());
+    match first_line {
+        _ if csv_file => BatchInputFormat::Csv,
+        Some(line) if !line.starts_with('{') => BatchInputFormat::Csv,
+        _ => BatchInputFormat::Ndjson,
+    }
+}
+
+type Record = Result<Map<String, Value>, CliError>;
+
+/// One record per non-blank line.
+fn ndjson_records(text: &str) -> Vec<(usize, Record)> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let record = match serde_json::from_str::<Value>(line) {
+                Ok(Value::Object(record)) => Ok(record),
+                Ok(_) => Err(invalid("Expected a JSON object.".to_string())),
+                Err(error) => Err(invalid(format!(
+                    "Invalid JSON at column {}: {}.",
+                    error.column(),
+                    fields::json_error_reason(&error)
+                ))),
+            };
+            (index + 1, record)
+        })
+        .collect()
+}
+
+/// One record per CSV row after the header, keyed by the header names. Empty cells are
+/// left out; the rest are read like `--set` values.
+fn csv_records(text: &str) -> Result<Vec<(usize, Record)>, CliError> {
+    let mut rows = csv_rows(text)
+        .map_err(|reason| CliError::InvalidInput {
+            message: format!("Invalid batch CSV: {reason}."),
+            hint: Some("Quote cells that contain commas, quotes or line breaks.".to_string()),
+        })?
+        .into_iter()
+        .filter(|(_, cells)| cells.iter().any(|cell| !cell.trim().is_empty()));
+    let Some((_, header)) = rows.next() else {
+        return Ok(Vec::new());
+    };
+    let header = header
+        .iter()
+        .map(|name| name.trim().to_string())
+        .collect::<Vec<_>>();
+    if !header.iter().any(|name| name == "op") {
+        return Err(CliError::InvalidInput {
+            message: "Invalid batch CSV: the header row has no \"op\" column.".to_string(),
+            hint: Some("Start the file with a header such as: op,id,priority,due".to_string()),
+        });
+    }
+
+    Ok(rows
+        .map(|(line, cells)| {
+            if cells.len() != header.len() {
+                return (
+                    line,
+                    Err(invalid(format!(
+                        "Expected {} cells like the header, found {}.",
+                        header.len(),
+                        cells.len()
+                    ))),
+                );
+            }
+            let record = header
+                .iter()
+                .zip(&cells)
+                .filter(|(_, cell)| !cell.trim().is_empty())
+                .map(|(name, cell)| {
+                    let value = match name.as_str() {
+                        "op" | "id" | "notificationKey" => Value::String(cell.trim().to_string()),
+                        _ => fields::parse_set_value(cell.trim()),
+                    };
+                    (name.clone(), value)
+                })
+                .collect();
+            (line, Ok(record))
+        })
+        .collect())
+}
+
+/// Splits CSV text into rows of cells (RFC 4180 quoting), each with the line it starts on.
+fn csv_rows(text: &str) -> Result<Vec<(usize, Vec<String>)>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut row_start = 1;
+
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if cell.is_empty() => quoted = true,
+            ',' if !quoted => row.push(std::mem::take(&mut cell)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut cell));
+                rows.push((row_start, std::mem::take(&mut row)));
+                line += 1;
+                row_start = line;
+            }
+            ch => {
+                if ch == '\n' {
+                    line += 1;
+                }
+                cell.push(ch);
+            }
+        }
+    }
+    if quoted {
+        return Err(format!("unterminated quote in the row on line {row_start}"));
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push((row_start, row));
+    }
+
+    Ok(rows)
+}
+
+/// Checks a record and turns it into an operation, so bad input fails before any request.
+fn parse_operation(
+    mut record: Map<String, Value>,
+    default_category: EventCategory,
+    time: &TimeContext,
+) -> Result<Operation, CliError> {
+    let op = match record.remove("op") {
+        Some(Value::String(op)) => op.trim().to_ascii_lowercase(),
+        Some(other) => return Err(invalid(format!("Invalid \"op\" {other}."))),
+        None => return Err(invalid("Missing \"op\".".to_string())),
+    };
+    let task_id = record.remove("id").map(parse_task_id).transpose()?;
+    let notification_key = match record.remove("notificationKey") {
+        Some(Value::String(key)) => Some(key),
+        Some(other) => return Err(invalid(format!("Invalid \"notificationKey\" {other}."))),
+        None => None,
+    };
+    for field in TASK_TIME_FIELDS {
+        if let Some(Value::String(raw)) = record.get_mut(field) {
+            *raw = datetime::normalize_timestamp(time, field, raw)?;
+        }
+    }
+
+    let require_id = || task_id.ok_or_else(|| invalid(format!("{op} needs an \"id\".")));
+    let require_fields = |fields: Map<String, Value>| {
+        if fields.is_empty() {
+            Err(invalid(format!("{op} needs at least one task field.")))
+        } else {
+            Ok(fields)
+        }
+    };
+    let reject_fields = |fields: &Map<String, Value>| match fields.keys().next() {
+        Some(field) => Err(invalid(format!(
+            "{op} takes no task fields, found \"{field}\"."
+        ))),
+        None => Ok(()),
+    };
+    let reject_notification_key = || match notification_key {
+        Some(_) => Err(invalid(format!(
+            "{op} does not take a \"notificationKey\"."
+        ))),
+        None => Ok(()),
+    };
+
+    match op.as_str() {
+        "create" => {
+            if task_id.is_some() {
+                return Err(invalid(
+                    "create takes no \"id\"; Reclaim assigns one.".to_string(),
+                ));
+            }
+            reject_notification_key()?;
+            let mut request: CreateTaskRequest = serde_json::from_value(Value::Object(record))
+                .map_err(|error| invalid(format!("Invalid create fields: {error}.")))?;
+            request
+                .event_category
+                .get_or_insert_with(|| default_category.as_str().to_string());
+            Ok(Operation::Create(request))
+        }
+        "patch" => Ok(Operation::Patch {
+            task_id: require_id()?,
+            fields: require_fields(record)?,
+            notification_key,
+        }),
+        "put" => Ok(Operation::Put {
+            task_id: require_id()?,
+            fields: require_fields(record)?,
+            notification_key,
+        }),
+        "delete" => {
+            reject_fields(&record)?;
+            Ok(Operation::Delete {
+                task_id: require_id()?,
+                notification_key,
+            })
+        }
+        "done" => {
+            reject_fields(&record)?;
+            reject_notification_key()?;
+            Ok(Operation::Done {
+                task_id: require_id()?,
+            })
+        }
+        _ => Err(invalid(format!(
+            "Unknown op '{op}': use create, patch, put, delete or done."
+        ))),
+    }
+}
+
+fn parse_task_id(value: Value) -> Result<u64, CliError> {
+    match &value {
+        Value::Number(number) => number.as_u64(),
+        Value::String(text) => text.trim().trim_start_matches('#').parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| invalid(format!("Invalid \"id\" {value}: expected a task ID.")))
+}
+
+fn invalid(message: String) -> CliError {
+    CliError::InvalidInput {
+        message,
+        hint: Some(OPS_HINT.to_string()),
+    }
+}
+
+fn print_result_human(result: &BatchResult) {
+    let target = match (result.op, result.task_id) {
+        (Some(op), Some(task_id)) => format!("{op} #{task_id}"),
+        (Some(op), None) => op.to_string(),
+        (None, _) => String::new(),
+    };
+    let detail = match result.status {
+        BatchStatus::Ok => result
+            .task
+            .as_ref()
+            .map(|task| format!(" ({})", task.title))
+            .unwrap_or_default(),
+        BatchStatus::Failed if target.is_empty() => result.error.clone().unwrap_or_default(),
+        BatchStatus::Failed => format!(": {}", result.error.as_deref().unwrap_or_default()),
+        BatchStatus::Skipped => String::new(),
+        BatchStatus::DryRun => result
+            .request
+            .as_ref()
+            .map(|request| format!(": {} {}", request.method, request.url))
+            .unwrap_or_default(),
+    };
+
+    println!(
+        "{:<7} line {}: {target}{detail}",
+        result.status.as_str(),
+        result.line
+    );
+    if let Some(hint) = result.hint.as_deref() {
+        println!("        Hint: {hint}");
+    }
+}
+
+fn summary(report: &[BatchResult]) -> String {
+    let count = |status| {
+        report
+            .iter()
+            .filter(|result| result.status == status)
+            .count()
+    };
+    let parts = [
+        (count(BatchStatus::Ok), "ok"),
+        (count(BatchStatus::Failed), "failed"),
+        (count(BatchStatus::Skipped), "skipped"),
+        (count(BatchStatus::DryRun), "dry run"),
+    ]
+    .into_iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, label)| format!("{count} {label}"))
+    .collect::<Vec<_>>();
+
+    format!(
+        "{} operation{}: {}.",
+        report.len(),
+        if report.len() == 1 { "" } else { "s" },
+        parts.join(", ")
+    )
+}
+
+fn report_table(report: &[BatchResult]) -> output::Table {
+    let mut table = output::Table::new(&["line", "op", "task_id", "status", "error"]);
+    for result in report {
+        table.push(vec![
+            result.line.to_string(),
+            result.op.unwrap_or_default().to_string(),
+            result
+                .task_id
+                .map(|task_id| task_id.to_string())
+                .unwrap_or_default(),
+            result.status.as_str().to_string(),
+            result.error.clone().unwrap_or_default(),
+        ]);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{task, test_time, FakeApi};
+
+    fn lines(text: &str, format: BatchInputFormat) -> Vec<BatchLine> {
+        let records = match format {
+            BatchInputFormat::Ndjson => ndjson_records(text),
+            BatchInputFormat::Csv => csv_records(text).unwrap(),
+        };
+        records
+            .into_iter()
+            .map(|(line, record)| BatchLine {
+                line,
+                operation: record
+                    .and_then(|record| parse_operation(record, EventCategory::Work, &test_time())),
+            })
+            .collect()
+    }
+
+    fn fake_api() -> FakeApi {
+        FakeApi::new(vec![task(1, "Plan"), task(3, "Review"), task(7, "Ship it")])
+    }
+
+    fn statuses(report: &[BatchResult]) -> Vec<(usize, &'static str)> {
+        report
+            .iter()
+            .map(|result| (result.line, result.status.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn ndjson_lines_become_checked_operations() {
+        let text = r##"{"op":"patch","id":7,"priority":"P1","due":"tomorrow 5pm"}
+
+{"op":"create","title":"Write report"}
+{"op":"delete","id":"#9","notificationKey":"abc"}
+{"op":"done","id":7,"priority":"P1"}
+{"op":"archive","id":7}
+{"op":"patch","id":7}
+{"op":"create","title":"x","colour":"red"}
+{"op":"patch","id":7,}"##;
+        let parsed = lines(text, BatchInputFormat::Ndjson);
+
+        assert_eq!(
+            parsed.iter().map(|line| line.line).collect::<Vec<_>>(),
+            [1, 3, 4, 5, 6, 7, 8, 9]
+        );
+        let Ok(Operation::Patch { fields, .. }) = &parsed[0].operation else {
+            panic!("expected a patch: {:?}", parsed[0].operation);
+        };
+        assert_eq!(
+            Value::Object(fields.clone()),
+            serde_json::json!({ "priority": "P1", "due": "2026-02-19T22:00:00Z" })
+        );
+        let Ok(Operation::Create(request)) = &parsed[1].operation else {
+            panic!("expected a create");
+        };
+        assert_eq!(request.event_category.as_deref(), Some("WORK"));
+        assert!(matches!(
+            &parsed[2].operation,
+            Ok(Operation::Delete { task_id: 9, notification_key: Some(key) }) if key == "abc"
+        ));
+
+        let error = |index: usize| parsed[index].operation.as_ref().unwrap_err().to_string();
+        assert_eq!(error(3), "done takes no task fields, found \"priority\".");
+        assert!(error(4).starts_with("Unknown op 'archive'"));
+        assert_eq!(error(5), "patch needs at least one task field.");
+        assert!(error(6).contains("unknown field `colour`"));
+        assert_eq!(error(7), "Invalid JSON at column 22: trailing comma.");
+    }
+
+    #[test]
+    fn csv_rows_use_the_header_and_skip_empty_cells() {
+        let text = "op,id,priority,notes\r\n\
+                    patch,7,P1,\"Call Ann, then \"\"ship\"\"\nfast\"\r\n\
+                    done,8,,\n\
+                    \n\
+                    patch,9\n";
+        let parsed = lines(text, BatchInputFormat::Csv);
+
+        assert_eq!(
+            parsed.iter().map(|line| line.line).collect::<Vec<_>>(),
+            [2, 4, 6]
+        );
+        let Ok(Operation::Patch {
+            task_id, fields, ..
+        }) = &parsed[0].operation
+        else {
+            panic!("expected a patch");
+        };
+        assert_eq!(*task_id, 7);
+        assert_eq!(
+            Value::Object(fields.clone()),
+            serde_json::json!({ "priority": "P1", "notes": "Call Ann, then \"ship\"\nfast" })
+        );
+        assert!(matches!(
+            parsed[1].operation,
+            Ok(Operation::Done { task_id: 8 })
+        ));
+        assert_eq!(
+            parsed[2].operation.as_ref().unwrap_err().to_string(),
+            "Expected 4 cells like the header, found 2."
+        );
+
+        let error = csv_records("id,priority\n7,P1\n").unwrap_err();
+        assert!(error.to_string().contains("no \"op\" column"));
+        let error = csv_records("op,notes\npatch,\"open\n").unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("unterminated quote in the row on line 2"));
+        assert_eq!(
+            detect_format("-", "\n{\"op\":\"done\",\"id\":1}"),
+            BatchInputFormat::Ndjson
+        );
+        assert_eq!(detect_format("-", "op,id\n"), BatchInputFormat::Csv);
+        assert_eq!(detect_format("ops.CSV", "{"), BatchInputFormat::Csv);
+    }
+
+    #[tokio::test]
+    async fn execute_continues_past_failures_and_reports_in_input_order() {
+        let api = fake_api();
+        let text = "{\"op\":\"patch\",\"id\":404,\"priority\":\"P1\"}\n\
+                    {\"op\":\"put\",\"id\":7,\"priority\":\"P2\"}\n\
+                    {\"op\":\"bogus\"}\n\
+                    {\"op\":\"create\",\"title\":\"Write report\"}\n";
+        let mut seen = Vec::new();
+        let report = execute(
+            &api,
+            lines(text, BatchInputFormat::Ndjson),
+            3,
+            false,
+            |result| seen.push(result.line),
+        )
+        .await;
+
+        assert_eq!(seen, [1, 2, 3, 4]);
+        assert_eq!(
+            statuses(&report),
+            [(1, "failed"), (2, "ok"), (3, "failed"), (4, "ok")]
+        );
+        assert_eq!(
+            report[0].error.as_deref(),
+            Some("Reclaim API returned HTTP 404: Task not found")
+        );
+        assert_eq!(report[3].task_id, Some(8));
+        let mut calls = api.calls();
+        calls.sort();
+        assert_eq!(calls, ["GET 7", "PATCH 404", "POST Write report", "PUT 7"]);
+    }
+
+    #[tokio::test]
+    async fn fail_fast_skips_operations_after_the_first_failure() {
+        let text = "{\"op\":\"done\",\"id\":1}\n\
+                    {\"op\":\"done\",\"id\":404}\n\
+                    {\"op\":\"done\",\"id\":3}\n";
+
+        let api = fake_api();
+        let report = execute(&api, lines(text, BatchInputFormat::Ndjson), 1, true, |_| {}).await;
+        assert_eq!(
+            statuses(&report),
+            [(1, "ok"), (2, "failed"), (3, "skipped")]
+        );
+        assert_eq!(api.calls(), ["DONE 1", "DONE 404"]);
+
+        let invalid = format!("{text}{{\"op\":\"done\"}}\n");
+        let api = fake_api();
+        let report = execute(
+            &api,
+            lines(&invalid, BatchInputFormat::Ndjson),
+            4,
+            true,
+            |_| {},
+        )
+        .await;
+        assert_eq!(
+            statuses(&report),
+            [
+                (1, "skipped"),
+                (2, "skipped"),
+                (3, "skipped"),
+                (4, "failed")
+            ]
+        );
+        assert!(api.calls().is_empty());
+
+        let api = fake_api().dry_run();
+        let report = execute(&api, lines(text, BatchInputFormat::Ndjson), 2, true, |_| {}).await;
+        assert_eq!(
+            statuses(&report),
+            [(1, "dry_run"), (2, "dry_run"), (3, "dry_run")]
+        );
+        assert_eq!(report[0].request.as_ref().unwrap().method, "DONE");
+    }
+}
@@ -0,0 +1,767 @@
+use crate::{
+    config::write_private_file,
+    error::CliError,
+    reclaim_api::{
+        CreateTaskRequest, CurrentUser, Event, EventListQuery, Outcome, PlannerAction, ReclaimApi,
+        Task, TaskFilter,
+    },
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+const CACHE_DIR_NAME: &str = "reclaim";
+/// Event lists are cached per query; the oldest are dropped beyond this many.
+const MAX_EVENT_LISTS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Serve cached lists younger than `max_age`, otherwise fetch and update the cache.
+    Online { max_age: Duration },
+    /// Serve everything from the cache and refuse writes.
+    Offline,
+}
+
+/// A list as fetched from Reclaim, with the time it was fetched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedList<T> {
+    pub fetched_at: DateTime<Utc>,
+    pub items: Vec<T>,
+}
+
+impl<T> CachedList<T> {
+    fn new(items: Vec<T>) -> Self {
+        Self {
+            fetched_at: Utc::now(),
+            items,
+        }
+    }
+
+    pub fn age(&self) -> Duration {
+        (Utc::now() - self.fetched_at)
+            .to_std()
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Whether the list may be served instead of fetching. A zero `max_age` always fetches,
+    /// and a list fetched "in the future" (clock skew, a copied cache) is never fresh.
+    fn is_fresh(&self, max_age: Duration) -> bool {
+        !max_age.is_zero()
+            && (Utc::now() - self.fetched_at)
+                .to_std()
+                .is_ok_and(|age| age <= max_age)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    /// Entries from another base URL belong to another account or server and are ignored.
+    #[serde(default)]
+    base_url: String,
+    /// The full `GET /tasks` list; `TaskFilter::Active` is applied when reading.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tasks: Option<CachedList<Task>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    events: Vec<CachedEvents>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedEvents {
+    query: String,
+    #[serde(flatten)]
+    list: CachedList<Event>,
+}
+
+/// Last task and event lists for one profile, stored as JSON under the cache directory.
+/// Without a `path` the lists only live for the current command.
+#[derive(Debug)]
+pub struct TaskCache {
+    path: Option<PathBuf>,
+    file: Mutex<CacheFile>,
+}
+
+impl TaskCache {
+    /// Opens the cache for `profile` in `$XDG_CACHE_HOME/reclaim`, falling back to
+    /// `~/.cache/reclaim`.
+    pub fn open(profile: Option<&str>, base_url: &str) -> Result<Self, CliError> {
+        let file_name = profile
+            .unwrap_or("default")
+            .chars()
+            .map(|ch| {
+                if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_') {
+                    ch
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+        Ok(Self::at(
+            cache_dir()?.join(format!("{file_name}.json")),
+            base_url,
+        ))
+    }
+
+    /// Loads the cache at `path`. A missing, unreadable or foreign cache starts empty.
+    pub fn at(path: PathBuf, base_url: &str) -> Self {
+        let file = fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<CacheFile>(&raw).ok())
+            .filter(|file| file.base_url == base_url)
+            .unwrap_or_else(|| CacheFile {
+                base_url: base_url.to_string(),
+                ..CacheFile::default()
+            });
+
+        Self {
+            path: Some(path),
+            file: Mutex::new(file),
+        }
+    }
+
+    /// A cache that is never read from or written to disk, for when there is no cache
+    /// directory. Commands then always fetch from Reclaim.
+    pub fn in_memory(base_url: &str) -> Self {
+        Self {
+            path: None,
+            file: Mutex::new(CacheFile {
+                base_url: base_url.to_string(),
+                ..CacheFile::default()
+            }),
+        }
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    fn tasks(&self) -> Option<CachedList<Task>> {
+        self.lock().tasks.clone()
+    }
+
+    fn events(&self, query: &str) -> Option<CachedList<Event>> {
+        self.lock()
+            .events
+            .iter()
+            .find(|entry| entry.query == query)
+            .map(|entry| entry.list.clone())
+    }
+
+    fn find_event(&self, calendar_id: u64, event_id: &str) -> Option<Event> {
+        self.lock()
+            .events
+            .iter()
+            .flat_map(|entry| &entry.list.items)
+            .find(|event| {
+                event.calendar_id == Some(calendar_id)
+                    && event.event_id.as_deref() == Some(event_id)
+            })
+            .cloned()
+    }
+
+    fn store_tasks(&self, tasks: Vec<Task>) {
+        self.update(|file| file.tasks = Some(CachedList::new(tasks)));
+    }
+
+    fn store_events(&self, query: String, events: Vec<Event>) {
+        self.update(|file| {
+            file.events.retain(|entry| entry.query != query);
+            file.events.push(CachedEvents {
+                query,
+                list: CachedList::new(events),
+            });
+            file.events.sort_by_key(|entry| entry.list.fetched_at);
+            let excess = file.events.len().saturating_sub(MAX_EVENT_LISTS);
+            file.events.drain(..excess);
+        });
+    }
+
+    /// Replaces or adds `task` in the cached list. After our own writes (`written`), cached
+    /// event lists are dropped too, since Reclaim may have rescheduled the task's events.
+    fn store_task(&self, task: &Task, written: bool) {
+        self.update(|file| {
+            if let Some(tasks) = file.tasks.as_mut() {
+                match tasks.items.iter_mut().find(|cached| cached.id == task.id) {
+                    Some(cached) => *cached = task.clone(),
+                    None => tasks.items.push(task.clone()),
+                }
+            }
+            if written {
+                file.events.clear();
+            }
+        });
+    }
+
+    fn remove_task(&self, task_id: u64) {
+        self.update(|file| {
+            if let Some(tasks) = file.tasks.as_mut() {
+                tasks.items.retain(|task| task.id != task_id);
+            }
+            file.events.clear();
+        });
+    }
+
+    fn clear_events(&self) {
+        self.update(|file| file.events.clear());
+    }
+
+    /// Applies `change` and writes the file. The cache is disposable, so a failed write
+    /// only warns instead of failing the command that already succeeded.
+    ///
+    /// Cached tasks include titles and notes, so the directory is 0700 and the file 0600.
+    fn update(&self, change: impl FnOnce(&mut CacheFile)) {
+        let mut file = self.lock();
+        change(&mut file);
+
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), create_private_dir)
+            .and_then(|()| {
+                let rendered = serde_json::to_string(&*file).map_err(std::io::Error::other)?;
+                write_private_file(path, rendered.as_bytes())
+            });
+        if let Err(error) = result {
+            eprintln!(
+                "Warning: could not update the cache at {}: {error}",
+                path.display()
+            );
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheFile> {
+        self.file
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Wraps an API client with the on-disk cache: list reads go through `CacheMode`, and
+/// successful writes update the cached lists.
+pub struct CachedApi<A> {
+    inner: A,
+    cache: TaskCache,
+    mode: CacheMode,
+    stale_fallback: bool,
+}
+
+impl<A: ReclaimApi> CachedApi<A> {
+    pub fn new(inner: A, cache: TaskCache, mode: CacheMode) -> Self {
+        Self {
+            inner,
+            cache,
+            mode,
+            stale_fallback: true,
+        }
+    }
+
+    /// Whether an unreachable API falls back to older cached lists, with a warning on
+    /// stderr. The dashboard turns this off and reports the failed refresh itself.
+    pub fn with_stale_fallback(mut self, stale_fallback: bool) -> Self {
+        self.stale_fallback = stale_fallback;
+        self
+    }
+
+    fn ensure_online(&self, action: &str) -> Result<(), CliError> {
+        match self.mode {
+            CacheMode::Online { .. } => Ok(()),
+            CacheMode::Offline => Err(CliError::InvalidInput {
+                message: format!("Cannot {action} while --offline."),
+                hint: Some("Drop --offline to send changes to Reclaim.".to_string()),
+            }),
+        }
+    }
+
+    fn offline_miss(&self, what: &str) -> CliError {
+        CliError::InvalidInput {
+            message: match self.cache.path() {
+                Some(path) => format!(
+                    "No cached {what} in {}; --offline only serves what was fetched before.",
+                    path.display()
+                ),
+                None => format!("No cached {what}; there is no cache directory."),
+            },
+            hint: Some(format!(
+                "Run the same command without --offline once to cache {what}."
+            )),
+        }
+    }
+
+    /// Shared read path: fresh cache, then the network, then stale cache on network errors.
+    async fn read_list<T: Clone>(
+        &self,
+        what: &str,
+        cached: Option<CachedList<T>>,
+        fetch: impl std::future::Future<Output = Result<Vec<T>, CliError>>,
+        store: impl FnOnce(Vec<T>),
+    ) -> Result<Vec<T>, CliError> {
+        let max_age = match self.mode {
+            CacheMode::Offline => {
+                return cached
+                    .map(|list| list.items)
+                    .ok_or_else(|| self.offline_miss(what));
+            }
+            CacheMode::Online { max_age } => max_age,
+        };
+        if let Some(list) = cached.as_ref().filter(|list| list.is_fresh(max_age)) {
+            return Ok(list.items.clone());
+        }
+
+        match fetch.await {
+            Ok(items) => {
+                store(items.clone());
+                Ok(items)
+            }
+            Err(error @ CliError::Transport { .. }) if self.stale_fallback => match cached {
+                Some(list) => {
+                    let first_line = error.to_string();
+                    let first_line = first_line.lines().next().unwrap_or_default();
+                    eprintln!(
+                        "Warning: showing {what} cached {} ago. {first_line}",
+                        format_age(list.age())
+                    );
+                    Ok(list.items)
+                }
+                None => Err(error),
+            },
+            Err(error) => Err(error),
+        }
+    }
+}
+
+impl<A: ReclaimApi> ReclaimApi for CachedApi<A> {
+    async fn current_user(&self) -> Result<CurrentUser, CliError> {
+        self.ensure_online("check the API key")?;
+        self.inner.current_user().await
+    }
+
+    async fn list_tasks(&self, filter: TaskFilter) -> Result<Vec<Task>, CliError> {
+        let mut tasks = self
+            .read_list(
+                "tasks",
+                self.cache.tasks(),
+                self.inner.list_tasks(TaskFilter::All),
+                |tasks| self.cache.store_tasks(tasks),
+            )
+            .await?;
+        tasks.retain(|task| filter.includes(task));
+        Ok(tasks)
+    }
+
+    async fn cached_tasks(&self, filter: TaskFilter) -> Option<CachedList<Task>> {
+        let mut list = self.cache.tasks()?;
+        list.items.retain(|task| filter.includes(task));
+        Some(list)
+    }
+
+    async fn get_task(&self, task_id: u64) -> Result<Task, CliError> {
+        if self.mode == CacheMode::Offline {
+            return self
+                .cache
+                .tasks()
+                .and_then(|list| list.items.into_iter().find(|task| task.id == task_id))
+                .ok_or_else(|| self.offline_miss(&format!("task #{task_id}")));
+        }
+
+        let task = self.inner.get_task(task_id).await?;
+        self.cache.store_task(&task, false);
+        Ok(task)
+    }
+
+    async fn create_task(&self, request: CreateTaskRequest) -> Result<Outcome<Task>, CliError> {
+        self.ensure_online("create a task")?;
+        let outcome = self.inner.create_task(request).await?;
+        if let Outcome::Sent(task) = &outcome {
+            self.cache.store_task(task, true);
+        }
+        Ok(outcome)
+    }
+
+    async fn list_events(&self, query: EventListQuery) -> Result<Vec<Event>, CliError> {
+        let key = event_query_key(&query);
+        self.read_list(
+            "events for this range",
+            self.cache.events(&key),
+            self.inner.list_events(query),
+            |events| self.cache.store_events(key.clone(), events),
+        )
+        .await
+    }
+
+    async fn get_event(
+        &self,
+        calendar_id: u64,
+        event_id: &str,
+        source_details: Option<bool>,
+        thin: Option<bool>,
+    ) -> Result<Event, CliError> {
+        if self.mode == CacheMode::Offline {
+            return self
+                .cache
+                .find_event(calendar_id, event_id)
+                .ok_or_else(|| self.offline_miss(&format!("event {calendar_id}/{event_id}")));
+        }
+
+        self.inner
+            .get_event(calendar_id, event_id, source_details, thin)
+            .await
+    }
+
+    async fn apply_schedule_actions(
+        &self,
+        request: serde_json::Value,
+    ) -> Result<Outcome<serde_json::Value>, CliError> {
+        self.ensure_online("change events")?;
+        let outcome = self.inner.apply_schedule_actions(request).await?;
+        if matches!(outcome, Outcome::Sent(_)) {
+            self.cache.clear_events();
+        }
+        Ok(outcome)
+    }
+
+    async fn put_task(
+        &self,
+        task_id: u64,
+        request: serde_json::Value,
+        notification_key: Option<&str>,
+    ) -> Result<Outcome<Task>, CliError> {
+        self.ensure_online("update a task")?;
+        let outcome = self
+            .inner
+            .put_task(task_id, request, notification_key)
+            .await?;
+        if let Outcome::Sent(task) = &outcome {
+            self.cache.store_task(task, true);
+        }
+        Ok(outcome)
+    }
+
+    async fn patch_task(
+        &self,
+        task_id: u64,
+        request: serde_json::Value,
+        notification_key: Option<&str>,
+    ) -> Result<Outcome<Task>, CliError> {
+        self.ensure_online("update a task")?;
+        let outcome = self
+            .inner
+            .patch_task(task_id, request, notification_key)
+            .await?;
+        if let Outcome::Sent(task) = &outcome {
+            self.cache.store_task(task, true);
+        }
+        Ok(outcome)
+    }
+
+    async fn delete_task(
+        &self,
+        task_id: u64,
+        notification_key: Option<&str>,
+    ) -> Result<Outcome<serde_json::Value>, CliError> {
+        self.ensure_online("delete a task")?;
+        let outcome = self.inner.delete_task(task_id, notification_key).await?;
+        if matches!(outcome, Outcome::Sent(_)) {
+            self.cache.remove_task(task_id);
+        }
+        Ok(outcome)
+    }
+
+    async fn run_planner_action(
+        &self,
+        task_id: u64,
+        action: PlannerAction,
+    ) -> Result<Outcome<Task>, CliError> {
+        self.ensure_online("update a task")?;
+        let outcome = self.inner.run_planner_action(task_id, action).await?;
+        if let Outcome::Sent(task) = &outcome {
+            self.cache.store_task(task, true);
+        }
+        Ok(outcome)
+    }
+
+    async fn snooze_task(&self, task_id: u64, until: &str) -> Result<Outcome<Task>, CliError> {
+        self.ensure_online("snooze a task")?;
+        let outcome = self.inner.snooze_task(task_id, until).await?;
+        if let Outcome::Sent(task) = &outcome {
+            self.cache.store_task(task, true);
+        }
+        Ok(outcome)
+    }
+
+    async fn log_work(&self, task_id: u64, minutes: u32) -> Result<Outcome<Task>, CliError> {
+        self.ensure_online("log work")?;
+        let outcome = self.inner.log_work(task_id, minutes).await?;
+        if let Outcome::Sent(task) = &outcome {
+            self.cache.store_task(task, true);
+        }
+        Ok(outcome)
+    }
+}
+
+/// Creates `dir` and its missing parents, then limits `dir` itself to its owner on Unix.
+pub(crate) fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+        builder.mode(0o700);
+        builder.create(dir)?;
+        // `mode` only applies to directories it creates, so tighten an existing one too.
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o700))
+    }
+    #[cfg(not(unix))]
+    builder.create(dir)
+}
+
+/// `$XDG_CACHE_HOME/reclaim`, falling back to `~/.cache/reclaim`.
+pub fn cache_dir() -> Result<PathBuf, CliError> {
+    let base = env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .ok_or_else(|| CliError::InvalidInput {
+            message:
+                "Could not determine the cache directory: neither XDG_CACHE_HOME nor HOME is set."
+                    .to_string(),
+            hint: Some("Set XDG_CACHE_HOME to a writable directory.".to_string()),
+        })?;
+
+    Ok(base.join(CACHE_DIR_NAME))
+}
+
+fn event_query_key(query: &EventListQuery) -> String {
+    let calendar_ids = query
+        .calendar_ids
+        .iter()
+        .map(u64::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(
+        "calendars={calendar_ids};all={:?};start={};end={};details={:?};thin={:?}",
+        query.all_connected,
+        query.start.as_deref().unwrap_or_default(),
+        query.end.as_deref().unwrap_or_default(),
+        query.source_details,
+        query.thin
+    )
+}
+
+/// `45s`, `12m`, `3h` or `2d`.
+pub fn format_age(age: Duration) -> String {
+    match age.as_secs() {
+        seconds @ 0..=59 => format!("{seconds}s"),
+        seconds @ 60..=3_599 => format!("{}m", seconds / 60),
+        seconds @ 3_600..=86_399 => format!("{}h", seconds / 3_600),
+        seconds => format!("{}d", seconds / 86_400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{task, FakeApi};
+
+    fn task_with_status(id: u64, title: &str, status: &str) -> Task {
+        Task {
+            status: Some(status.to_string()),
+            ..task(id, title)
+        }
+    }
+
+    fn list_calls(api: &FakeApi) -> usize {
+        api.calls()
+            .iter()
+            .filter(|call| *call == "GET tasks")
+            .count()
+    }
+
+    fn temp_cache(name: &str) -> TaskCache {
+        let path = env::temp_dir()
+            .join(format!("reclaim-cache-test-{}", std::process::id()))
+            .join(format!("{name}.json"));
+        let _ = fs::remove_file(&path);
+        TaskCache::at(path, "https://api.test/api")
+    }
+
+    fn online(max_age: Duration) -> CacheMode {
+        CacheMode::Online { max_age }
+    }
+
+    #[tokio::test]
+    async fn fresh_cache_skips_the_network_and_writes_update_it() {
+        let tasks = vec![
+            task_with_status(1, "Plan", "NEW"),
+            task_with_status(2, "Old", "ARCHIVED"),
+        ];
+        let api = CachedApi::new(
+            FakeApi::new(tasks),
+            temp_cache("fresh"),
+            online(Duration::from_secs(300)),
+        );
+
+        assert_eq!(api.list_tasks(TaskFilter::Active).await.unwrap().len(), 1);
+        assert_eq!(api.list_tasks(TaskFilter::All).await.unwrap().len(), 2);
+        assert_eq!(list_calls(&api.inner), 1);
+
+        api.create_task(CreateTaskRequest {
+            title: "New".to_string(),
+            ..CreateTaskRequest::default()
+        })
+        .await
+        .unwrap();
+        api.patch_task(1, serde_json::json!({ "title": "Patched" }), None)
+            .await
+            .unwrap();
+        api.delete_task(2, None).await.unwrap();
+
+        let titles = api
+            .list_tasks(TaskFilter::All)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|task| task.title)
+            .collect::<Vec<_>>();
+        assert_eq!(titles, vec!["Patched", "New"]);
+        assert_eq!(list_calls(&api.inner), 1);
+    }
+
+    #[tokio::test]
+    async fn planned_writes_leave_the_cache_alone() {
+        let api = CachedApi::new(
+            FakeApi::new(vec![task(7, "Ship it")]).dry_run(),
+            temp_cache("planned"),
+            online(Duration::from_secs(300)),
+        );
+        api.list_tasks(TaskFilter::All).await.unwrap();
+
+        let outcome = api
+            .patch_task(7, serde_json::json!({ "title": "Patched" }), None)
+            .await
+            .unwrap();
+        assert!(matches!(outcome, Outcome::Planned(_)));
+        assert!(matches!(
+            api.delete_task(7, None).await.unwrap(),
+            Outcome::Planned(_)
+        ));
+
+        let tasks = api.list_tasks(TaskFilter::All).await.unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Ship it");
+        assert_eq!(list_calls(&api.inner), 1);
+    }
+
+    #[tokio::test]
+    async fn offline_serves_the_cache_and_refuses_writes() {
+        let cache = temp_cache("offline");
+        let path = cache.path().unwrap().to_path_buf();
+        let api = CachedApi::new(
+            FakeApi::new(vec![task(7, "Ship it")]),
+            cache,
+            online(Duration::ZERO),
+        );
+        api.list_tasks(TaskFilter::All).await.unwrap();
+
+        let offline = CachedApi::new(
+            FakeApi::new(Vec::new()),
+            TaskCache::at(path.clone(), "https://api.test/api"),
+            CacheMode::Offline,
+        );
+        assert_eq!(offline.get_task(7).await.unwrap().title, "Ship it");
+        assert_eq!(offline.list_tasks(TaskFilter::All).await.unwrap().len(), 1);
+        assert_eq!(list_calls(&offline.inner), 0);
+        let error = offline.delete_task(7, None).await.unwrap_err();
+        assert!(error.to_string().contains("while --offline"));
+        let error = offline.get_task(8).await.unwrap_err();
+        assert!(error.to_string().contains("No cached task #8"));
+
+        let other_server = CachedApi::new(
+            FakeApi::new(Vec::new()),
+            TaskCache::at(path, "https://other.test/api"),
+            CacheMode::Offline,
+        );
+        assert!(other_server.list_tasks(TaskFilter::All).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn unreachable_api_falls_back_to_stale_cache() {
+        let cache = temp_cache("stale");
+        let path = cache.path().unwrap().to_path_buf();
+        let api = CachedApi::new(
+            FakeApi::new(vec![task(7, "Ship it")]),
+            cache,
+            online(Duration::ZERO),
+        );
+        api.list_tasks(TaskFilter::All).await.unwrap();
+
+        let api = CachedApi::new(
+            FakeApi::new(Vec::new()).unreachable(),
+            TaskCache::at(path, "https://api.test/api"),
+            online(Duration::ZERO),
+        );
+        assert_eq!(api.list_tasks(TaskFilter::All).await.unwrap().len(), 1);
+
+        let api = api.with_stale_fallback(false);
+        assert!(api.list_tasks(TaskFilter::All).await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn cache_file_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let cache = temp_cache("private");
+        let path = cache.path().unwrap().to_path_buf();
+        let api = CachedApi::new(
+            FakeApi::new(vec![task(7, "Ship it")]),
+            cache,
+            online(Duration::ZERO),
+        );
+        api.list_tasks(TaskFilter::All).await.unwrap();
+
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&path), 0o600);
+        assert_eq!(mode(path.parent().unwrap()), 0o700);
+    }
+
+    #[tokio::test]
+    async fn in_memory_cache_passes_reads_through() {
+        let api = CachedApi::new(
+            FakeApi::new(vec![task(7, "Ship it")]),
+            TaskCache::in_memory("https://api.test/api"),
+            online(Duration::ZERO),
+        );
+        assert_eq!(api.list_tasks(TaskFilter::All).await.unwrap().len(), 1);
+        assert_eq!(api.list_tasks(TaskFilter::All).await.unwrap().len(), 1);
+        assert_eq!(list_calls(&api.inner), 2);
+        assert!(api.cache.path().is_none());
+    }
+
+    #[test]
+    fn only_lists_within_max_age_are_fresh() {
+        let fetched = |seconds_ago: i64| CachedList::<Task> {
+            fetched_at: Utc::now() - chrono::Duration::seconds(seconds_ago),
+            items: Vec::new(),
+        };
+        let five_minutes = Duration::from_secs(300);
+        assert!(fetched(60).is_fresh(five_minutes));
+        assert!(!fetched(600).is_fresh(five_minutes));
+        assert!(!fetched(0).is_fresh(Duration::ZERO));
+        assert!(!fetched(-600).is_fresh(Duration::ZERO));
+        assert!(!fetched(-600).is_fresh(five_minutes));
+    }
+
+    #[test]
+    fn format_age_uses_the_largest_unit() {
+        assert_eq!(format_age(Duration::from_secs(45)), "45s");
+        assert_eq!(format_age(Duration::from_secs(720)), "12m");
+        assert_eq!(format_age(Duration::from_secs(3 * 3_600 + 5)), "3h");
+        assert_eq!(format_age(Duration::from_secs(2 * 86_400)), "2d");
+    }
+}
@@ -2,32 +2,69 @@ use clap::{
     builder::NonEmptyStringValueParser, value_parser, ArgAction, Args, Parser, Subcommand,
     ValueEnum,
 };
+use std::{path::PathBuf, time::Duration};
 
 const AFTER_HELP: &str = "\
 Examples:
   reclaim list
   reclaim list --filter open
   reclaim list --filter IN_PROGRESS
+  reclaim list --priority P1,P2 --due-before eow
+  reclaim list --where 'timeChunksRemaining>4' --title-contains spec
+  reclaim list --sort due,-priority --limit 10 --fields id,title,due
   reclaim dashboard
   reclaim list --format json
+  reclaim list --format csv --fields id,title,due > tasks.csv
+  reclaim events list --format table
+  reclaim create --title \"Send report\" --template '{{.id}}'
+  reclaim list --query '.[] | select(.priority == \"P1\") | .id'
   reclaim get 123
   reclaim patch 123 --set priority=P4 --set snoozeUntil=2026-02-25T17:00:00Z
+  reclaim edit 123
+  reclaim patch 123 --set priority=P1 --diff --format json
   reclaim put 123 --set priority=P2 --set due=2026-02-28T17:00:00Z
   reclaim delete 123
+  reclaim --dry-run patch 123 --set priority=P1
+  reclaim done 123
+  reclaim snooze 123 --until \"tomorrow 9am\"
+  reclaim log 123 --minutes 45
   reclaim create --title \"Plan Q1 roadmap\" --priority P2 --event-category WORK
+  reclaim create --title \"Send report\" --due \"friday 5pm\"
+  reclaim create --title \"Write design doc\" --duration 2h30m --min-chunk 30m --max-chunk 1h
+  reclaim patch 123 --set snoozeUntil=+2h
+  reclaim patch 123 --unset notes --set-string title=2026
   reclaim events list --start 2026-02-01 --end 2026-02-28 --format json
   reclaim events get 829105 r2d260ojiopn --format json
   reclaim events create --calendar-id 829105 --title \"Team sync\" --start 2026-02-21T18:30:00Z --end 2026-02-21T19:00:00Z
   reclaim events update --calendar-id 829105 --event-id r2d260ojiopn --set priority=P4
+  reclaim events update --calendar-id 829105 --event-id r2d260ojiopn --set 'attendees[+]={\"email\":\"x@y.com\"}'
   reclaim events delete --calendar-id 829105 --event-id r2d260ojiopn
+  reclaim events apply --json @actions.json
+  reclaim batch ops.csv --fail-fast
+  reclaim list --format ndjson --fields id | jq -c '{op:\"done\",id}' | reclaim batch
+  reclaim patch 123 --set notes=@notes.md
   RECLAIM_API_KEY=... reclaim list
+  reclaim config set api-key ... --profile work
+  reclaim --profile work list
+  reclaim auth login --profile work
+  reclaim --trace get 123 2> trace.log
+  reclaim list --max-age 5m
+  reclaim --offline list
+  source <(reclaim completions bash)
+
+Time input:
+  ISO 8601 (2026-02-21T18:30:00Z), local time (2026-02-21 09:30), or relative input:
+  now, today, tomorrow 5pm, next friday, eod, eow, +2h, -15m, in 3 days.
+  A time of day that has already passed today (5pm, eod) means tomorrow.
+  Local and relative input uses --timezone, falling back to the system timezone.
+
+Settings precedence:
+  flag > environment variable > config profile > built-in default
 
 Agent-friendly tip:
   Use --format json for stable machine-readable output and --json/--set for updates.
 ";
 
-const DEFAULT_POLICY_ID: &str = "00000000-0000-0000-0000-000000000000";
-
 #[derive(Debug, Parser)]
 #[command(
     name = "reclaim",
@@ -36,7 +73,8 @@ const DEFAULT_POLICY_ID: &str = "00000000-0000-0000-0000-000000000000";
     about = "Simple CLI for Reclaim.ai tasks and events.",
     long_about = "Simple CLI for Reclaim.ai tasks and events.
 
-Set your API key with RECLAIM_API_KEY or pass --api-key.
+Set your API key with RECLAIM_API_KEY, pass --api-key, or store it in a config profile
+with `reclaim config set api-key ...`.
 Use --format json when another tool/agent will parse the output.",
     after_help = AFTER_HELP
 )]
@@ -53,29 +91,134 @@ pub struct Cli {
     #[arg(
         long,
         env = "RECLAIM_BASE_URL",
-        default_value = "https://api.app.reclaim.ai/api",
         global = true,
-        help = "Reclaim API base URL."
+        help = "Reclaim API base URL. Defaults to https://api.app.reclaim.ai/api."
     )]
-    pub base_url: String,
+    pub base_url: Option<String>,
 
     #[arg(
         long,
         env = "RECLAIM_TIMEOUT_SECS",
-        default_value_t = 15,
         global = true,
-        help = "HTTP timeout in seconds."
+        help = "HTTP timeout in seconds. Defaults to 15."
+    )]
+    pub timeout_secs: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        env = "RECLAIM_RETRIES",
+        global = true,
+        help = "Retries after HTTP 429/502/503/504 or a connection failure. Defaults to 3; 0 disables retries."
+    )]
+    pub retries: Option<u32>,
+
+    #[arg(
+        long,
+        value_name = "SECS",
+        env = "RECLAIM_MAX_RETRY_WAIT",
+        global = true,
+        help = "Longest wait before a retry, in seconds. A longer Retry-After stops retrying. Defaults to 30."
     )]
-    pub timeout_secs: u64,
+    pub max_retry_wait: Option<u64>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Also retry POST and PATCH requests. They can apply twice if a failed attempt reached Reclaim."
+    )]
+    pub retry_non_idempotent: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Print the HTTP request a change would send (method, URL and JSON body) instead of sending it. Reads still happen."
+    )]
+    pub dry_run: bool,
 
     #[arg(
         long,
         value_enum,
-        default_value_t = OutputFormat::Human,
+        env = "RECLAIM_FORMAT",
+        global = true,
+        help = "Output format: human, json, ndjson, yaml, table, csv or tsv. Defaults to human."
+    )]
+    pub format: Option<OutputFormat>,
+
+    #[arg(
+        long,
+        value_name = "TEMPLATE",
+        global = true,
+        conflicts_with = "query",
+        help = "Print results through a template, e.g. '{{.id}} {{.title}}'. Lists print one line per item. Overrides --format."
+    )]
+    pub template: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "FILTER",
+        global = true,
+        help = "Print values selected by a jq-style filter, e.g. '.[] | select(.priority == \"P1\") | .id'. Overrides --format."
+    )]
+    pub query: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Serve tasks and events from the local cache without contacting Reclaim. Changes are refused."
+    )]
+    pub offline: bool,
+
+    #[arg(
+        long,
+        value_name = "AGE",
+        env = "RECLAIM_MAX_AGE",
+        global = true,
+        value_parser = parse_max_age,
+        help = "Reuse cached task and event lists younger than this, e.g. 30s, 5m or 1h. Defaults to 0 (always fetch). Older lists are refetched first and only shown if Reclaim cannot be reached."
+    )]
+    pub max_age: Option<Duration>,
+
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "Print each HTTP request's method, URL, status and latency to stderr."
+    )]
+    pub verbose: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Like --verbose, plus headers and request/response bodies. The API key is always redacted."
+    )]
+    pub trace: bool,
+
+    #[arg(
+        long,
+        value_name = "TZ",
+        env = "RECLAIM_TIMEZONE",
+        global = true,
+        help = "IANA timezone for local and relative time input, e.g. America/New_York. Defaults to the system timezone."
+    )]
+    pub timezone: Option<String>,
+
+    #[arg(
+        long,
+        env = "RECLAIM_PROFILE",
         global = true,
-        help = "Output format."
+        help = "Config profile to use. Falls back to default_profile in the config file."
     )]
-    pub format: OutputFormat,
+    pub profile: Option<String>,
+
+    #[arg(
+        long = "config",
+        value_name = "PATH",
+        env = "RECLAIM_CONFIG",
+        global = true,
+        help = "Config file path. Defaults to $XDG_CONFIG_HOME/reclaim/config.toml."
+    )]
+    pub config_path: Option<PathBuf>,
 
     #[command(subcommand)]
     pub command: Command,
@@ -102,8 +245,29 @@ pub enum Command {
         long_about = "Partially update a task via PATCH.\n\nPass --json with a partial JSON object and/or repeated --set key=value entries."
     )]
     Patch(PatchArgs),
+    #[command(
+        about = "Edit a task in $EDITOR and send the changes as a PATCH.",
+        long_about = "Edit a task in $EDITOR and send the changes as a PATCH.\n\nThe editable fields are shown as commented TOML. Only fields you change are sent. If the edit is invalid, the editor reopens with the error at the top; save without changes to give up. Leaving the file unchanged, or deleting everything, cancels the edit.\n\nThe editor is taken from $VISUAL, then $EDITOR, then vi."
+    )]
+    Edit(EditArgs),
     #[command(about = "Delete one task by ID.", aliases = ["del", "rm", "remove"])]
     Delete(DeleteArgs),
+    #[command(
+        about = "Mark a task as done.",
+        alias = "complete",
+        long_about = "Mark a task as done.\n\nUses Reclaim's planner, so remaining scheduled blocks are released."
+    )]
+    Done(TaskActionArgs),
+    #[command(about = "Start working on a task now.")]
+    Start(TaskActionArgs),
+    #[command(about = "Stop working on a task that was started.")]
+    Stop(TaskActionArgs),
+    #[command(about = "Snooze a task until a given time.")]
+    Snooze(SnoozeArgs),
+    #[command(about = "Log time spent on a task.")]
+    Log(LogWorkArgs),
+    #[command(about = "Reopen a completed or archived task.", alias = "unarchive")]
+    Reopen(TaskActionArgs),
     #[command(
         about = "Manage calendar events.",
         long_about = "Manage calendar events.\n\nUse create/update/delete for convenient action wrappers, or use apply for raw /schedule-actions/apply-actions requests."
@@ -111,6 +275,26 @@ pub enum Command {
     Events(EventsArgs),
     #[command(about = "Create a new task.")]
     Create(CreateArgs),
+    #[command(
+        about = "Run many task operations from an NDJSON or CSV file.",
+        long_about = "Run many task operations from an NDJSON or CSV file.\n\nEach NDJSON line is an object with \"op\" (create, patch, put, delete or done), \"id\" (except for create), an optional \"notificationKey\" (patch, put, delete) and task fields in API form, e.g.\n  {\"op\":\"patch\",\"id\":123,\"priority\":\"P1\"}\n  {\"op\":\"create\",\"title\":\"Write report\",\"due\":\"friday\"}\nCSV files have a header row with the same names; empty cells are left out, and other cells are read like --set values.\n\npatch sends the fields, put sends the current task with the fields replaced, and due/snoozeUntil/startAfter accept the same input as --due. Every line is checked before anything is sent. The report lists each line's result; reclaim exits non-zero if any line failed."
+    )]
+    Batch(BatchArgs),
+    #[command(
+        about = "Show or edit the config file and its profiles.",
+        long_about = "Show or edit the config file and its profiles.\n\nThe config file lives at $XDG_CONFIG_HOME/reclaim/config.toml (or ~/.config/reclaim/config.toml).\nset/unset operate on the profile selected by --profile, falling back to default_profile and then \"default\"."
+    )]
+    Config(ConfigArgs),
+    #[command(
+        about = "Log in, log out, or check which API key is active.",
+        long_about = "Log in, log out, or check which API key is active.\n\nlogin prompts for the key without echo, validates it against the Reclaim API and stores it for the active profile in a 0600 credentials file (or the OS keyring when built with the keyring feature)."
+    )]
+    Auth(AuthArgs),
+    #[command(
+        about = "Print a shell completion script.",
+        long_about = "Print a shell completion script.\n\nThe script calls back into reclaim while completing, so task IDs (get, patch, done, ...) and --calendar-id values are completed with titles from the local cache or the Reclaim API. Load it from your shell startup file so it stays in sync with the installed binary:\n\n  bash:       source <(reclaim completions bash)\n  zsh:        source <(reclaim completions zsh)\n  fish:       reclaim completions fish | source\n  elvish:     eval (reclaim completions elvish | slurp)\n  powershell: reclaim completions powershell | Out-String | Invoke-Expression"
+    )]
+    Completions(CompletionsArgs),
 }
 
 #[derive(Debug, Args)]
@@ -121,9 +305,115 @@ pub struct ListArgs {
         help = "Optional task status filter (exact status or bucket)."
     )]
     pub filter: Option<TaskStatusFilter>,
+
+    #[command(flatten)]
+    pub query: TaskQueryArgs,
+
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "KEYS",
+        allow_hyphen_values = true,
+        help = "Sort by comma-separated fields; prefix with - for descending, e.g. due,-priority,title."
+    )]
+    pub sort: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Show at most N tasks (after filtering and sorting)."
+    )]
+    pub limit: Option<usize>,
+
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "FIELDS",
+        help = "Only output these fields, e.g. id,title,due,priority,timeChunksRemaining."
+    )]
+    pub fields: Vec<String>,
+}
+
+/// Client-side task filters shared by `list` and `dashboard`. All filters must match.
+#[derive(Debug, Clone, Default, Args)]
+pub struct TaskQueryArgs {
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        help_heading = "Filters",
+        help = "Only tasks with one of these priorities, e.g. P1,P2."
+    )]
+    pub priority: Vec<Priority>,
+
+    #[arg(
+        long,
+        value_enum,
+        help_heading = "Filters",
+        help = "Only tasks in this category."
+    )]
+    pub category: Option<EventCategory>,
+
+    #[arg(
+        long = "due-before",
+        value_name = "TIME",
+        help_heading = "Filters",
+        allow_hyphen_values = true,
+        help = "Only tasks due before this time, e.g. eow or 2026-03-01."
+    )]
+    pub due_before: Option<String>,
+
+    #[arg(
+        long = "due-after",
+        value_name = "TIME",
+        help_heading = "Filters",
+        allow_hyphen_values = true,
+        help = "Only tasks due after this time, e.g. today."
+    )]
+    pub due_after: Option<String>,
+
+    #[arg(
+        long,
+        help_heading = "Filters",
+        help = "Only open tasks whose due time has passed."
+    )]
+    pub overdue: bool,
+
+    #[arg(
+        long = "title-contains",
+        value_name = "TEXT",
+        help_heading = "Filters",
+        help = "Only tasks whose title contains TEXT (case-insensitive)."
+    )]
+    pub title_contains: Option<String>,
+
+    #[arg(
+        long = "notes-contains",
+        value_name = "TEXT",
+        help_heading = "Filters",
+        help = "Only tasks whose notes contain TEXT (case-insensitive)."
+    )]
+    pub notes_contains: Option<String>,
+
+    #[arg(
+        long = "id",
+        value_delimiter = ',',
+        help_heading = "Filters",
+        help = "Only these task IDs, e.g. --id 12,34."
+    )]
+    pub ids: Vec<u64>,
+
+    #[arg(
+        long = "where",
+        value_name = "EXPR",
+        action = ArgAction::Append,
+        help_heading = "Filters",
+        help = "Field condition over the task JSON, e.g. 'timeChunksRemaining>4'. Operators: = != > >= < <= ~ (contains). Repeatable."
+    )]
+    pub conditions: Vec<String>,
 }
 
-#[derive(Debug, Clone, Copy, ValueEnum)]
+#[derive(Debug, Clone, Copy, ValueEnum, Eq, PartialEq)]
 pub enum TaskStatusFilter {
     #[value(name = "open", alias = "incomplete")]
     Open,
@@ -148,6 +438,21 @@ pub enum TaskStatusFilter {
     Archived,
 }
 
+impl TaskStatusFilter {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskStatusFilter::Open => "open",
+            TaskStatusFilter::Completed => "completed",
+            TaskStatusFilter::New => "NEW",
+            TaskStatusFilter::Scheduled => "SCHEDULED",
+            TaskStatusFilter::InProgress => "IN_PROGRESS",
+            TaskStatusFilter::Complete => "COMPLETE",
+            TaskStatusFilter::Cancelled => "CANCELLED",
+            TaskStatusFilter::Archived => "ARCHIVED",
+        }
+    }
+}
+
 #[derive(Debug, Args)]
 pub struct DashboardArgs {
     #[arg(
@@ -156,6 +461,9 @@ pub struct DashboardArgs {
         help = "Include all tasks, including archived/cancelled/deleted."
     )]
     pub all: bool,
+
+    #[command(flatten)]
+    pub query: TaskQueryArgs,
 }
 
 #[derive(Debug, Args)]
@@ -172,7 +480,7 @@ pub struct PutArgs {
     #[arg(
         long,
         value_name = "JSON_OBJECT",
-        help = "Full task object JSON to send in PUT. Must be a JSON object."
+        help = "Full task object JSON to send in PUT. Must be a JSON object; @FILE reads it from a file, - from stdin."
     )]
     pub json: Option<String>,
 
@@ -180,15 +488,37 @@ pub struct PutArgs {
         long = "set",
         value_name = "KEY=VALUE",
         action = ArgAction::Append,
-        help = "Field override for PUT. Repeatable. Value supports JSON literals (true, null, numbers, arrays, objects)."
+        help = "Field override for PUT. Repeatable. KEY may be a dot path with [N] or [+] (append), e.g. dateRange.start or attendees[+]. Value supports JSON literals (true, null, numbers, arrays, objects); @FILE reads it from a file as a string; @@ is a literal @."
     )]
     pub set: Vec<String>,
 
+    #[arg(
+        long = "set-string",
+        value_name = "KEY=VALUE",
+        action = ArgAction::Append,
+        help = "Like --set, but always sends VALUE as a string (e.g. --set-string title=123). Repeatable. VALUE @FILE reads a file; @@ is a literal @."
+    )]
+    pub set_string: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "KEY",
+        action = ArgAction::Append,
+        help = "Field to leave out of the PUT body, or array element to remove. Repeatable. Applied in command-line order with --set."
+    )]
+    pub unset: Vec<String>,
+
     #[arg(
         long = "notification-key",
         help = "Optional notification key forwarded to the Reclaim API."
     )]
     pub notification_key: Option<String>,
+
+    #[arg(
+        long,
+        help = "With --format json or yaml, print the changed task fields as a list of {field, before, after} instead of the updated task. Human output always shows this diff."
+    )]
+    pub diff: bool,
 }
 
 #[derive(Debug, Args)]
@@ -199,7 +529,7 @@ pub struct PatchArgs {
     #[arg(
         long,
         value_name = "JSON_OBJECT",
-        help = "Partial task JSON object to send in PATCH. Must be a JSON object."
+        help = "Partial task JSON object to send in PATCH. Must be a JSON object; @FILE reads it from a file, - from stdin."
     )]
     pub json: Option<String>,
 
@@ -207,15 +537,93 @@ pub struct PatchArgs {
         long = "set",
         value_name = "KEY=VALUE",
         action = ArgAction::Append,
-        help = "Field update for PATCH. Repeatable. Value supports JSON literals (true, null, numbers, arrays, objects)."
+        help = "Field update for PATCH. Repeatable. KEY may be a dot path with [N] or [+] (append), e.g. dateRange.start or attendees[+]. Value supports JSON literals (true, null, numbers, arrays, objects); @FILE reads it from a file as a string; @@ is a literal @."
     )]
     pub set: Vec<String>,
 
+    #[arg(
+        long = "set-string",
+        value_name = "KEY=VALUE",
+        action = ArgAction::Append,
+        help = "Like --set, but always sends VALUE as a string (e.g. --set-string title=123). Repeatable. VALUE @FILE reads a file; @@ is a literal @."
+    )]
+    pub set_string: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "KEY",
+        action = ArgAction::Append,
+        help = "Field to clear (sent as null), or array element to remove. Repeatable. Applied in command-line order with --set."
+    )]
+    pub unset: Vec<String>,
+
     #[arg(
         long = "notification-key",
         help = "Optional notification key forwarded to the Reclaim API."
     )]
     pub notification_key: Option<String>,
+
+    #[arg(
+        long,
+        help = "With --format json or yaml, print the changed task fields as a list of {field, before, after} instead of the updated task. Human output always shows this diff."
+    )]
+    pub diff: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct EditArgs {
+    #[arg(help = "Task ID.")]
+    pub task_id: u64,
+
+    #[arg(
+        long = "notification-key",
+        help = "Optional notification key forwarded to the Reclaim API."
+    )]
+    pub notification_key: Option<String>,
+
+    #[arg(
+        long,
+        help = "With --format json or yaml, print the changed task fields as a list of {field, before, after} instead of the updated task. Human output always shows this diff."
+    )]
+    pub diff: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct BatchArgs {
+    #[arg(
+        value_name = "FILE",
+        default_value = "-",
+        help = "NDJSON or CSV file of operations, or - for stdin."
+    )]
+    pub input: String,
+
+    #[arg(
+        long = "input-format",
+        value_enum,
+        help = "Format of FILE. Defaults to csv for .csv files or input that does not start with a JSON object, ndjson otherwise."
+    )]
+    pub input_format: Option<BatchInputFormat>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 4,
+        value_parser = value_parser!(u16).range(1..=32),
+        help = "How many operations to run at once (1-32)."
+    )]
+    pub concurrency: u16,
+
+    #[arg(
+        long = "fail-fast",
+        help = "Stop after the first failure: operations already sent finish, the rest are skipped. With invalid lines, nothing is sent."
+    )]
+    pub fail_fast: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, Eq, PartialEq)]
+pub enum BatchInputFormat {
+    Ndjson,
+    Csv,
 }
 
 #[derive(Debug, Args)]
@@ -230,6 +638,38 @@ pub struct DeleteArgs {
     pub notification_key: Option<String>,
 }
 
+#[derive(Debug, Args)]
+pub struct TaskActionArgs {
+    #[arg(help = "Task ID.")]
+    pub task_id: u64,
+}
+
+#[derive(Debug, Args)]
+pub struct SnoozeArgs {
+    #[arg(help = "Task ID.")]
+    pub task_id: u64,
+
+    #[arg(
+        long,
+        allow_hyphen_values = true,
+        help = "When the task should come back: ISO 8601, local time, or relative input, e.g. \"tomorrow 9am\", +2h."
+    )]
+    pub until: String,
+}
+
+#[derive(Debug, Args)]
+pub struct LogWorkArgs {
+    #[arg(help = "Task ID.")]
+    pub task_id: u64,
+
+    #[arg(
+        long,
+        value_parser = value_parser!(u32).range(1..),
+        help = "Minutes worked on the task."
+    )]
+    pub minutes: u32,
+}
+
 #[derive(Debug, Args)]
 pub struct EventsArgs {
     #[command(subcommand)]
@@ -273,10 +713,18 @@ pub struct EventsListArgs {
     #[arg(long = "all-connected", help = "Include all connected calendars.")]
     pub all_connected: bool,
 
-    #[arg(long, help = "Optional start date filter (YYYY-MM-DD).")]
+    #[arg(
+        long,
+        allow_hyphen_values = true,
+        help = "Optional start date filter: YYYY-MM-DD or relative input like today, -7d."
+    )]
     pub start: Option<String>,
 
-    #[arg(long, help = "Optional end date filter (YYYY-MM-DD).")]
+    #[arg(
+        long,
+        allow_hyphen_values = true,
+        help = "Optional end date filter: YYYY-MM-DD or relative input like tomorrow, +2w."
+    )]
     pub end: Option<String>,
 
     #[arg(long = "source-details", help = "Include source details if available.")]
@@ -340,8 +788,11 @@ impl EventVisibility {
 
 #[derive(Debug, Args)]
 pub struct EventsCreateArgs {
-    #[arg(long = "calendar-id", help = "Calendar ID for the new event.")]
-    pub calendar_id: u64,
+    #[arg(
+        long = "calendar-id",
+        help = "Calendar ID for the new event. Falls back to the profile's calendar_id."
+    )]
+    pub calendar_id: Option<u64>,
 
     #[arg(
         long,
@@ -350,18 +801,25 @@ pub struct EventsCreateArgs {
     )]
     pub title: String,
 
-    #[arg(long, help = "Start timestamp (ISO 8601), e.g. 2026-02-21T18:30:00Z.")]
+    #[arg(
+        long,
+        allow_hyphen_values = true,
+        help = "Start time: ISO 8601, local time, or relative input, e.g. 2026-02-21T18:30:00Z, \"tomorrow 10am\"."
+    )]
     pub start: String,
 
-    #[arg(long, help = "End timestamp (ISO 8601), e.g. 2026-02-21T19:00:00Z.")]
+    #[arg(
+        long,
+        allow_hyphen_values = true,
+        help = "End time: ISO 8601, local time, or relative input, e.g. 2026-02-21T19:00:00Z, \"tomorrow 10:30\"."
+    )]
     pub end: String,
 
     #[arg(
         long = "policy-id",
-        default_value = DEFAULT_POLICY_ID,
-        help = "Policy UUID used in AddEventAction."
+        help = "Policy UUID used in AddEventAction. Falls back to the profile, then the all-zero UUID."
     )]
-    pub policy_id: String,
+    pub policy_id: Option<String>,
 
     #[arg(
         long = "attendee",
@@ -409,7 +867,7 @@ pub struct EventsCreateArgs {
     #[arg(
         long,
         value_name = "JSON_OBJECT",
-        help = "Additional AddEventAction fields as a JSON object."
+        help = "Additional AddEventAction fields as a JSON object, deep-merged into the action. @FILE reads it from a file, - from stdin."
     )]
     pub json: Option<String>,
 
@@ -417,25 +875,43 @@ pub struct EventsCreateArgs {
         long = "set",
         value_name = "KEY=VALUE",
         action = ArgAction::Append,
-        help = "Additional AddEventAction field override. Repeatable."
+        help = "Additional AddEventAction field override. Repeatable. KEY may be a dot path with [N] or [+] (append), e.g. dateRange.start or attendees[+]. VALUE @FILE reads it from a file as a string; @@ is a literal @."
     )]
     pub set: Vec<String>,
+
+    #[arg(
+        long = "set-string",
+        value_name = "KEY=VALUE",
+        action = ArgAction::Append,
+        help = "Like --set, but always sends VALUE as a string (e.g. --set-string title=123). Repeatable. VALUE @FILE reads a file; @@ is a literal @."
+    )]
+    pub set_string: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "KEY",
+        action = ArgAction::Append,
+        help = "AddEventAction field to leave out, or array element to remove. Repeatable. Applied in command-line order with --set."
+    )]
+    pub unset: Vec<String>,
 }
 
 #[derive(Debug, Args)]
 pub struct EventsUpdateArgs {
-    #[arg(long = "calendar-id", help = "Calendar ID for the event.")]
-    pub calendar_id: u64,
+    #[arg(
+        long = "calendar-id",
+        help = "Calendar ID for the event. Falls back to the profile's calendar_id."
+    )]
+    pub calendar_id: Option<u64>,
 
     #[arg(long = "event-id", help = "Event ID to update.")]
     pub event_id: String,
 
     #[arg(
         long = "policy-id",
-        default_value = DEFAULT_POLICY_ID,
-        help = "Policy UUID used in UpdateEventAction."
+        help = "Policy UUID used in UpdateEventAction. Falls back to the profile, then the all-zero UUID."
     )]
-    pub policy_id: String,
+    pub policy_id: Option<String>,
 
     #[arg(long, help = "Optional updated title.")]
     pub title: Option<String>,
@@ -455,16 +931,24 @@ pub struct EventsUpdateArgs {
     #[arg(long, value_enum, help = "Optional updated transparency.")]
     pub transparency: Option<EventTransparency>,
 
-    #[arg(long, help = "Optional updated start timestamp (ISO 8601).")]
+    #[arg(
+        long,
+        allow_hyphen_values = true,
+        help = "Optional updated start time (ISO 8601, local time, or relative input)."
+    )]
     pub start: Option<String>,
 
-    #[arg(long, help = "Optional updated end timestamp (ISO 8601).")]
+    #[arg(
+        long,
+        allow_hyphen_values = true,
+        help = "Optional updated end time (ISO 8601, local time, or relative input)."
+    )]
     pub end: Option<String>,
 
     #[arg(
         long,
         value_name = "JSON_OBJECT",
-        help = "Additional UpdateEventAction fields as a JSON object."
+        help = "Additional UpdateEventAction fields as a JSON object, deep-merged into the action. @FILE reads it from a file, - from stdin."
     )]
     pub json: Option<String>,
 
@@ -472,25 +956,43 @@ pub struct EventsUpdateArgs {
         long = "set",
         value_name = "KEY=VALUE",
         action = ArgAction::Append,
-        help = "Additional UpdateEventAction field override. Repeatable."
+        help = "Additional UpdateEventAction field override. Repeatable. KEY may be a dot path with [N] or [+] (append), e.g. dateRange.start or attendees[+]. VALUE @FILE reads it from a file as a string; @@ is a literal @."
     )]
     pub set: Vec<String>,
+
+    #[arg(
+        long = "set-string",
+        value_name = "KEY=VALUE",
+        action = ArgAction::Append,
+        help = "Like --set, but always sends VALUE as a string (e.g. --set-string title=123). Repeatable. VALUE @FILE reads a file; @@ is a literal @."
+    )]
+    pub set_string: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "KEY",
+        action = ArgAction::Append,
+        help = "UpdateEventAction field to clear (sent as null), or array element to remove. Repeatable. Applied in command-line order with --set."
+    )]
+    pub unset: Vec<String>,
 }
 
 #[derive(Debug, Args)]
 pub struct EventsDeleteArgs {
-    #[arg(long = "calendar-id", help = "Calendar ID for the event.")]
-    pub calendar_id: u64,
+    #[arg(
+        long = "calendar-id",
+        help = "Calendar ID for the event. Falls back to the profile's calendar_id."
+    )]
+    pub calendar_id: Option<u64>,
 
     #[arg(long = "event-id", help = "Event ID to delete/cancel.")]
     pub event_id: String,
 
     #[arg(
         long = "policy-id",
-        default_value = DEFAULT_POLICY_ID,
-        help = "Policy UUID used in CancelEventAction."
+        help = "Policy UUID used in CancelEventAction. Falls back to the profile, then the all-zero UUID."
     )]
-    pub policy_id: String,
+    pub policy_id: Option<String>,
 
     #[arg(
         long = "message",
@@ -504,7 +1006,7 @@ pub struct EventsApplyArgs {
     #[arg(
         long,
         value_name = "JSON_OBJECT",
-        help = "Raw ApplyScheduleActionsRequest JSON object."
+        help = "Raw ApplyScheduleActionsRequest JSON object. @FILE reads it from a file, - from stdin."
     )]
     pub json: String,
 }
@@ -566,38 +1068,67 @@ pub struct CreateArgs {
 
     #[arg(
         long,
-        help = "Optional due timestamp (ISO 8601), e.g. 2026-02-19T15:00:00Z."
+        allow_hyphen_values = true,
+        help = "Optional due time: ISO 8601, local time, or relative input, e.g. 2026-02-19T15:00:00Z, \"friday 5pm\", eow."
     )]
     pub due: Option<String>,
 
+    #[arg(
+        long,
+        conflicts_with = "time_chunks_required",
+        help = "Total time needed, e.g. 45m, 2h30m or 1.5h. Converted to 15-minute chunks."
+    )]
+    pub duration: Option<String>,
+
     #[arg(
         long = "time-chunks-required",
-        help = "Optional total time in 15-minute chunks."
+        help = "Optional total time in 15-minute chunks. Prefer --duration."
     )]
     pub time_chunks_required: Option<u32>,
 
     #[arg(
         long = "event-category",
         value_enum,
-        default_value_t = EventCategory::Work,
-        help = "Task category. Defaults to WORK."
+        help = "Task category. Falls back to the profile, then WORK."
+    )]
+    pub event_category: Option<EventCategory>,
+
+    #[arg(
+        long = "min-chunk",
+        conflicts_with = "min_chunk_size",
+        help = "Shortest block to schedule, e.g. 30m."
+    )]
+    pub min_chunk: Option<String>,
+
+    #[arg(
+        long = "max-chunk",
+        conflicts_with = "max_chunk_size",
+        help = "Longest block to schedule, e.g. 2h."
     )]
-    pub event_category: EventCategory,
+    pub max_chunk: Option<String>,
 
     #[arg(
         long = "min-chunk-size",
         value_parser = value_parser!(u32).range(1..),
-        help = "Minimum chunk size in 15-minute increments."
+        help = "Minimum chunk size in 15-minute increments. Prefer --min-chunk."
     )]
     pub min_chunk_size: Option<u32>,
 
     #[arg(
         long = "max-chunk-size",
         value_parser = value_parser!(u32).range(1..),
-        help = "Maximum chunk size in 15-minute increments."
+        help = "Maximum chunk size in 15-minute increments. Prefer --max-chunk."
     )]
     pub max_chunk_size: Option<u32>,
 
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = RoundMode::Strict,
+        help = "How durations that are not a multiple of 15 minutes are handled."
+    )]
+    pub round: RoundMode,
+
     #[arg(
         long = "always-private",
         default_value_t = true,
@@ -606,8 +1137,218 @@ pub struct CreateArgs {
     pub always_private: bool,
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum, Eq, PartialEq)]
+pub enum RoundMode {
+    #[value(help = "Reject durations that are not a multiple of 15 minutes.")]
+    Strict,
+    #[value(help = "Round up to the next 15 minutes.")]
+    Up,
+    #[value(help = "Round down to the previous 15 minutes.")]
+    Down,
+    #[value(help = "Round to the nearest 15 minutes.")]
+    Nearest,
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum, Eq, PartialEq)]
 pub enum OutputFormat {
+    #[value(help = "Readable text for people.")]
     Human,
+    #[value(help = "Pretty-printed JSON.")]
     Json,
+    #[value(help = "One compact JSON object per line; lists stream one item per line.")]
+    Ndjson,
+    #[value(help = "YAML.")]
+    Yaml,
+    #[value(help = "Aligned columns, truncated to the terminal width.")]
+    Table,
+    #[value(help = "Comma-separated values with a header row.")]
+    Csv,
+    #[value(help = "Tab-separated values with a header row.")]
+    Tsv,
+}
+
+impl OutputFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OutputFormat::Human => "human",
+            OutputFormat::Json => "json",
+            OutputFormat::Ndjson => "ndjson",
+            OutputFormat::Yaml => "yaml",
+            OutputFormat::Table => "table",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Tsv => "tsv",
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    #[command(about = "Show the resolved settings for the active profile.")]
+    Show,
+    #[command(about = "Set a value in the active profile.")]
+    Set(ConfigSetArgs),
+    #[command(about = "Remove a value from the active profile.")]
+    Unset(ConfigUnsetArgs),
+    #[command(about = "List profiles in the config file.")]
+    Profiles,
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigSetArgs {
+    #[arg(value_enum, help = "Setting to change.")]
+    pub key: ConfigKey,
+
+    #[arg(help = "New value.")]
+    pub value: String,
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigUnsetArgs {
+    #[arg(value_enum, help = "Setting to remove.")]
+    pub key: ConfigKey,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, Eq, PartialEq)]
+pub enum ConfigKey {
+    #[value(name = "api-key", alias = "api_key")]
+    ApiKey,
+    #[value(name = "base-url", alias = "base_url")]
+    BaseUrl,
+    #[value(name = "timeout-secs", alias = "timeout_secs")]
+    TimeoutSecs,
+    #[value(name = "retries")]
+    Retries,
+    #[value(name = "max-retry-wait", alias = "max_retry_wait")]
+    MaxRetryWait,
+    #[value(name = "format")]
+    Format,
+    #[value(name = "event-category", alias = "event_category")]
+    EventCategory,
+    #[value(name = "policy-id", alias = "policy_id")]
+    PolicyId,
+    #[value(name = "calendar-id", alias = "calendar_id")]
+    CalendarId,
+    #[value(name = "timezone")]
+    Timezone,
+    #[value(name = "default-profile", alias = "default_profile")]
+    DefaultProfile,
+}
+
+impl ConfigKey {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ConfigKey::ApiKey => "api_key",
+            ConfigKey::BaseUrl => "base_url",
+            ConfigKey::TimeoutSecs => "timeout_secs",
+            ConfigKey::Retries => "retries",
+            ConfigKey::MaxRetryWait => "max_retry_wait",
+            ConfigKey::Format => "format",
+            ConfigKey::EventCategory => "event_category",
+            ConfigKey::PolicyId => "policy_id",
+            ConfigKey::CalendarId => "calendar_id",
+            ConfigKey::Timezone => "timezone",
+            ConfigKey::DefaultProfile => "default_profile",
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct AuthArgs {
+    #[command(subcommand)]
+    pub command: AuthCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AuthCommand {
+    #[command(about = "Prompt for an API key, validate it, and store it.")]
+    Login(AuthLoginArgs),
+    #[command(about = "Remove the stored API key for the active profile.")]
+    Logout,
+    #[command(about = "Show where the active API key comes from and whether it is valid.")]
+    Status,
+}
+
+#[derive(Debug, Args)]
+pub struct AuthLoginArgs {
+    #[arg(
+        long = "with-token",
+        help = "Read the API key from stdin instead of prompting."
+    )]
+    pub with_token: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = CredentialBackend::File,
+        help = "Where to store the key."
+    )]
+    pub store: CredentialBackend,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, Eq, PartialEq)]
+pub enum CredentialBackend {
+    #[value(name = "file")]
+    File,
+    #[value(name = "keyring")]
+    Keyring,
+}
+
+#[derive(Debug, Args)]
+pub struct CompletionsArgs {
+    #[arg(value_enum, help = "Shell to generate the script for.")]
+    pub shell: CompletionShell,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, Eq, PartialEq)]
+pub enum CompletionShell {
+    #[value(name = "bash")]
+    Bash,
+    #[value(name = "zsh")]
+    Zsh,
+    #[value(name = "fish")]
+    Fish,
+    #[value(name = "powershell")]
+    Powershell,
+    #[value(name = "elvish")]
+    Elvish,
+}
+
+impl CompletionShell {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CompletionShell::Bash => "bash",
+            CompletionShell::Zsh => "zsh",
+            CompletionShell::Fish => "fish",
+            CompletionShell::Powershell => "powershell",
+            CompletionShell::Elvish => "elvish",
+        }
+    }
+}
+
+/// Parses `--max-age`: a number with an optional `s`, `m`, `h` or `d` unit (seconds by
+/// default).
+fn parse_max_age(raw: &str) -> Result<Duration, String> {
+    let raw = raw.trim();
+    let split = raw
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(raw.len());
+    let (amount, unit) = raw.split_at(split);
+    let amount = amount
+        .parse::<u64>()
+        .map_err(|_| format!("'{raw}' is not an age; use e.g. 30s, 5m, 1h or 0"))?;
+    let seconds = match unit.trim() {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 3_600,
+        "d" => 86_400,
+        unit => return Err(format!("unknown unit '{unit}'; use s, m, h or d")),
+    };
+
+    Ok(Duration::from_secs(amount.saturating_mul(seconds)))
 }
@@ -0,0 +1,339 @@
+use crate::{
+    cache::{CacheMode, CachedApi, TaskCache},
+    cli::{Cli, CompletionShell},
+    config::{ConfigStore, Settings},
+    error::CliError,
+    reclaim_api::{Event, EventListQuery, HttpReclaimApi, ReclaimApi, Task, TaskFilter},
+};
+use clap::{CommandFactory, FromArgMatches};
+use clap_complete::{
+    engine::{ArgValueCompleter, CompletionCandidate},
+    env::{CompleteEnv, Shells},
+};
+use std::{
+    collections::BTreeMap,
+    env,
+    ffi::{OsStr, OsString},
+    future::Future,
+    io::{self, Write},
+    time::Duration,
+};
+
+/// Set by the registration script when the shell asks reclaim for completions.
+const COMPLETE_VAR: &str = "RECLAIM_COMPLETE";
+const BIN_NAME: &str = "reclaim";
+/// Cached lists younger than this are offered without asking Reclaim.
+const CACHE_MAX_AGE: Duration = Duration::from_secs(60);
+/// Completion blocks the shell, so a slow API gives up early and falls back to the cache.
+const TIMEOUT_SECS: u64 = 3;
+/// Global flags that select the account, and therefore which IDs are offered.
+const SETTINGS_FLAGS: [&str; 4] = ["--profile", "--config", "--api-key", "--base-url"];
+
+/// The CLI definition with dynamic completers on every task and calendar ID argument.
+fn command() -> clap::Command {
+    attach_completers(Cli::command())
+}
+
+fn attach_completers(command: clap::Command) -> clap::Command {
+    command
+        .mut_args(|arg| match arg.get_id().as_str() {
+            "task_id" => arg.add(ArgValueCompleter::new(complete_task_id)),
+            "calendar_id" | "calendar_ids" => arg.add(ArgValueCompleter::new(complete_calendar_id)),
+            _ => arg,
+        })
+        .mut_subcommands(attach_completers)
+}
+
+/// Answers a completion request and exits when `RECLAIM_COMPLETE` is set; returns right
+/// away on normal runs.
+pub fn complete_from_env() {
+    CompleteEnv::with_factory(command)
+        .var(COMPLETE_VAR)
+        .bin(BIN_NAME)
+        .completer(BIN_NAME)
+        .complete();
+}
+
+/// `reclaim completions <shell>`: prints the script that calls back into
+/// `complete_from_env`.
+pub fn print_registration(shell: CompletionShell) -> Result<(), CliError> {
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(shell.as_str())
+        .ok_or_else(|| CliError::InvalidInput {
+            message: format!("Completions are not supported for {}.", shell.as_str()),
+            hint: Some("Use bash, zsh, fish, powershell or elvish.".to_string()),
+        })?;
+
+    let mut stdout = io::stdout().lock();
+    completer
+        .write_registration(COMPLETE_VAR, BIN_NAME, BIN_NAME, BIN_NAME, &mut stdout)
+        .and_then(|()| stdout.flush())
+        .map_err(|error| {
+            CliError::Output(format!("Could not write the completion script: {error}"))
+        })
+}
+
+fn complete_task_id(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some(settings) = completion_settings() else {
+        return Vec::new();
+    };
+    let tasks = block_on(fetch_list(&settings, |api| async move {
+        api.list_tasks(TaskFilter::Active).await
+    }));
+
+    candidates(current, task_choices(&tasks))
+}
+
+fn complete_calendar_id(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some(settings) = completion_settings() else {
+        return Vec::new();
+    };
+    let events = block_on(fetch_list(&settings, |api| async move {
+        api.list_events(EventListQuery::default()).await
+    }));
+
+    candidates(current, calendar_choices(&events, settings.calendar_id))
+}
+
+/// Settings for the command line being completed, honoring the flags in `SETTINGS_FLAGS`.
+/// `None` when they cannot be resolved, e.g. for an unknown profile.
+fn completion_settings() -> Option<Settings> {
+    let words = env::args_os()
+        .skip_while(|arg| arg != "--")
+        .skip(2)
+        .collect::<Vec<_>>();
+    let matches = Cli::command()
+        .try_get_matches_from(settings_argv(&words))
+        .ok()?;
+    let cli = Cli::from_arg_matches(&matches).ok()?;
+    let store = ConfigStore::load(cli.config_path.as_deref()).ok()?;
+    // Anything printed here would land in the middle of the user's prompt.
+    let mut settings =
+        Settings::resolve_with_stored_key(&cli, &matches, &store, true, drop).ok()?;
+    settings.timeout_secs = settings.timeout_secs.min(TIMEOUT_SECS);
+    settings.retries = 0;
+
+    Some(settings)
+}
+
+/// Keeps the settings flags from the words being completed and appends a subcommand, so
+/// the result parses like a regular `reclaim list`.
+fn settings_argv(words: &[OsString]) -> Vec<OsString> {
+    let mut argv = vec![OsString::from(BIN_NAME)];
+    let mut words = words.iter();
+    while let Some(word) = words.next() {
+        let Some(text) = word.to_str() else {
+            continue;
+        };
+        let (flag, inline_value) = match text.split_once('=') {
+            Some((flag, value)) => (flag, Some(value)),
+            None => (text, None),
+        };
+        if !SETTINGS_FLAGS.contains(&flag) {
+            continue;
+        }
+
+        let value = match inline_value {
+            Some(value) => OsString::from(value),
+            None => match words.next() {
+                Some(value) => value.clone(),
+                None => break,
+            },
+        };
+        argv.push(OsString::from(flag));
+        argv.push(value);
+    }
+    argv.push(OsString::from("list"));
+
+    argv
+}
+
+/// A recently cached or freshly fetched list, falling back to any cached copy when Reclaim
+/// cannot be reached.
+async fn fetch_list<T, F, Fut>(settings: &Settings, fetch: F) -> Vec<T>
+where
+    F: Fn(CachedApi<HttpReclaimApi>) -> Fut,
+    Fut: Future<Output = Result<Vec<T>, CliError>>,
+{
+    for mode in [
+        CacheMode::Online {
+            max_age: CACHE_MAX_AGE,
+        },
+        CacheMode::Offline,
+    ] {
+        let Some(api) = completion_api(settings, mode) else {
+            break;
+        };
+        if let Ok(items) = fetch(api).await {
+            return items;
+        }
+    }
+
+    Vec::new()
+}
+
+fn completion_api(settings: &Settings, mode: CacheMode) -> Option<CachedApi<HttpReclaimApi>> {
+    let http = HttpReclaimApi::new(
+        settings.api_key.clone(),
+        settings.base_url.clone(),
+        settings.timeout_secs,
+        settings.retry_policy(),
+    )
+    .ok()?;
+    let cache = TaskCache::open(settings.profile.as_deref(), &settings.base_url).ok()?;
+
+    // The completer retries with `CacheMode::Offline` itself, without a warning on stderr.
+    Some(CachedApi::new(http, cache, mode).with_stale_fallback(false))
+}
+
+/// Completers run before `main` starts its runtime, so each lookup gets a small one.
+fn block_on<T: Default>(future: impl Future<Output = T>) -> T {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map(|runtime| runtime.block_on(future))
+        .unwrap_or_default()
+}
+
+fn task_choices(tasks: &[Task]) -> Vec<(String, String)> {
+    tasks
+        .iter()
+        .map(|task| (task.id.to_string(), task.title.clone()))
+        .collect()
+}
+
+/// Calendars seen in the event list, with the profile's `calendar_id` first.
+fn calendar_choices(events: &[Event], default_calendar: Option<u64>) -> Vec<(String, String)> {
+    let mut counts = BTreeMap::<u64, usize>::new();
+    for calendar_id in events.iter().filter_map(|event| event.calendar_id) {
+        *counts.entry(calendar_id).or_default() += 1;
+    }
+
+    let mut choices = Vec::new();
+    if let Some(calendar_id) = default_calendar {
+        let count = counts.remove(&calendar_id).unwrap_or_default();
+        choices.push((
+            calendar_id.to_string(),
+            format!("profile calendar_id, {}", event_count(count)),
+        ));
+    }
+    choices.extend(
+        counts
+            .into_iter()
+            .map(|(calendar_id, count)| (calendar_id.to_string(), event_count(count))),
+    );
+
+    choices
+}
+
+fn event_count(count: usize) -> String {
+    match count {
+        1 => "1 upcoming event".to_string(),
+        count => format!("{count} upcoming events"),
+    }
+}
+
+fn candidates(current: &OsStr, choices: Vec<(String, String)>) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    choices
+        .into_iter()
+        .filter(|(value, _)| value.starts_with(current.as_ref()))
+        .map(|(value, help)| CompletionCandidate::new(value).help(Some(help.into())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::task;
+
+    fn event(calendar_id: u64) -> Event {
+        serde_json::from_value(serde_json::json!({ "calendarId": calendar_id })).unwrap()
+    }
+
+    #[test]
+    fn task_and_calendar_arguments_get_dynamic_completers() {
+        let command = command();
+        command.clone().debug_assert();
+
+        let has_completer = |path: &[&str], arg_id: &str| {
+            let mut command = &command;
+            for name in path {
+                command = command.find_subcommand(name).unwrap();
+            }
+            command
+                .get_arguments()
+                .find(|arg| arg.get_id() == arg_id)
+                .and_then(|arg| arg.get::<ArgValueCompleter>())
+                .is_some()
+        };
+        for subcommand in ["get", "patch", "put", "delete", "done", "snooze"] {
+            assert!(has_completer(&[subcommand], "task_id"), "{subcommand}");
+        }
+        assert!(has_completer(&["events", "list"], "calendar_ids"));
+        assert!(has_completer(&["events", "create"], "calendar_id"));
+        assert!(!has_completer(&["create"], "title"));
+    }
+
+    #[test]
+    fn settings_argv_keeps_only_account_flags() {
+        let words = [
+            "--profile",
+            "work",
+            "--format",
+            "json",
+            "--base-url=http://localhost/api",
+            "done",
+            "12",
+        ]
+        .map(OsString::from);
+
+        assert_eq!(
+            settings_argv(&words),
+            [
+                "reclaim",
+                "--profile",
+                "work",
+                "--base-url",
+                "http://localhost/api",
+                "list"
+            ]
+            .map(OsString::from)
+        );
+        assert_eq!(
+            settings_argv(&["get".into(), "--profile".into()]),
+            ["reclaim", "list"].map(OsString::from)
+        );
+    }
+
+    #[test]
+    fn candidates_match_the_typed_prefix_and_carry_titles() {
+        let tasks = [task(12, "Ship it"), task(120, "Review"), task(7, "Plan")];
+
+        let completed = candidates(OsStr::new("12"), task_choices(&tasks));
+        assert_eq!(
+            completed
+                .iter()
+                .map(|candidate| candidate.get_value().to_string_lossy().into_owned())
+                .collect::<Vec<_>>(),
+            ["12", "120"]
+        );
+        assert_eq!(
+            completed[0].get_help().map(ToString::to_string).as_deref(),
+            Some("Ship it")
+        );
+
+        let calendars = calendar_choices(&[event(5), event(9), event(5)], Some(9));
+        assert_eq!(
+            calendars,
+            [
+                (
+                    "9".to_string(),
+                    "profile calendar_id, 1 upcoming event".to_string()
+                ),
+                ("5".to_string(), "2 upcoming events".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,909 @@
+use crate::{
+    cache::CacheMode,
+    cli::{Cli, ConfigKey, EventCategory, OutputFormat},
+    credentials::CredentialStore,
+    datetime::Zone,
+    error::CliError,
+    reclaim_api::TraceLevel,
+    retry::{RetryPolicy, DEFAULT_MAX_RETRY_WAIT_SECS, DEFAULT_RETRIES},
+};
+use clap::{parser::ValueSource, ArgMatches, ValueEnum};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    env, fmt, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://api.app.reclaim.ai/api";
+pub const DEFAULT_TIMEOUT_SECS: u64 = 15;
+pub const DEFAULT_POLICY_ID: &str = "00000000-0000-0000-0000-000000000000";
+const DEFAULT_PROFILE_NAME: &str = "default";
+const CONFIG_DIR_NAME: &str = "reclaim";
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retry_wait: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calendar_id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct ConfigStore {
+    pub path: PathBuf,
+    pub exists: bool,
+    pub file: ConfigFile,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SettingSource {
+    Flag,
+    Env,
+    Keyring,
+    CredentialsFile,
+    Profile,
+    BuiltIn,
+    Unset,
+}
+
+impl fmt::Display for SettingSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            SettingSource::Flag => "flag",
+            SettingSource::Env => "env",
+            SettingSource::Keyring => "keyring",
+            SettingSource::CredentialsFile => "credentials file",
+            SettingSource::Profile => "profile",
+            SettingSource::BuiltIn => "built-in",
+            SettingSource::Unset => "unset",
+        };
+        write!(f, "{label}")
+    }
+}
+
+/// Effective settings after applying flag > env > profile > built-in precedence.
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub profile: Option<String>,
+    pub api_key: Option<String>,
+    pub base_url: String,
+    pub timeout_secs: u64,
+    pub retries: u32,
+    /// Seconds.
+    pub max_retry_wait: u64,
+    /// Only ever set by the `--retry-non-idempotent` flag.
+    pub retry_non_idempotent: bool,
+    /// Only ever set by the `--dry-run` flag.
+    pub dry_run: bool,
+    /// From `--verbose`/`--trace`.
+    pub trace: TraceLevel,
+    /// From `--offline`/`--max-age`.
+    pub cache_mode: CacheMode,
+    pub format: OutputFormat,
+    pub event_category: EventCategory,
+    pub policy_id: String,
+    pub calendar_id: Option<u64>,
+    pub timezone: Zone,
+    sources: BTreeMap<&'static str, SettingSource>,
+}
+
+impl Settings {
+    /// Resolves settings for the active profile. With `strict_profile`, a selected profile
+    /// that does not exist in the config file is an error; `reclaim config` passes false so
+    /// it can create profiles.
+    pub fn resolve(
+        cli: &Cli,
+        matches: &ArgMatches,
+        store: &ConfigStore,
+        strict_profile: bool,
+    ) -> Result<Self, CliError> {
+        let profile_name = store.active_profile_name(cli.profile.as_deref());
+        let profile = match profile_name.as_deref() {
+            Some(name) => match store.file.profiles.get(name) {
+                Some(profile) => profile.clone(),
+                None if strict_profile => return Err(store.unknown_profile_error(name)),
+                None => Profile::default(),
+            },
+            None => Profile::default(),
+        };
+        let context = ProfileContext {
+            path: &store.path,
+            name: profile_name.as_deref().unwrap_or(DEFAULT_PROFILE_NAME),
+            strict: strict_profile,
+        };
+
+        let mut sources = BTreeMap::new();
+
+        let api_key = pick(
+            &mut sources,
+            ConfigKey::ApiKey,
+            matches,
+            cli.api_key.clone(),
+            profile.api_key.clone(),
+            None,
+        );
+        let base_url = pick(
+            &mut sources,
+            ConfigKey::BaseUrl,
+            matches,
+            cli.base_url.clone(),
+            profile.base_url.clone(),
+            Some(DEFAULT_BASE_URL.to_string()),
+        )
+        .unwrap_or_default();
+        let timeout_secs = pick(
+            &mut sources,
+            ConfigKey::TimeoutSecs,
+            matches,
+            cli.timeout_secs,
+            profile.timeout_secs,
+            Some(DEFAULT_TIMEOUT_SECS),
+        )
+        .unwrap_or(DEFAULT_TIMEOUT_SECS);
+        let retries = pick(
+            &mut sources,
+            ConfigKey::Retries,
+            matches,
+            cli.retries,
+            profile.retries,
+            Some(DEFAULT_RETRIES),
+        )
+        .unwrap_or(DEFAULT_RETRIES);
+        let max_retry_wait = pick(
+            &mut sources,
+            ConfigKey::MaxRetryWait,
+            matches,
+            cli.max_retry_wait,
+            profile.max_retry_wait,
+            Some(DEFAULT_MAX_RETRY_WAIT_SECS),
+        )
+        .unwrap_or(DEFAULT_MAX_RETRY_WAIT_SECS);
+
+        let profile_format = profile
+            .format
+            .as_deref()
+            .and_then(|value| {
+                context
+                    .parse_enum::<OutputFormat>(ConfigKey::Format, value)
+                    .transpose()
+            })
+            .transpose()?;
+        let format = pick(
+            &mut sources,
+            ConfigKey::Format,
+            matches,
+            cli.format,
+            profile_format,
+            Some(OutputFormat::Human),
+        )
+        .unwrap_or(OutputFormat::Human);
+
+        let profile_category = profile
+            .event_category
+            .as_deref()
+            .and_then(|value| {
+                context
+                    .parse_enum::<EventCategory>(ConfigKey::EventCategory, value)
+                    .transpose()
+            })
+            .transpose()?;
+        let event_category = pick(
+            &mut sources,
+            ConfigKey::EventCategory,
+            matches,
+            None,
+            profile_category,
+            Some(EventCategory::Work),
+        )
+        .unwrap_or(EventCategory::Work);
+
+        let policy_id = pick(
+            &mut sources,
+            ConfigKey::PolicyId,
+            matches,
+            None,
+            profile.policy_id.clone(),
+            Some(DEFAULT_POLICY_ID.to_string()),
+        )
+        .unwrap_or_default();
+        let calendar_id = pick(
+            &mut sources,
+            ConfigKey::CalendarId,
+            matches,
+            None,
+            profile.calendar_id,
+            None,
+        );
+
+        let cli_timezone = cli.timezone.as_deref().map(Zone::parse).transpose()?;
+        let profile_timezone = match profile.timezone.as_deref().map(Zone::parse) {
+            Some(Ok(zone)) => Some(zone),
+            Some(Err(error)) if strict_profile => {
+                return Err(CliError::InvalidInput {
+                    message: format!(
+                        "Invalid timezone in profile '{}' of {}: {error}",
+                        context.name,
+                        store.path.display()
+                    ),
+                    hint: Some(format!(
+                        "Fix it with: reclaim config set timezone <TZ> --profile {}",
+                        context.name
+                    )),
+                });
+            }
+            Some(Err(_)) | None => None,
+        };
+        let timezone = pick(
+            &mut sources,
+            ConfigKey::Timezone,
+            matches,
+            cli_timezone,
+            profile_timezone,
+            Some(Zone::Local),
+        )
+        .unwrap_or(Zone::Local);
+
+        Ok(Self {
+            profile: profile_name,
+            api_key,
+            base_url,
+            timeout_secs,
+            retries,
+            max_retry_wait,
+            retry_non_idempotent: cli.retry_non_idempotent,
+            dry_run: cli.dry_run,
+            trace: match (cli.trace, cli.verbose) {
+                (true, _) => TraceLevel::Trace,
+                (false, true) => TraceLevel::Verbose,
+                (false, false) => TraceLevel::Off,
+            },
+            cache_mode: if cli.offline {
+                CacheMode::Offline
+            } else {
+                CacheMode::Online {
+                    max_age: cli.max_age.unwrap_or(Duration::ZERO),
+                }
+            },
+            format,
+            event_category,
+            policy_id,
+            calendar_id,
+            timezone,
+            sources,
+        })
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            retries: self.retries,
+            max_wait: Duration::from_secs(self.max_retry_wait),
+            retry_non_idempotent: self.retry_non_idempotent,
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// Applies a key from `reclaim auth login`. Stored credentials rank below flags and env
+    /// vars but above a plaintext `api_key` in the profile.
+    pub fn use_stored_api_key(&mut self, api_key: String, source: SettingSource) {
+        if matches!(
+            self.source(ConfigKey::ApiKey),
+            SettingSource::Flag | SettingSource::Env
+        ) {
+            return;
+        }
+
+        self.api_key = Some(api_key);
+        self.sources.insert(ConfigKey::ApiKey.as_str(), source);
+    }
+
+    /// `resolve`, then swaps in the key stored by `reclaim auth login` unless
+    /// `--api-key`/`RECLAIM_API_KEY` was given. A credential store that cannot be read goes
+    /// to `unreadable` instead of failing, so a profile `api_key` still works.
+    pub fn resolve_with_stored_key(
+        cli: &Cli,
+        matches: &ArgMatches,
+        store: &ConfigStore,
+        strict_profile: bool,
+        unreadable: impl FnOnce(CliError),
+    ) -> Result<Self, CliError> {
+        let mut settings = Self::resolve(cli, matches, store, strict_profile)?;
+        if !matches!(
+            settings.source(ConfigKey::ApiKey),
+            SettingSource::Flag | SettingSource::Env
+        ) {
+            let profile = store.target_profile_name(cli.profile.as_deref());
+            match CredentialStore::new(store.dir()).load(&profile) {
+                Ok(Some((api_key, source))) => settings.use_stored_api_key(api_key, source),
+                Ok(None) => {}
+                Err(error) => unreadable(error),
+            }
+        }
+
+        Ok(settings)
+    }
+
+    pub fn source(&self, key: ConfigKey) -> SettingSource {
+        self.sources
+            .get(key.as_str())
+            .copied()
+            .unwrap_or(SettingSource::Unset)
+    }
+}
+
+fn pick<T>(
+    sources: &mut BTreeMap<&'static str, SettingSource>,
+    key: ConfigKey,
+    matches: &ArgMatches,
+    from_cli: Option<T>,
+    from_profile: Option<T>,
+    built_in: Option<T>,
+) -> Option<T> {
+    let (value, source) = if let Some(value) = from_cli {
+        let source = match matches.value_source(key.as_str()) {
+            Some(ValueSource::EnvVariable) => SettingSource::Env,
+            _ => SettingSource::Flag,
+        };
+        (Some(value), source)
+    } else if let Some(value) = from_profile {
+        (Some(value), SettingSource::Profile)
+    } else if let Some(value) = built_in {
+        (Some(value), SettingSource::BuiltIn)
+    } else {
+        (None, SettingSource::Unset)
+    };
+
+    sources.insert(key.as_str(), source);
+    value
+}
+
+struct ProfileContext<'a> {
+    path: &'a Path,
+    name: &'a str,
+    strict: bool,
+}
+
+impl ProfileContext<'_> {
+    /// Parses an enum-valued profile entry. Outside strict mode invalid values are ignored so
+    /// `reclaim config set` can still repair them.
+    fn parse_enum<T: ValueEnum>(&self, key: ConfigKey, value: &str) -> Result<Option<T>, CliError> {
+        if !self.strict {
+            return Ok(T::from_str(value, true).ok());
+        }
+
+        T::from_str(value, true)
+            .map(Some)
+            .map_err(|_| CliError::InvalidInput {
+                message: format!(
+                    "Invalid {} '{value}' in profile '{}' of {}.",
+                    key.as_str(),
+                    self.name,
+                    self.path.display()
+                ),
+                hint: Some(format!(
+                    "Fix it with: reclaim config set {} <VALUE> --profile {}",
+                    key.as_str().replace('_', "-"),
+                    self.name
+                )),
+            })
+    }
+}
+
+impl ConfigStore {
+    pub fn load(path_override: Option<&Path>) -> Result<Self, CliError> {
+        let path = match path_override {
+            Some(path) => path.to_path_buf(),
+            None => default_config_path()?,
+        };
+
+        let raw = match fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self {
+                    path,
+                    exists: false,
+                    file: ConfigFile::default(),
+                });
+            }
+            Err(error) => {
+                return Err(CliError::InvalidInput {
+                    message: format!("Could not read config file {}: {error}", path.display()),
+                    hint: Some("Check the file permissions or pass --config <PATH>.".to_string()),
+                });
+            }
+        };
+
+        let file = toml::from_str::<ConfigFile>(&raw).map_err(|error| CliError::InvalidInput {
+            message: format!("Invalid config file {}: {error}", path.display()),
+            hint: Some(
+                "Fix the TOML by hand, or delete the file and recreate it with `reclaim config set`."
+                    .to_string(),
+            ),
+        })?;
+
+        Ok(Self {
+            path,
+            exists: true,
+            file,
+        })
+    }
+
+    /// Directory holding the config file; credentials are stored alongside it.
+    pub fn dir(&self) -> &Path {
+        self.path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."))
+    }
+
+    pub fn save(&self) -> Result<(), CliError> {
+        let rendered = toml::to_string_pretty(&self.file)
+            .map_err(|error| CliError::Output(format!("Could not render config TOML: {error}")))?;
+
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent).map_err(|error| {
+                    CliError::Output(format!(
+                        "Could not create config directory {}: {error}",
+                        parent.display()
+                    ))
+                })?;
+            }
+        }
+
+        // The file can hold an API key, so keep it owner-only like the credentials file.
+        write_private_file(&self.path, rendered.as_bytes()).map_err(|error| {
+            CliError::Output(format!(
+                "Could not write config file {}: {error}",
+                self.path.display()
+            ))
+        })
+    }
+
+    /// Profile selected by `--profile`, then `default_profile`, then a profile named "default".
+    pub fn active_profile_name(&self, explicit: Option<&str>) -> Option<String> {
+        explicit
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .or_else(|| self.file.default_profile.clone())
+            .or_else(|| {
+                self.file
+                    .profiles
+                    .contains_key(DEFAULT_PROFILE_NAME)
+                    .then(|| DEFAULT_PROFILE_NAME.to_string())
+            })
+    }
+
+    /// Profile that `config set`/`config unset` write to; created on demand.
+    pub fn target_profile_name(&self, explicit: Option<&str>) -> String {
+        self.active_profile_name(explicit)
+            .unwrap_or_else(|| DEFAULT_PROFILE_NAME.to_string())
+    }
+
+    pub fn set(&mut self, profile: &str, key: ConfigKey, raw_value: &str) -> Result<(), CliError> {
+        let value = raw_value.trim();
+        if value.is_empty() {
+            return Err(CliError::InvalidInput {
+                message: format!("Invalid value for {}: it cannot be empty.", key.as_str()),
+                hint: Some(format!(
+                    "To remove it, run: reclaim config unset {}",
+                    key.as_str().replace('_', "-")
+                )),
+            });
+        }
+
+        if key == ConfigKey::DefaultProfile {
+            self.file.default_profile = Some(value.to_string());
+            return Ok(());
+        }
+
+        let entry = self.file.profiles.entry(profile.to_string()).or_default();
+        match key {
+            ConfigKey::ApiKey => entry.api_key = Some(value.to_string()),
+            ConfigKey::BaseUrl => {
+                reqwest::Url::parse(value)
+                    .map_err(|_| CliError::InvalidBaseUrl(value.to_string()))?;
+                entry.base_url = Some(value.to_string());
+            }
+            ConfigKey::TimeoutSecs => {
+                entry.timeout_secs = Some(parse_number(key, value)?);
+            }
+            ConfigKey::Retries => entry.retries = Some(parse_number(key, value)?),
+            ConfigKey::MaxRetryWait => {
+                entry.max_retry_wait = Some(parse_number(key, value)?);
+            }
+            ConfigKey::Format => {
+                let format = parse_enum_value::<OutputFormat>(key, value)?;
+                entry.format = Some(format.as_str().to_string());
+            }
+            ConfigKey::EventCategory => {
+                let category = parse_enum_value::<EventCategory>(key, value)?;
+                entry.event_category = Some(category.as_str().to_string());
+            }
+            ConfigKey::PolicyId => entry.policy_id = Some(value.to_string()),
+            ConfigKey::CalendarId => {
+                entry.calendar_id = Some(parse_number(key, value)?);
+            }
+            ConfigKey::Timezone => {
+                entry.timezone = Some(Zone::parse(value)?.to_string());
+            }
+            ConfigKey::DefaultProfile => unreachable!("handled above"),
+        }
+
+        Ok(())
+    }
+
+    /// Removes a value; returns false when there was nothing to remove.
+    pub fn unset(&mut self, profile: &str, key: ConfigKey) -> bool {
+        if key == ConfigKey::DefaultProfile {
+            return self.file.default_profile.take().is_some();
+        }
+
+        let Some(entry) = self.file.profiles.get_mut(profile) else {
+            return false;
+        };
+        let removed = match key {
+            ConfigKey::ApiKey => entry.api_key.take().is_some(),
+            ConfigKey::BaseUrl => entry.base_url.take().is_some(),
+            ConfigKey::TimeoutSecs => entry.timeout_secs.take().is_some(),
+            ConfigKey::Retries => entry.retries.take().is_some(),
+            ConfigKey::MaxRetryWait => entry.max_retry_wait.take().is_some(),
+            ConfigKey::Format => entry.format.take().is_some(),
+            ConfigKey::EventCategory => entry.event_category.take().is_some(),
+            ConfigKey::PolicyId => entry.policy_id.take().is_some(),
+            ConfigKey::CalendarId => entry.calendar_id.take().is_some(),
+            ConfigKey::Timezone => entry.timezone.take().is_some(),
+            ConfigKey::DefaultProfile => unreachable!("handled above"),
+        };
+
+        if *entry == Profile::default() {
+            self.file.profiles.remove(profile);
+        }
+
+        removed
+    }
+
+    /// Adds an empty profile so `--profile <name>` resolves; returns true if it was created.
+    pub fn ensure_profile(&mut self, profile: &str) -> bool {
+        if self.file.profiles.contains_key(profile) {
+            return false;
+        }
+
+        self.file
+            .profiles
+            .insert(profile.to_string(), Profile::default());
+        true
+    }
+
+    fn unknown_profile_error(&self, name: &str) -> CliError {
+        let known = self
+            .file
+            .profiles
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let hint = if known.is_empty() {
+            format!("Create it with: reclaim config set api-key <KEY> --profile {name}")
+        } else {
+            format!(
+                "Known profiles: {}. Create it with: reclaim config set api-key <KEY> --profile {name}",
+                known.join(", ")
+            )
+        };
+
+        CliError::InvalidInput {
+            message: format!("Profile '{name}' was not found in {}.", self.path.display()),
+            hint: Some(hint),
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(key: ConfigKey, value: &str) -> Result<T, CliError> {
+    value.parse::<T>().map_err(|_| CliError::InvalidInput {
+        message: format!(
+            "Invalid value for {}: '{value}' is not a non-negative integer.",
+            key.as_str()
+        ),
+        hint: Some(format!(
+            "Example: reclaim config set {} 30",
+            key.as_str().replace('_', "-")
+        )),
+    })
+}
+
+fn parse_enum_value<T: ValueEnum>(key: ConfigKey, value: &str) -> Result<T, CliError> {
+    T::from_str(value, true).map_err(|_| {
+        let allowed = T::value_variants()
+            .iter()
+            .filter_map(|variant| variant.to_possible_value())
+            .map(|value| value.get_name().to_string())
+            .collect::<Vec<_>>();
+        CliError::InvalidInput {
+            message: format!("Invalid value for {}: '{value}'.", key.as_str()),
+            hint: Some(format!("Allowed values: {}", allowed.join(", "))),
+        }
+    })
+}
+
+fn default_config_path() -> Result<PathBuf, CliError> {
+    Ok(config_dir()?.join(CONFIG_FILE_NAME))
+}
+
+/// `$XDG_CONFIG_HOME/reclaim`, falling back to `~/.config/reclaim`.
+pub fn config_dir() -> Result<PathBuf, CliError> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .ok_or_else(|| CliError::InvalidInput {
+            message:
+                "Could not determine the config directory: neither XDG_CONFIG_HOME nor HOME is set."
+                    .to_string(),
+            hint: Some("Pass --config <PATH> or set RECLAIM_CONFIG.".to_string()),
+        })?;
+
+    Ok(base.join(CONFIG_DIR_NAME))
+}
+
+pub fn redact_secret(secret: &str) -> String {
+    let visible = secret
+        .chars()
+        .rev()
+        .take(4)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect::<String>();
+    if secret.chars().count() <= 8 {
+        "****".to_string()
+    } else {
+        format!("****{visible}")
+    }
+}
+
+/// Writes `contents` to `path` with mode 0600 on Unix, tightening an existing file as well.
+pub(crate) fn write_private_file(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut handle = options.open(path)?;
+
+    // `mode` only applies on creation, so tighten pre-existing files too.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        handle.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+
+    handle.write_all(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{CommandFactory, FromArgMatches};
+
+    fn store_with(file: ConfigFile) -> ConfigStore {
+        ConfigStore {
+            path: PathBuf::from("/tmp/reclaim-test/config.toml"),
+            exists: true,
+            file,
+        }
+    }
+
+    fn resolve(args: &[&str], store: &ConfigStore) -> Result<Settings, CliError> {
+        let matches = Cli::command()
+            .try_get_matches_from(args)
+            .expect("args should parse");
+        let cli = Cli::from_arg_matches(&matches).expect("cli should build");
+        Settings::resolve(&cli, &matches, store, true)
+    }
+
+    fn work_profile() -> Profile {
+        Profile {
+            api_key: Some("profile-key".to_string()),
+            base_url: Some("https://example.test/api".to_string()),
+            timeout_secs: Some(40),
+            retries: Some(5),
+            max_retry_wait: None,
+            format: Some("json".to_string()),
+            event_category: Some("PERSONAL".to_string()),
+            policy_id: None,
+            calendar_id: Some(42),
+            timezone: Some("Europe/Berlin".to_string()),
+        }
+    }
+
+    #[test]
+    fn flags_take_precedence_over_profile_values() {
+        let mut file = ConfigFile::default();
+        file.profiles.insert("work".to_string(), work_profile());
+        let store = store_with(file);
+
+        let settings = resolve(
+            &[
+                "reclaim",
+                "--profile",
+                "work",
+                "--timeout-secs",
+                "5",
+                "list",
+            ],
+            &store,
+        )
+        .unwrap();
+
+        assert_eq!(settings.timeout_secs, 5);
+        assert_eq!(settings.source(ConfigKey::TimeoutSecs), SettingSource::Flag);
+        assert_eq!(settings.retries, 5);
+        assert_eq!(settings.source(ConfigKey::Retries), SettingSource::Profile);
+        assert_eq!(settings.max_retry_wait, DEFAULT_MAX_RETRY_WAIT_SECS);
+        assert_eq!(settings.api_key.as_deref(), Some("profile-key"));
+        assert_eq!(settings.source(ConfigKey::ApiKey), SettingSource::Profile);
+        assert_eq!(settings.format, OutputFormat::Json);
+        assert_eq!(settings.event_category, EventCategory::Personal);
+        assert_eq!(settings.calendar_id, Some(42));
+        assert_eq!(settings.policy_id, DEFAULT_POLICY_ID);
+        assert_eq!(settings.source(ConfigKey::PolicyId), SettingSource::BuiltIn);
+    }
+
+    #[test]
+    fn default_profile_is_used_when_no_profile_flag_is_passed() {
+        let mut file = ConfigFile {
+            default_profile: Some("work".to_string()),
+            ..ConfigFile::default()
+        };
+        file.profiles.insert("work".to_string(), work_profile());
+        let store = store_with(file);
+
+        let settings = resolve(&["reclaim", "list"], &store).unwrap();
+        assert_eq!(settings.profile.as_deref(), Some("work"));
+        assert_eq!(settings.base_url, "https://example.test/api");
+    }
+
+    #[test]
+    fn missing_config_falls_back_to_built_in_defaults() {
+        let store = store_with(ConfigFile::default());
+        let settings = resolve(&["reclaim", "list"], &store).unwrap();
+
+        assert_eq!(settings.profile, None);
+        assert_eq!(settings.base_url, DEFAULT_BASE_URL);
+        assert_eq!(settings.timeout_secs, DEFAULT_TIMEOUT_SECS);
+        assert_eq!(settings.format, OutputFormat::Human);
+        assert_eq!(settings.source(ConfigKey::ApiKey), SettingSource::Unset);
+    }
+
+    #[test]
+    fn unknown_explicit_profile_is_an_error() {
+        let store = store_with(ConfigFile::default());
+        let error = resolve(&["reclaim", "--profile", "nope", "list"], &store).unwrap_err();
+        assert!(error.to_string().contains("Profile 'nope' was not found"));
+    }
+
+    #[test]
+    fn set_validates_and_normalizes_values() {
+        let mut store = store_with(ConfigFile::default());
+        store.set("work", ConfigKey::Format, "JSON").unwrap();
+        store
+            .set("work", ConfigKey::EventCategory, "personal")
+            .unwrap();
+        assert!(store.set("work", ConfigKey::TimeoutSecs, "soon").is_err());
+
+        let profile = store.file.profiles.get("work").unwrap();
+        assert_eq!(profile.format.as_deref(), Some("json"));
+        assert_eq!(profile.event_category.as_deref(), Some("PERSONAL"));
+    }
+
+    #[test]
+    fn unset_removes_empty_profiles() {
+        let mut store = store_with(ConfigFile::default());
+        store.set("work", ConfigKey::ApiKey, "abc").unwrap();
+        assert!(store.unset("work", ConfigKey::ApiKey));
+        assert!(!store.file.profiles.contains_key("work"));
+        assert!(!store.unset("work", ConfigKey::ApiKey));
+    }
+
+    #[test]
+    fn stored_api_key_ranks_between_env_and_profile() {
+        let mut file = ConfigFile::default();
+        file.profiles.insert("work".to_string(), work_profile());
+        let store = store_with(file);
+
+        let mut settings = resolve(&["reclaim", "--profile", "work", "list"], &store).unwrap();
+        settings.use_stored_api_key("stored-key".to_string(), SettingSource::CredentialsFile);
+        assert_eq!(settings.api_key.as_deref(), Some("stored-key"));
+        assert_eq!(
+            settings.source(ConfigKey::ApiKey),
+            SettingSource::CredentialsFile
+        );
+
+        let mut settings = resolve(
+            &[
+                "reclaim",
+                "--profile",
+                "work",
+                "--api-key",
+                "flag-key",
+                "list",
+            ],
+            &store,
+        )
+        .unwrap();
+        settings.use_stored_api_key("stored-key".to_string(), SettingSource::Keyring);
+        assert_eq!(settings.api_key.as_deref(), Some("flag-key"));
+    }
+
+    #[test]
+    fn config_file_round_trips_through_toml() {
+        let mut file = ConfigFile {
+            default_profile: Some("work".to_string()),
+            ..ConfigFile::default()
+        };
+        file.profiles.insert("work".to_string(), work_profile());
+
+        let rendered = toml::to_string_pretty(&file).unwrap();
+        assert!(rendered.contains("[profiles.work]"));
+        assert_eq!(toml::from_str::<ConfigFile>(&rendered).unwrap(), file);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn save_writes_owner_only_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = env::temp_dir().join(format!("reclaim-config-save-{}", std::process::id()));
+        let path = dir.join("config.toml");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, "").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let mut file = ConfigFile::default();
+        file.profiles.insert("work".to_string(), work_profile());
+        let store = ConfigStore {
+            path: path.clone(),
+            exists: true,
+            file,
+        };
+        store.save().unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(fs::read_to_string(&path).unwrap().contains("profile-key"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
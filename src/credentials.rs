@@ -0,0 +1,388 @@
+use crate::{
+    cli::CredentialBackend,
+    config::{write_private_file, SettingSource},
+    error::CliError,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+const CREDENTIALS_FILE_NAME: &str = "credentials.toml";
+#[cfg(feature = "keyring")]
+const KEYRING_SERVICE: &str = "reclaim-cli";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CredentialsFile {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    profiles: BTreeMap<String, StoredCredential>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StoredCredential {
+    api_key: String,
+}
+
+/// The backends `CredentialStore::remove` took a key out of, and the ones it could not clean.
+#[derive(Debug, Default)]
+pub struct Removal {
+    pub removed: Vec<SettingSource>,
+    pub errors: Vec<CliError>,
+}
+
+/// API keys stored by `reclaim auth login`, keyed by profile name.
+///
+/// The file backend lives next to the config file and must only be readable by its owner.
+#[derive(Debug)]
+pub struct CredentialStore {
+    path: PathBuf,
+}
+
+impl CredentialStore {
+    pub fn new(config_dir: &Path) -> Self {
+        Self {
+            path: config_dir.join(CREDENTIALS_FILE_NAME),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Looks up a key for `profile`, preferring the OS keyring over the credentials file.
+    pub fn load(&self, profile: &str) -> Result<Option<(String, SettingSource)>, CliError> {
+        if let Some(api_key) = keyring_get(profile)? {
+            return Ok(Some((api_key, SettingSource::Keyring)));
+        }
+
+        Ok(self
+            .read_file()?
+            .profiles
+            .remove(profile)
+            .map(|credential| (credential.api_key, SettingSource::CredentialsFile)))
+    }
+
+    pub fn save(
+        &self,
+        profile: &str,
+        api_key: &str,
+        backend: CredentialBackend,
+    ) -> Result<(), CliError> {
+        match backend {
+            CredentialBackend::File => {
+                let mut file = self.read_file()?;
+                file.profiles.insert(
+                    profile.to_string(),
+                    StoredCredential {
+                        api_key: api_key.to_string(),
+                    },
+                );
+                self.write_file(&file)
+            }
+            CredentialBackend::Keyring => keyring_set(profile, api_key),
+        }
+    }
+
+    /// Checks that `backend` can take a key for `profile`, so `auth login` fails before it
+    /// contacts Reclaim rather than after.
+    pub fn check_backend(&self, profile: &str, backend: CredentialBackend) -> Result<(), CliError> {
+        match backend {
+            CredentialBackend::File => self.read_file().map(drop),
+            CredentialBackend::Keyring => keyring_get(profile).map(drop),
+        }
+    }
+
+    /// Removes the key for `profile` from each backend independently, so a failing keyring
+    /// does not keep the file entry around.
+    ///
+    /// The file is not validated first: one other users can read is rewritten owner-only,
+    /// and one that cannot be read or parsed is deleted.
+    pub fn remove(&self, profile: &str) -> Removal {
+        let mut removal = Removal::default();
+
+        match keyring_delete(profile) {
+            Ok(true) => removal.removed.push(SettingSource::Keyring),
+            Ok(false) => {}
+            Err(error) => removal.errors.push(error),
+        }
+
+        match self.remove_from_file(profile) {
+            Ok(true) => removal.removed.push(SettingSource::CredentialsFile),
+            Ok(false) => {}
+            Err(error) => removal.errors.push(error),
+        }
+
+        removal
+    }
+
+    fn remove_from_file(&self, profile: &str) -> Result<bool, CliError> {
+        let parsed = match fs::read_to_string(&self.path) {
+            Ok(raw) => toml::from_str::<CredentialsFile>(&raw).ok(),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(_) => None,
+        };
+
+        match parsed {
+            Some(mut file) => {
+                let removed = file.profiles.remove(profile).is_some();
+                if removed || !permissions_are_private(&self.path) {
+                    self.write_file(&file)?;
+                }
+                Ok(removed)
+            }
+            // Nothing in it can be used, so there is nothing worth keeping.
+            None => fs::remove_file(&self.path).map(|()| true).map_err(|error| {
+                CliError::Output(format!(
+                    "Could not remove credentials file {}: {error}",
+                    self.path.display()
+                ))
+            }),
+        }
+    }
+
+    fn read_file(&self) -> Result<CredentialsFile, CliError> {
+        let raw = match fs::read_to_string(&self.path) {
+            Ok(raw) => raw,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(CredentialsFile::default());
+            }
+            Err(error) => {
+                return Err(CliError::InvalidInput {
+                    message: format!(
+                        "Could not read credentials file {}: {error}",
+                        self.path.display()
+                    ),
+                    hint: Some(
+                        "Check the file permissions, or delete it with: reclaim auth logout"
+                            .to_string(),
+                    ),
+                });
+            }
+        };
+
+        check_permissions(&self.path)?;
+
+        toml::from_str::<CredentialsFile>(&raw).map_err(|error| CliError::InvalidInput {
+            message: format!("Invalid credentials file {}: {error}", self.path.display()),
+            hint: Some(
+                "Delete it with reclaim auth logout, then run: reclaim auth login".to_string(),
+            ),
+        })
+    }
+
+    fn write_file(&self, file: &CredentialsFile) -> Result<(), CliError> {
+        let rendered = toml::to_string_pretty(file).map_err(|error| {
+            CliError::Output(format!("Could not render credentials TOML: {error}"))
+        })?;
+
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent).map_err(|error| {
+                    CliError::Output(format!(
+                        "Could not create config directory {}: {error}",
+                        parent.display()
+                    ))
+                })?;
+            }
+        }
+
+        write_private_file(&self.path, rendered.as_bytes()).map_err(|error| {
+            CliError::Output(format!(
+                "Could not write credentials file {}: {error}",
+                self.path.display()
+            ))
+        })
+    }
+}
+
+#[cfg(unix)]
+fn permissions_are_private(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::metadata(path).is_ok_and(|metadata| metadata.permissions().mode() & 0o077 == 0)
+}
+
+#[cfg(not(unix))]
+fn permissions_are_private(_path: &Path) -> bool {
+    true
+}
+
+#[cfg(unix)]
+fn check_permissions(path: &Path) -> Result<(), CliError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = fs::metadata(path)
+        .map_err(|error| CliError::InvalidInput {
+            message: format!(
+                "Could not inspect credentials file {}: {error}",
+                path.display()
+            ),
+            hint: None,
+        })?
+        .permissions()
+        .mode();
+
+    if mode & 0o077 != 0 {
+        return Err(CliError::InvalidInput {
+            message: format!(
+                "Credentials file {} is accessible by other users (mode {:o}).",
+                path.display(),
+                mode & 0o777
+            ),
+            hint: Some(format!("Restrict it with: chmod 600 {}", path.display())),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_permissions(_path: &Path) -> Result<(), CliError> {
+    Ok(())
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_entry(profile: &str) -> Result<keyring::Entry, CliError> {
+    keyring::Entry::new(KEYRING_SERVICE, profile).map_err(keyring_error)
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_error(error: keyring::Error) -> CliError {
+    CliError::InvalidInput {
+        message: format!("OS keyring error: {error}"),
+        hint: Some("Retry with --store file to use the credentials file instead.".to_string()),
+    }
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_get(profile: &str) -> Result<Option<String>, CliError> {
+    match keyring_entry(profile)?.get_password() {
+        Ok(api_key) => Ok(Some(api_key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(error) => Err(keyring_error(error)),
+    }
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_set(profile: &str, api_key: &str) -> Result<(), CliError> {
+    keyring_entry(profile)?
+        .set_password(api_key)
+        .map_err(keyring_error)
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_delete(profile: &str) -> Result<bool, CliError> {
+    match keyring_entry(profile)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(error) => Err(keyring_error(error)),
+    }
+}
+
+#[cfg(not(feature = "keyring"))]
+fn keyring_get(_profile: &str) -> Result<Option<String>, CliError> {
+    Ok(None)
+}
+
+#[cfg(not(feature = "keyring"))]
+fn keyring_set(_profile: &str, _api_key: &str) -> Result<(), CliError> {
+    Err(CliError::InvalidInput {
+        message: "This build of reclaim does not include OS keyring support.".to_string(),
+        hint: Some(
+            "Use --store file, or rebuild with: cargo install --path . --features keyring"
+                .to_string(),
+        ),
+    })
+}
+
+#[cfg(not(feature = "keyring"))]
+fn keyring_delete(_profile: &str) -> Result<bool, CliError> {
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store(name: &str) -> CredentialStore {
+        let dir =
+            std::env::temp_dir().join(format!("reclaim-credentials-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        CredentialStore::new(&dir)
+    }
+
+    #[test]
+    fn file_backend_round_trips_per_profile() {
+        let store = temp_store("round-trip");
+        store
+            .save("work", "work-key", CredentialBackend::File)
+            .unwrap();
+        store
+            .save("personal", "personal-key", CredentialBackend::File)
+            .unwrap();
+
+        assert_eq!(
+            store.load("work").unwrap(),
+            Some(("work-key".to_string(), SettingSource::CredentialsFile))
+        );
+        let removal = store.remove("work");
+        assert_eq!(removal.removed, vec![SettingSource::CredentialsFile]);
+        assert!(removal.errors.is_empty());
+        assert_eq!(store.load("work").unwrap(), None);
+        assert!(store.load("personal").unwrap().is_some());
+
+        fs::remove_dir_all(store.path().parent().unwrap()).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn file_backend_writes_owner_only_file_and_rejects_loose_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let store = temp_store("permissions");
+        store.save("work", "key", CredentialBackend::File).unwrap();
+        let mode = fs::metadata(store.path()).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        fs::set_permissions(store.path(), fs::Permissions::from_mode(0o644)).unwrap();
+        let error = store.load("work").unwrap_err();
+        assert!(error.to_string().contains("accessible by other users"));
+
+        fs::remove_dir_all(store.path().parent().unwrap()).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn remove_repairs_loose_permissions_and_deletes_unusable_files() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let store = temp_store("remove");
+        store
+            .save("work", "work-key", CredentialBackend::File)
+            .unwrap();
+        store
+            .save("personal", "personal-key", CredentialBackend::File)
+            .unwrap();
+        fs::set_permissions(store.path(), fs::Permissions::from_mode(0o644)).unwrap();
+
+        let removal = store.remove("work");
+        assert_eq!(removal.removed, vec![SettingSource::CredentialsFile]);
+        assert!(removal.errors.is_empty());
+        let mode = fs::metadata(store.path()).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(store.load("personal").unwrap().is_some());
+
+        fs::write(store.path(), "not = [valid").unwrap();
+        assert!(store.load("personal").is_err());
+        let removal = store.remove("personal");
+        assert_eq!(removal.removed, vec![SettingSource::CredentialsFile]);
+        assert!(!store.path().exists());
+        assert!(store.remove("personal").removed.is_empty());
+
+        fs::remove_dir_all(store.path().parent().unwrap()).unwrap();
+    }
+}
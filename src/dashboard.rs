@@ -1,12 +1,24 @@
 use crate::{
+    cache,
+    cli::{EventCategory, Priority, RoundMode, TaskStatusFilter},
+    dashboard_agenda::{Agenda, BlockKind},
+    dashboard_command::{self, CommandHistory, ExCommand, FilterTerm, SortKey, SortOrder},
+    datetime::{self, TimeContext, Zone},
+    duration,
     error::CliError,
-    reclaim_api::{ReclaimApi, Task, TaskFilter},
+    fields::{self, UnsetMode},
+    reclaim_api::{
+        CreateTaskRequest, Outcome, PlannedRequest, PlannerAction, ReclaimApi, Task, TaskFilter,
+    },
+    task_query::TaskQuery,
 };
+use clap::ValueEnum;
 use crossterm::{
     event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use futures_util::{stream::FuturesUnordered, StreamExt};
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Constraint, Direction, Layout, Rect},
@@ -15,32 +27,423 @@ use ratatui::{
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
     Frame, Terminal,
 };
+use serde_json::{json, Map, Value};
 use std::{
-    cmp,
+    cmp::{self, Ordering},
+    collections::{btree_map::Entry, BTreeMap, HashMap},
     io::{self, Stdout},
     time::Duration,
 };
 
 const POLL_INTERVAL: Duration = Duration::from_millis(200);
-const DASHBOARD_HINT: &str = "j/k move  g/G jump  r refresh  ? help  :q/Esc/Ctrl+C quit";
+/// How long the loop waits for a task change to finish before checking for keys again.
+const CHANGE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const DASHBOARD_HINT: &str =
+    "j/k move  / search  f filter  a add  x done  s start/stop  z snooze  p priority  e due  D delete  ? help  Esc quit";
+const AGENDA_HINT: &str =
+    "h/l page  w day/week  t today  j/k move  Enter open task  r reload  Tab tasks  ? help  Esc quit";
+const PRIORITIES: [&str; 4] = ["P1", "P2", "P3", "P4"];
+/// Choices in the snooze picker, as label and time input. A "Custom..." entry follows.
+const SNOOZE_PRESETS: [(&str, &str); 4] = [
+    ("1 hour", "+1h"),
+    ("3 hours", "+3h"),
+    ("Tomorrow 9am", "tomorrow 9am"),
+    ("Next Monday 9am", "next monday 9:00"),
+];
 
 type DashboardTerminal = Terminal<CrosstermBackend<Stdout>>;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 enum AppAction {
     None,
     Quit,
     Refresh,
+    Send(TaskRequest),
+    /// `:w`: send the staged edits, then wait for every change still on its way.
+    Write(Vec<TaskRequest>),
+    /// `:wq`: like `Write`, then quit.
+    WriteQuit(Vec<TaskRequest>),
+}
+
+/// A change to one task. The row is updated right away and restored if Reclaim rejects it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum TaskChange {
+    Done,
+    Start,
+    Stop,
+    Snooze(String),
+    Priority(&'static str),
+    Due(Option<String>),
+    /// Fields from `:set`/`:unset`, sent as a PATCH body.
+    Fields(Map<String, Value>),
+    Delete,
+}
+
+impl TaskChange {
+    /// What the change did, for the status bar, e.g. "priority P1".
+    fn summary(&self) -> String {
+        match self {
+            TaskChange::Done => "done".to_string(),
+            TaskChange::Start => "started".to_string(),
+            TaskChange::Stop => "stopped".to_string(),
+            TaskChange::Snooze(until) => format!("snoozed until {until}"),
+            TaskChange::Priority(priority) => format!("priority {priority}"),
+            TaskChange::Due(Some(due)) => format!("due {due}"),
+            TaskChange::Due(None) => "due date cleared".to_string(),
+            TaskChange::Fields(fields) => {
+                let names = fields.keys().map(String::as_str).collect::<Vec<_>>();
+                format!("set {}", names.join(", "))
+            }
+            TaskChange::Delete => "deleted".to_string(),
+        }
+    }
+
+    /// The optimistic update; the response from Reclaim replaces it.
+    fn apply(&self, task: &mut Task) {
+        match self {
+            TaskChange::Done => task.status = Some("COMPLETE".to_string()),
+            TaskChange::Start => task.status = Some("IN_PROGRESS".to_string()),
+            TaskChange::Stop => task.status = Some("SCHEDULED".to_string()),
+            TaskChange::Snooze(until) => {
+                task.extra
+                    .insert("snoozeUntil".to_string(), Value::String(until.clone()));
+            }
+            TaskChange::Priority(priority) => task.priority = Some(priority.to_string()),
+            TaskChange::Due(due) => task.due = due.clone(),
+            TaskChange::Fields(fields) => {
+                if let Ok(updated) = merge_fields(task, fields) {
+                    *task = updated;
+                }
+            }
+            TaskChange::Delete => {}
+        }
+    }
+}
+
+/// `task` with a PATCH body merged in, as Reclaim would apply it.
+fn merge_fields(task: &Task, fields: &Map<String, Value>) -> Result<Task, String> {
+    let Ok(Value::Object(mut document)) = serde_json::to_value(task) else {
+        return Err("the task could not be read".to_string());
+    };
+    fields::deep_merge(&mut document, fields.clone());
+    serde_json::from_value(Value::Object(document)).map_err(|error| error.to_string())
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct TaskRequest {
+    task_id: u64,
+    change: TaskChange,
+}
+
+/// A row as it was before its optimistic update.
+#[derive(Debug)]
+struct Rollback {
+    task: Task,
+    index: usize,
+}
+
+/// `:set`/`:unset` edits to one task, shown on its row but only sent by `:w`.
+#[derive(Debug, Clone)]
+struct StagedEdit {
+    fields: Map<String, Value>,
+    /// The row before the first edit, restored if Reclaim rejects them.
+    before: Task,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum InputField {
+    Due,
+    Snooze,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Popup {
+    Snooze {
+        task_id: u64,
+        selected: usize,
+    },
+    Input {
+        task_id: u64,
+        field: InputField,
+        text: String,
+        error: Option<String>,
+    },
+    ConfirmDelete {
+        task_id: u64,
+    },
+    Create(CreateForm),
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum FormField {
+    Title,
+    Notes,
+    Priority,
+    Due,
+    Duration,
+    Category,
+    MinChunk,
+    MaxChunk,
+}
+
+impl FormField {
+    const ALL: [FormField; 8] = [
+        FormField::Title,
+        FormField::Notes,
+        FormField::Priority,
+        FormField::Due,
+        FormField::Duration,
+        FormField::Category,
+        FormField::MinChunk,
+        FormField::MaxChunk,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            FormField::Title => "Title",
+            FormField::Notes => "Notes",
+            FormField::Priority => "Priority",
+            FormField::Due => "Due",
+            FormField::Duration => "Duration",
+            FormField::Category => "Category",
+            FormField::MinChunk => "Min chunk",
+            FormField::MaxChunk => "Max chunk",
+        }
+    }
+
+    fn placeholder(self) -> &'static str {
+        match self {
+            FormField::Title => "required",
+            FormField::Notes => "",
+            FormField::Priority => "P1-P4",
+            FormField::Due => "e.g. friday 5pm, 2026-03-01, +2d",
+            FormField::Duration => "e.g. 45m, 2h30m",
+            FormField::Category => "WORK or PERSONAL",
+            FormField::MinChunk => "e.g. 30m",
+            FormField::MaxChunk => "e.g. 2h",
+        }
+    }
+}
+
+/// The `a` form. Fields take the same input as the matching `reclaim create` flags.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+struct CreateForm {
+    values: [String; 8],
+    focus: usize,
+    error: Option<String>,
+}
+
+impl CreateForm {
+    fn value(&self, field: FormField) -> &str {
+        self.values[field as usize].trim()
+    }
+
+    fn focus_next(&mut self, steps: usize) {
+        self.focus = (self.focus + steps) % FormField::ALL.len();
+    }
+
+    /// Builds the request like `reclaim create` does, or names the field to fix.
+    fn build_request(
+        &self,
+        time: &TimeContext,
+        default_category: EventCategory,
+    ) -> Result<CreateTaskRequest, (FormField, String)> {
+        let optional = |field| Some(self.value(field)).filter(|value| !value.is_empty());
+        let failed = |field| move |error: CliError| (field, error.to_string());
+
+        let title = self.value(FormField::Title);
+        if title.is_empty() {
+            return Err((FormField::Title, "Title is required.".to_string()));
+        }
+        let priority = optional(FormField::Priority)
+            .map(|raw| {
+                Priority::from_str(raw, true).map_err(|_| {
+                    (
+                        FormField::Priority,
+                        format!("Invalid priority '{raw}': use P1, P2, P3 or P4."),
+                    )
+                })
+            })
+            .transpose()?;
+        let due = optional(FormField::Due)
+            .map(|raw| datetime::normalize_timestamp(time, "due", raw))
+            .transpose()
+            .map_err(failed(FormField::Due))?;
+        let category = optional(FormField::Category)
+            .map(|raw| {
+                EventCategory::from_str(raw, true).map_err(|_| {
+                    (
+                        FormField::Category,
+                        format!("Invalid category '{raw}': use WORK or PERSONAL."),
+                    )
+                })
+            })
+            .transpose()?
+            .unwrap_or(default_category);
+
+        let chunks = |field: FormField, name: &str| {
+            optional(field)
+                .map(|raw| duration::parse_chunks(name, raw, RoundMode::Strict))
+                .transpose()
+                .map_err(failed(field))
+        };
+        let total = chunks(FormField::Duration, "duration")?;
+        let min = chunks(FormField::MinChunk, "min chunk")?;
+        let max = chunks(FormField::MaxChunk, "max chunk")?;
+        let plan = duration::plan_chunks(total, min, max).map_err(|error| {
+            let field = match total {
+                None => FormField::Duration,
+                Some(total) if min.unwrap_or(1) <= total && max.is_some_and(|max| max > total) => {
+                    FormField::MaxChunk
+                }
+                Some(_) => FormField::MinChunk,
+            };
+            (field, error.to_string())
+        })?;
+
+        Ok(CreateTaskRequest {
+            title: title.to_string(),
+            notes: optional(FormField::Notes).map(str::to_string),
+            priority: priority.map(|priority| priority.as_str().to_owned()),
+            due,
+            time_chunks_required: plan.time_chunks_required,
+            event_category: Some(category.as_str().to_owned()),
+            min_chunk_size: plan.min_chunk_size,
+            max_chunk_size: plan.max_chunk_size,
+            always_private: Some(true),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Tab {
+    Tasks,
+    Agenda,
+}
+
+/// Filter bar settings (`f`). They narrow the list on top of `--all` and the query flags.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+struct ViewFilters {
+    status: Option<TaskStatusFilter>,
+    priorities: Vec<&'static str>,
+    category: Option<EventCategory>,
+}
+
+impl ViewFilters {
+    fn is_empty(&self) -> bool {
+        self.status.is_none() && self.priorities.is_empty() && self.category.is_none()
+    }
+
+    fn matches(&self, task: &Task) -> bool {
+        self.status
+            .is_none_or(|status| crate::task_matches_status(task, status))
+            && (self.priorities.is_empty()
+                || task.priority.as_deref().is_some_and(|priority| {
+                    self.priorities
+                        .iter()
+                        .any(|wanted| wanted.eq_ignore_ascii_case(priority))
+                }))
+            && self.category.is_none_or(|category| {
+                task.extra
+                    .get("eventCategory")
+                    .and_then(Value::as_str)
+                    .is_some_and(|value| value.eq_ignore_ascii_case(category.as_str()))
+            })
+    }
+
+    /// Steps through "any" and then every `--filter` bucket.
+    fn cycle_status(&mut self) {
+        let buckets = TaskStatusFilter::value_variants();
+        self.status = match self.status {
+            None => buckets.first().copied(),
+            Some(current) => buckets
+                .iter()
+                .position(|bucket| *bucket == current)
+                .and_then(|index| buckets.get(index + 1))
+                .copied(),
+        };
+    }
+
+    fn toggle_priority(&mut self, priority: &'static str) {
+        match self
+            .priorities
+            .iter()
+            .position(|wanted| *wanted == priority)
+        {
+            Some(index) => {
+                self.priorities.remove(index);
+            }
+            None => {
+                self.priorities.push(priority);
+                self.priorities.sort_unstable();
+            }
+        }
+    }
+
+    fn cycle_category(&mut self) {
+        self.category = match self.category {
+            None => Some(EventCategory::Work),
+            Some(EventCategory::Work) => Some(EventCategory::Personal),
+            Some(EventCategory::Personal) => None,
+        };
+    }
+
+    /// e.g. "status open, P1/P2, WORK"; empty when nothing is set.
+    fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(status) = self.status {
+            parts.push(format!("status {}", status.as_str()));
+        }
+        if !self.priorities.is_empty() {
+            parts.push(self.priorities.join("/"));
+        }
+        if let Some(category) = self.category {
+            parts.push(category.as_str().to_string());
+        }
+        parts.join(", ")
+    }
 }
 
 #[derive(Debug)]
 struct DashboardApp {
     tasks: Vec<Task>,
     filter: TaskFilter,
+    query: TaskQuery,
+    view_filters: ViewFilters,
+    /// Ids of the tasks that pass `view_filters`, in list order. The list selection indexes
+    /// into this.
+    view: Vec<u64>,
+    /// Search text from `/`; matches are highlighted and `n`/`N` jump between them.
+    search: String,
+    /// True while `/` input is being typed.
+    search_input: bool,
+    /// True while the filter bar has the keyboard.
+    filter_bar: bool,
+    /// Order from `:sort`; `None` keeps the order Reclaim returned.
+    sort: Option<SortOrder>,
+    tab: Tab,
+    agenda: Agenda,
+    agenda_list: ListState,
+    /// Set when the agenda range changed; the next frame shows "Loading..." and fetches it.
+    agenda_pending: bool,
+    history: CommandHistory,
     list_state: ListState,
     show_help: bool,
+    popup: Option<Popup>,
     command_buffer: String,
     status_message: Option<String>,
+    /// Set when the app starts from cached tasks; the first frame then triggers a refresh.
+    refresh_pending: bool,
+    /// A task from the `a` form, sent after the next frame shows "Creating...".
+    create_pending: Option<CreateTaskRequest>,
+    /// Category for new tasks when the form leaves it empty.
+    event_category: EventCategory,
+    /// Tasks with a change on its way to Reclaim, and how to undo it.
+    in_flight: HashMap<u64, Rollback>,
+    /// Edits waiting for `:w`, by task id.
+    staged: BTreeMap<u64, StagedEdit>,
+    /// Reference for due/snooze input; the event loop moves "now" forward before each key.
+    time: TimeContext,
 }
 
 impl DashboardApp {
@@ -51,32 +454,89 @@ impl DashboardApp {
         }
 
         Self {
+            view: tasks.iter().map(|task| task.id).collect(),
             tasks,
             filter,
+            query: TaskQuery::default(),
+            view_filters: ViewFilters::default(),
+            search: String::new(),
+            search_input: false,
+            filter_bar: false,
+            sort: None,
+            tab: Tab::Tasks,
+            agenda: Agenda::new(chrono::Local::now().date_naive()),
+            agenda_list: ListState::default(),
+            agenda_pending: false,
+            history: CommandHistory::default(),
             list_state,
             show_help: false,
+            popup: None,
             command_buffer: String::new(),
             status_message: None,
+            refresh_pending: false,
+            create_pending: None,
+            event_category: EventCategory::Work,
+            in_flight: HashMap::new(),
+            staged: BTreeMap::new(),
+            time: TimeContext::new(Zone::Local),
         }
     }
 
+    fn with_query(mut self, query: TaskQuery) -> Self {
+        self.query = query;
+        self
+    }
+
+    fn with_time(mut self, time: TimeContext) -> Self {
+        self.time = time;
+        self.agenda = Agenda::new(time.today());
+        self
+    }
+
+    fn with_event_category(mut self, event_category: EventCategory) -> Self {
+        self.event_category = event_category;
+        self
+    }
+
+    fn with_history(mut self, history: CommandHistory) -> Self {
+        self.history = history;
+        self
+    }
+
     fn selected_index(&self) -> Option<usize> {
         self.list_state.selected()
     }
 
     fn selected_task(&self) -> Option<&Task> {
-        self.selected_index().and_then(|idx| self.tasks.get(idx))
+        let task_id = self.view.get(self.selected_index()?)?;
+        self.tasks.iter().find(|task| task.id == *task_id)
+    }
+
+    /// The rows of the list, in display order.
+    fn listed_tasks(&self) -> impl Iterator<Item = &Task> {
+        self.view
+            .iter()
+            .filter_map(|task_id| self.tasks.iter().find(|task| task.id == *task_id))
+    }
+
+    fn visible_tasks(&self) -> impl Iterator<Item = &Task> {
+        self.tasks
+            .iter()
+            .filter(|task| self.view_filters.matches(task))
     }
 
     fn replace_tasks(&mut self, tasks: Vec<Task>) {
         self.tasks = tasks;
-        if self.tasks.is_empty() {
-            self.list_state.select(None);
-        } else {
-            let selected = self.selected_index().unwrap_or(0);
-            let new_index = cmp::min(selected, self.tasks.len() - 1);
-            self.list_state.select(Some(new_index));
+        // Staged edits stay on top of the fresh rows until `:w` sends them.
+        for row in &mut self.tasks {
+            if let Some(edit) = self.staged.get_mut(&row.id) {
+                edit.before = row.clone();
+                if let Ok(updated) = merge_fields(row, &edit.fields) {
+                    *row = updated;
+                }
+            }
         }
+        self.update_view();
 
         let count = self.tasks.len();
         self.status_message = Some(format!(
@@ -85,38 +545,114 @@ impl DashboardApp {
         ));
     }
 
+    /// Recomputes the visible rows after tasks or filters changed. The selected task stays
+    /// selected while it is visible; otherwise the selection keeps its position.
+    fn update_view(&mut self) {
+        let selected_id = self
+            .selected_index()
+            .and_then(|index| self.view.get(index))
+            .copied();
+        let mut visible = self.visible_tasks().collect::<Vec<_>>();
+        if let Some(order) = self.sort {
+            visible.sort_by(|a, b| {
+                let ordering = compare_tasks(a, b, order.key);
+                if order.descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+        self.view = visible.into_iter().map(|task| task.id).collect();
+
+        match selected_id.and_then(|id| self.view.iter().position(|visible| *visible == id)) {
+            Some(index) => self.list_state.select(Some(index)),
+            None if self.view.is_empty() => self.list_state.select(None),
+            None => {
+                let selected = self.selected_index().unwrap_or(0);
+                self.list_state
+                    .select(Some(cmp::min(selected, self.view.len() - 1)));
+            }
+        }
+    }
+
+    fn select_task(&mut self, task_id: u64) -> bool {
+        let index = self.view.iter().position(|visible| *visible == task_id);
+        if index.is_some() {
+            self.list_state.select(index);
+        }
+        index.is_some()
+    }
+
+    /// List positions of visible tasks whose title or notes match the search.
+    fn search_matches(&self) -> Vec<usize> {
+        if self.search.trim().is_empty() {
+            return Vec::new();
+        }
+        self.listed_tasks()
+            .enumerate()
+            .filter(|(_, task)| task_matches_search(task, &self.search))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Selects the next match after the selection (`forward`) or the one before it, wrapping
+    /// around. `from_selection` also accepts the selected row itself, for live search.
+    fn jump_to_match(&mut self, forward: bool, from_selection: bool) {
+        let matches = self.search_matches();
+        if matches.is_empty() {
+            self.set_status(format!("No matches for '{}'.", self.search));
+            return;
+        }
+
+        let current = self.selected_index().unwrap_or(0);
+        let next = if forward {
+            matches
+                .iter()
+                .find(|index| **index > current || (from_selection && **index == current))
+                .or(matches.first())
+        } else {
+            matches
+                .iter()
+                .rev()
+                .find(|index| **index < current)
+                .or(matches.last())
+        };
+        self.list_state.select(next.copied());
+    }
+
     fn set_status(&mut self, message: impl Into<String>) {
         self.status_message = Some(message.into());
     }
 
     fn select_next(&mut self) {
-        if self.tasks.is_empty() {
+        if self.view.is_empty() {
             self.list_state.select(None);
             return;
         }
 
         let next = match self.selected_index() {
-            Some(index) if index + 1 < self.tasks.len() => index + 1,
+            Some(index) if index + 1 < self.view.len() => index + 1,
             _ => 0,
         };
         self.list_state.select(Some(next));
     }
 
     fn select_previous(&mut self) {
-        if self.tasks.is_empty() {
+        if self.view.is_empty() {
             self.list_state.select(None);
             return;
         }
 
         let previous = match self.selected_index() {
-            Some(0) | None => self.tasks.len() - 1,
+            Some(0) | None => self.view.len() - 1,
             Some(index) => index - 1,
         };
         self.list_state.select(Some(previous));
     }
 
     fn select_first(&mut self) {
-        if self.tasks.is_empty() {
+        if self.view.is_empty() {
             self.list_state.select(None);
         } else {
             self.list_state.select(Some(0));
@@ -124,21 +660,46 @@ impl DashboardApp {
     }
 
     fn select_last(&mut self) {
-        if self.tasks.is_empty() {
+        if self.view.is_empty() {
             self.list_state.select(None);
         } else {
-            self.list_state.select(Some(self.tasks.len() - 1));
+            self.list_state.select(Some(self.view.len() - 1));
         }
     }
 
     fn handle_key(&mut self, key: KeyEvent) -> AppAction {
-        if is_quit_key(key) {
+        let modal = self.popup.is_some()
+            || self.search_input
+            || self.filter_bar
+            || !self.command_buffer.is_empty();
+        if modal && key.code != KeyCode::Esc && is_quit_key(key) {
             return AppAction::Quit;
         }
-
+        if let Some(popup) = self.popup.take() {
+            return self.handle_popup_key(popup, key);
+        }
+        if self.search_input {
+            self.handle_search_key(key);
+            return AppAction::None;
+        }
+        if self.filter_bar {
+            self.handle_filter_key(key);
+            return AppAction::None;
+        }
         if !self.command_buffer.is_empty() {
             return self.handle_command_key(key);
         }
+        if key.code == KeyCode::Esc && !self.search.is_empty() {
+            self.search.clear();
+            self.set_status("Search cleared.");
+            return AppAction::None;
+        }
+        if key.code == KeyCode::Esc {
+            return self.quit();
+        }
+        if is_quit_key(key) {
+            return AppAction::Quit;
+        }
 
         if self.show_help {
             if matches!(key.code, KeyCode::Char('?') | KeyCode::Enter) {
@@ -147,7 +708,18 @@ impl DashboardApp {
             return AppAction::None;
         }
 
+        let global = matches!(
+            key.code,
+            KeyCode::Char('?' | ':' | '1' | '2') | KeyCode::Tab
+        );
+        if self.tab == Tab::Agenda && !global {
+            return self.handle_agenda_key(key);
+        }
+
         match key.code {
+            KeyCode::Tab if self.tab == Tab::Agenda => self.show_tab(Tab::Tasks),
+            KeyCode::Tab | KeyCode::Char('2') => self.show_tab(Tab::Agenda),
+            KeyCode::Char('1') => self.show_tab(Tab::Tasks),
             KeyCode::Char('?') => {
                 self.show_help = true;
                 self.status_message = Some("Help opened. Press ? or Enter to close.".to_string());
@@ -155,7 +727,10 @@ impl DashboardApp {
             }
             KeyCode::Char(':') => {
                 self.command_buffer = ":".to_string();
-                self.status_message = Some("Command mode: type :q to quit.".to_string());
+                self.status_message = Some(
+                    "Command mode: Tab completes, Up/Down browse history, :help lists commands."
+                        .to_string(),
+                );
                 AppAction::None
             }
             KeyCode::Char('j') | KeyCode::Down => {
@@ -175,28 +750,547 @@ impl DashboardApp {
                 AppAction::None
             }
             KeyCode::Char('r') => AppAction::Refresh,
+            KeyCode::Char('/') => {
+                self.search.clear();
+                self.search_input = true;
+                self.set_status("Search: type to find, Enter to keep, Esc to clear.");
+                AppAction::None
+            }
+            KeyCode::Char('f') => {
+                self.filter_bar = true;
+                self.set_status(
+                    "Filters: s status  1-4 priority  c category  0 clear  f/Enter/Esc done",
+                );
+                AppAction::None
+            }
+            KeyCode::Char('n' | 'N') if self.search.is_empty() => {
+                self.set_status("No search to repeat; press / to search.");
+                AppAction::None
+            }
+            KeyCode::Char('n') => {
+                self.jump_to_match(true, false);
+                AppAction::None
+            }
+            KeyCode::Char('N') => {
+                self.jump_to_match(false, false);
+                AppAction::None
+            }
+            KeyCode::Char('a') => {
+                self.popup = Some(Popup::Create(CreateForm::default()));
+                AppAction::None
+            }
+            KeyCode::Char('x') => self.change_selected(|_| Some(TaskChange::Done)),
+            KeyCode::Char('s') => self.change_selected(|task| {
+                Some(match task.status.as_deref() {
+                    Some("IN_PROGRESS") => TaskChange::Stop,
+                    _ => TaskChange::Start,
+                })
+            }),
+            KeyCode::Char('p') => self.change_selected(|task| Some(cycle_priority(task, 1))),
+            KeyCode::Char('P') => self.change_selected(|task| Some(cycle_priority(task, 3))),
+            KeyCode::Char('z') => self.open_popup(|task| Popup::Snooze {
+                task_id: task.id,
+                selected: 0,
+            }),
+            KeyCode::Char('e') => self.open_popup(|task| Popup::Input {
+                task_id: task.id,
+                field: InputField::Due,
+                text: task.due.clone().unwrap_or_default(),
+                error: None,
+            }),
+            KeyCode::Char('D') => self.open_popup(|task| Popup::ConfirmDelete { task_id: task.id }),
             _ => AppAction::None,
         }
     }
 
+    /// The selected task, unless there is none or its last change is still being sent.
+    fn selected_for_change(&mut self) -> Option<&Task> {
+        let Some(task_id) = self.selected_task().map(|task| task.id) else {
+            self.set_status("No task selected.");
+            return None;
+        };
+        if self.in_flight.contains_key(&task_id) {
+            self.set_status(format!(
+                "Task #{task_id} is still saving; try again in a moment."
+            ));
+            return None;
+        }
+        self.selected_task()
+    }
+
+    fn change_selected(&mut self, change: impl FnOnce(&Task) -> Option<TaskChange>) -> AppAction {
+        let Some((task_id, change)) = self
+            .selected_for_change()
+            .and_then(|task| Some((task.id, change(task)?)))
+        else {
+            return AppAction::None;
+        };
+        self.request_change(task_id, change)
+    }
+
+    fn open_popup(&mut self, popup: impl FnOnce(&Task) -> Popup) -> AppAction {
+        if let Some(popup) = self.selected_for_change().map(popup) {
+            self.popup = Some(popup);
+        }
+        AppAction::None
+    }
+
+    /// Applies `change` to the row now and asks the event loop to send it.
+    fn request_change(&mut self, task_id: u64, change: TaskChange) -> AppAction {
+        let Some(index) = self.tasks.iter().position(|task| task.id == task_id) else {
+            self.set_status(format!("Task #{task_id} is no longer listed."));
+            return AppAction::None;
+        };
+
+        let previous = self.tasks[index].clone();
+        if change == TaskChange::Delete {
+            self.tasks.remove(index);
+        } else {
+            change.apply(&mut self.tasks[index]);
+        }
+        self.update_view();
+        self.in_flight.insert(
+            task_id,
+            Rollback {
+                task: previous,
+                index,
+            },
+        );
+        self.set_status(format!("Saving #{task_id}: {}...", change.summary()));
+
+        AppAction::Send(TaskRequest { task_id, change })
+    }
+
+    /// Takes Reclaim's answer to a change: the returned task replaces the row, and a
+    /// failure restores the row as it was. So does a change `--dry-run` only planned,
+    /// since Reclaim never saw it.
+    fn finish_change(
+        &mut self,
+        request: TaskRequest,
+        result: Result<Outcome<Option<Task>>, CliError>,
+    ) {
+        let rollback = self.in_flight.remove(&request.task_id);
+        let summary = request.change.summary();
+        match result {
+            Ok(Outcome::Sent(updated)) => {
+                let title = updated
+                    .as_ref()
+                    .or(rollback.as_ref().map(|rollback| &rollback.task))
+                    .map(|task| task.title.clone())
+                    .unwrap_or_default();
+                if let Some(updated) = updated {
+                    if let Some(row) = self.tasks.iter_mut().find(|task| task.id == updated.id) {
+                        *row = updated;
+                    }
+                }
+                self.set_status(format!("#{} {title}: {summary}.", request.task_id));
+            }
+            Ok(Outcome::Planned(planned)) => {
+                self.roll_back(&request.change, rollback);
+                self.set_status(format!(
+                    "Dry run: #{} ({summary}) not sent; would {} {}.",
+                    request.task_id, planned.method, planned.url
+                ));
+            }
+            Err(error) => {
+                self.roll_back(&request.change, rollback);
+                self.set_status(format!(
+                    "Could not save #{} ({summary}): {}. Change undone.",
+                    request.task_id,
+                    first_line(&error)
+                ));
+            }
+        }
+        self.update_view();
+    }
+
+    /// Puts a row back the way it was before `change` was applied to it.
+    fn roll_back(&mut self, change: &TaskChange, rollback: Option<Rollback>) {
+        let Some(Rollback { task, index }) = rollback else {
+            return;
+        };
+        match self.tasks.iter_mut().find(|row| row.id == task.id) {
+            Some(row) => *row = task,
+            None if *change == TaskChange::Delete => {
+                let index = cmp::min(index, self.tasks.len());
+                self.tasks.insert(index, task);
+            }
+            None => {}
+        }
+    }
+
+    /// Shows a task from the `a` form at the end of the list and selects it.
+    fn insert_created(&mut self, task: Task) {
+        if matches!(self.popup, Some(Popup::Create(_))) {
+            self.popup = None;
+        }
+        let (task_id, title) = (task.id, task.title.clone());
+        self.tasks.push(task);
+        self.update_view();
+        if self.select_task(task_id) {
+            self.set_status(format!("Created task #{task_id}: {title}"));
+        } else {
+            self.set_status(format!(
+                "Created task #{task_id}: {title} (hidden by the filter bar)"
+            ));
+        }
+    }
+
+    /// Closes the form; `--dry-run` kept the task from being created.
+    fn create_planned(&mut self, planned: &PlannedRequest) {
+        if matches!(self.popup, Some(Popup::Create(_))) {
+            self.popup = None;
+        }
+        self.set_status(format!(
+            "Dry run: task not created; would {} {}.",
+            planned.method, planned.url
+        ));
+    }
+
+    /// Keeps the form open with the error so the input is not lost.
+    fn create_failed(&mut self, error: &CliError) {
+        let message = first_line(error);
+        if let Some(Popup::Create(form)) = &mut self.popup {
+            form.error = Some(message.clone());
+        }
+        self.set_status(format!("Could not create task: {message}"));
+    }
+
+    fn handle_form_key(&mut self, mut form: CreateForm, key: KeyEvent) -> AppAction {
+        match key.code {
+            KeyCode::Esc => {
+                self.set_status("New task discarded.");
+                return AppAction::None;
+            }
+            KeyCode::Enter => match form.build_request(&self.time, self.event_category) {
+                Ok(request) => {
+                    form.error = None;
+                    self.set_status(format!("Creating task '{}'...", request.title));
+                    self.create_pending = Some(request);
+                }
+                Err((field, message)) => {
+                    form.focus = field as usize;
+                    form.error = Some(message);
+                }
+            },
+            KeyCode::Tab | KeyCode::Down => form.focus_next(1),
+            KeyCode::BackTab | KeyCode::Up => form.focus_next(FormField::ALL.len() - 1),
+            KeyCode::Backspace => {
+                form.values[form.focus].pop();
+            }
+            KeyCode::Char(ch)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                form.values[form.focus].push(ch);
+            }
+            _ => {}
+        }
+        self.popup = Some(Popup::Create(form));
+        AppAction::None
+    }
+
+    fn handle_popup_key(&mut self, popup: Popup, key: KeyEvent) -> AppAction {
+        match popup {
+            Popup::Create(form) => self.handle_form_key(form, key),
+            Popup::ConfirmDelete { task_id } => match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    self.request_change(task_id, TaskChange::Delete)
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.set_status("Delete cancelled.");
+                    AppAction::None
+                }
+                _ => {
+                    self.popup = Some(popup);
+                    AppAction::None
+                }
+            },
+            Popup::Snooze { task_id, selected } => {
+                let choices = SNOOZE_PRESETS.len() + 1;
+                let pick = match key.code {
+                    KeyCode::Esc => {
+                        self.set_status("Snooze cancelled.");
+                        return AppAction::None;
+                    }
+                    KeyCode::Enter => Some(selected),
+                    KeyCode::Char(digit @ '1'..='9') => {
+                        Some(digit as usize - '1' as usize).filter(|index| *index < choices)
+                    }
+                    KeyCode::Char('j') | KeyCode::Down => {
+                        self.popup = Some(Popup::Snooze {
+                            task_id,
+                            selected: (selected + 1) % choices,
+                        });
+                        None
+                    }
+                    KeyCode::Char('k') | KeyCode::Up => {
+                        self.popup = Some(Popup::Snooze {
+                            task_id,
+                            selected: (selected + choices - 1) % choices,
+                        });
+                        None
+                    }
+                    _ => {
+                        self.popup = Some(popup);
+                        None
+                    }
+                };
+
+                match pick.map(|index| SNOOZE_PRESETS.get(index)) {
+                    Some(Some((_, input))) => self.submit_time(task_id, InputField::Snooze, input),
+                    Some(None) => {
+                        self.popup = Some(Popup::Input {
+                            task_id,
+                            field: InputField::Snooze,
+                            text: String::new(),
+                            error: None,
+                        });
+                        AppAction::None
+                    }
+                    None => AppAction::None,
+                }
+            }
+            Popup::Input {
+                task_id,
+                field,
+                mut text,
+                error,
+            } => {
+                match key.code {
+                    KeyCode::Esc => {
+                        self.set_status("Edit cancelled.");
+                        return AppAction::None;
+                    }
+                    KeyCode::Enter => return self.submit_time(task_id, field, &text),
+                    KeyCode::Backspace => {
+                        text.pop();
+                    }
+                    KeyCode::Char(ch)
+                        if !key
+                            .modifiers
+                            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+                    {
+                        text.push(ch);
+                    }
+                    _ => {}
+                }
+                self.popup = Some(Popup::Input {
+                    task_id,
+                    field,
+                    text,
+                    error,
+                });
+                AppAction::None
+            }
+        }
+    }
+
+    /// Parses due/snooze input and requests the change, or reopens the input with the error.
+    fn submit_time(&mut self, task_id: u64, field: InputField, input: &str) -> AppAction {
+        let input = input.trim();
+        let parsed = match (field, input) {
+            (InputField::Due, "") => Ok(None),
+            (InputField::Snooze, "") => Err("enter a time".to_string()),
+            (_, input) => self
+                .time
+                .parse_instant(input)
+                .map(|instant| Some(datetime::format_timestamp(instant))),
+        };
+
+        match (field, parsed) {
+            (InputField::Due, Ok(due)) => self.request_change(task_id, TaskChange::Due(due)),
+            (InputField::Snooze, Ok(until)) => self.request_change(
+                task_id,
+                TaskChange::Snooze(until.expect("empty snooze input is rejected")),
+            ),
+            (field, Err(reason)) => {
+                self.popup = Some(Popup::Input {
+                    task_id,
+                    field,
+                    text: input.to_string(),
+                    error: Some(reason),
+                });
+                AppAction::None
+            }
+        }
+    }
+
+    fn show_tab(&mut self, tab: Tab) -> AppAction {
+        self.tab = tab;
+        self.status_message = None;
+        if tab == Tab::Agenda && !self.agenda.is_loaded() {
+            self.load_agenda();
+        }
+        AppAction::None
+    }
+
+    fn load_agenda(&mut self) {
+        self.agenda_pending = true;
+        self.set_status(format!("Loading agenda for {}...", self.agenda.label()));
+    }
+
+    fn handle_agenda_key(&mut self, key: KeyEvent) -> AppAction {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.agenda.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => self.agenda.select_previous(),
+            KeyCode::Char('g') | KeyCode::Home => self.agenda.select_first(),
+            KeyCode::Char('G') | KeyCode::End => self.agenda.select_last(),
+            KeyCode::Char('h') | KeyCode::Left => {
+                self.agenda.page(-1);
+                self.load_agenda();
+            }
+            KeyCode::Char('l') | KeyCode::Right => {
+                self.agenda.page(1);
+                self.load_agenda();
+            }
+            KeyCode::Char('t') => {
+                self.agenda.go_to(self.time.today());
+                if !self.agenda.is_loaded() {
+                    self.load_agenda();
+                }
+            }
+            KeyCode::Char('w') => {
+                self.agenda.toggle_span();
+                self.load_agenda();
+            }
+            KeyCode::Char('r') => self.load_agenda(),
+            KeyCode::Enter => self.open_agenda_task(),
+            _ => {}
+        }
+        AppAction::None
+    }
+
+    /// Enter on a task block: switch to the task list with its task selected.
+    fn open_agenda_task(&mut self) {
+        let Some(entry) = self.agenda.selected_entry() else {
+            return;
+        };
+        let BlockKind::Task(task_id) = entry.kind() else {
+            self.set_status("Only task blocks link to a task.");
+            return;
+        };
+
+        if self.select_task(task_id) {
+            self.tab = Tab::Tasks;
+            self.status_message = None;
+        } else if self.tasks.iter().any(|task| task.id == task_id) {
+            self.set_status(format!(
+                "Task #{task_id} is hidden by the filter bar; press f then 0 to clear it."
+            ));
+        } else {
+            self.set_status(format!(
+                "Task #{task_id} is not in the list; try --all or press r on the task tab."
+            ));
+        }
+    }
+
+    fn handle_search_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.search.clear();
+                self.search_input = false;
+                self.set_status("Search cleared.");
+                return;
+            }
+            KeyCode::Enter => {
+                self.search_input = false;
+                let count = self.search_matches().len();
+                self.set_status(if self.search.is_empty() {
+                    "Search cleared.".to_string()
+                } else {
+                    format!(
+                        "{count} match{} for '{}'. n/N next/previous, Esc clears.",
+                        if count == 1 { "" } else { "es" },
+                        self.search
+                    )
+                });
+                return;
+            }
+            KeyCode::Backspace => {
+                self.search.pop();
+            }
+            KeyCode::Char(ch)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.search.push(ch);
+            }
+            _ => return,
+        }
+
+        if !self.search.trim().is_empty() {
+            self.jump_to_match(true, true);
+        }
+        self.set_status(format!("/{}", self.search));
+    }
+
+    fn handle_filter_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('f') | KeyCode::Enter | KeyCode::Esc => {
+                self.filter_bar = false;
+                self.status_message = None;
+                return;
+            }
+            KeyCode::Char('s') => self.view_filters.cycle_status(),
+            KeyCode::Char(digit @ '1'..='4') => self
+                .view_filters
+                .toggle_priority(PRIORITIES[digit as usize - '1' as usize]),
+            KeyCode::Char('c') => self.view_filters.cycle_category(),
+            KeyCode::Char('0') => self.view_filters = ViewFilters::default(),
+            _ => return,
+        }
+        self.update_view();
+    }
+
     fn handle_command_key(&mut self, key: KeyEvent) -> AppAction {
         match key.code {
             KeyCode::Enter => {
-                let command = self.command_buffer.clone();
+                let line = self.command_buffer.split_off(1);
                 self.command_buffer.clear();
-                if command == ":q" {
-                    AppAction::Quit
-                } else if command == ":" {
-                    self.status_message = Some("Command cancelled.".to_string());
-                    AppAction::None
-                } else {
-                    self.status_message = Some(format!("Unknown command: {command}"));
-                    AppAction::None
+                if let Err(error) = self.history.push(&line) {
+                    self.set_status(error.to_string());
+                }
+                match dashboard_command::parse(&line) {
+                    Ok(command) => self.run_command(command),
+                    Err(message) => {
+                        self.set_status(message);
+                        AppAction::None
+                    }
+                }
+            }
+            KeyCode::Esc => {
+                self.command_buffer.clear();
+                self.history.reset();
+                self.set_status("Command cancelled.");
+                AppAction::None
+            }
+            KeyCode::Tab => {
+                let completion = dashboard_command::complete(&self.command_buffer[1..]);
+                self.command_buffer = format!(":{}", completion.line);
+                if !completion.candidates.is_empty() {
+                    self.set_status(completion.candidates.join("  "));
+                }
+                AppAction::None
+            }
+            KeyCode::Up => {
+                if let Some(entry) = self.history.previous(&self.command_buffer[1..]) {
+                    self.command_buffer = format!(":{entry}");
+                }
+                AppAction::None
+            }
+            KeyCode::Down => {
+                if let Some(entry) = self.history.next() {
+                    self.command_buffer = format!(":{entry}");
                 }
+                AppAction::None
             }
             KeyCode::Backspace => {
                 self.command_buffer.pop();
                 if self.command_buffer.is_empty() {
+                    self.history.reset();
                     self.status_message = Some(DASHBOARD_HINT.to_string());
                 }
                 AppAction::None
@@ -207,24 +1301,224 @@ impl DashboardApp {
                     .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
             {
                 self.command_buffer.push(ch);
-                if self.command_buffer == ":q" {
-                    return AppAction::Quit;
-                }
                 AppAction::None
             }
             _ => AppAction::None,
         }
     }
+
+    fn run_command(&mut self, command: ExCommand) -> AppAction {
+        match command {
+            ExCommand::Quit => self.quit(),
+            ExCommand::ForceQuit => AppAction::Quit,
+            ExCommand::Write if self.staged.is_empty() && self.in_flight.is_empty() => {
+                self.set_status("Nothing to save; every change has finished.");
+                AppAction::None
+            }
+            ExCommand::Write => AppAction::Write(self.write_staged()),
+            ExCommand::WriteQuit => AppAction::WriteQuit(self.write_staged()),
+            ExCommand::Help => {
+                self.show_help = true;
+                AppAction::None
+            }
+            ExCommand::Sort(order) => {
+                self.sort = order;
+                self.update_view();
+                self.set_status(match order {
+                    Some(order) => format!("Sorted by {}.", order.label()),
+                    None => "Sort cleared; tasks are in Reclaim's order.".to_string(),
+                });
+                AppAction::None
+            }
+            ExCommand::Filter(terms) => {
+                if terms.is_empty() {
+                    self.view_filters = ViewFilters::default();
+                }
+                for term in terms {
+                    match term {
+                        FilterTerm::Status(status) => self.view_filters.status = status,
+                        FilterTerm::Priorities(priorities) => {
+                            self.view_filters.priorities = priorities
+                        }
+                        FilterTerm::Category(category) => self.view_filters.category = category,
+                    }
+                }
+                self.update_view();
+                self.set_status(if self.view_filters.is_empty() {
+                    "Filters cleared.".to_string()
+                } else {
+                    format!("Filters: {}.", self.view_filters.summary())
+                });
+                AppAction::None
+            }
+            ExCommand::Set(updates) => self.set_fields(updates),
+            ExCommand::Snooze(input) => {
+                // A bare duration such as "2h" means from now, like "+2h".
+                let input = if duration::parse_minutes(&input).is_ok() {
+                    format!("+{input}")
+                } else {
+                    input
+                };
+                match self.time.parse_instant(&input) {
+                    Ok(until) => self.change_selected(|_| {
+                        Some(TaskChange::Snooze(datetime::format_timestamp(until)))
+                    }),
+                    Err(reason) => {
+                        self.set_status(format!("Invalid snooze time '{input}': {reason}."));
+                        AppAction::None
+                    }
+                }
+            }
+            ExCommand::Done => self.change_selected(|_| Some(TaskChange::Done)),
+            ExCommand::Start => self.change_selected(|_| Some(TaskChange::Start)),
+            ExCommand::Stop => self.change_selected(|_| Some(TaskChange::Stop)),
+            ExCommand::New(title) => {
+                let mut form = CreateForm::default();
+                form.values[FormField::Title as usize] = title;
+                match form.build_request(&self.time, self.event_category) {
+                    Ok(request) => {
+                        self.set_status(format!("Creating task '{}'...", request.title));
+                        self.create_pending = Some(request);
+                    }
+                    Err((_, message)) => self.set_status(message),
+                }
+                AppAction::None
+            }
+        }
+    }
+
+    /// `:set`/`:unset` on the selected task, with the same paths and values as `patch --set`.
+    fn set_fields(&mut self, mut updates: Vec<fields::FieldUpdate>) -> AppAction {
+        let Some(task) = self.selected_for_change().cloned() else {
+            return AppAction::None;
+        };
+
+        // Paths below a top-level field change part of it; the whole field is sent.
+        let mut body = Map::new();
+        let checked = crate::normalize_task_time_fields(&mut updates, &self.time)
+            .and_then(|()| crate::task_fields(&task))
+            .and_then(|current| {
+                fields::seed_nested(&mut body, &current, &updates);
+                fields::apply_updates(&mut body, updates, UnsetMode::Null)
+            })
+            .map_err(|error| error.to_string())
+            .and_then(|()| merge_fields(&task, &body));
+        match checked {
+            Ok(updated) => self.stage(task, body, updated),
+            Err(reason) => self.set_status(format!("Cannot update #{}: {reason}", task.id)),
+        }
+        AppAction::None
+    }
+
+    /// Shows `updated` on the row and keeps `fields` for `:w`, merged with earlier edits.
+    fn stage(&mut self, before: Task, fields: Map<String, Value>, updated: Task) {
+        let task_id = before.id;
+        if let Some(row) = self.tasks.iter_mut().find(|task| task.id == task_id) {
+            *row = updated;
+        }
+        match self.staged.entry(task_id) {
+            Entry::Occupied(mut edit) => fields::deep_merge(&mut edit.get_mut().fields, fields),
+            Entry::Vacant(edit) => {
+                edit.insert(StagedEdit { fields, before });
+            }
+        }
+        self.update_view();
+
+        let count = self.staged.len();
+        self.set_status(format!(
+            "#{task_id} edited; {count} unsaved task{}, :w saves.",
+            if count == 1 { "" } else { "s" }
+        ));
+    }
+
+    /// Turns the staged edits into requests for `:w`. A task whose last change is still
+    /// saving keeps its edits staged for the next `:w`.
+    fn write_staged(&mut self) -> Vec<TaskRequest> {
+        let mut requests = Vec::new();
+        for (task_id, edit) in std::mem::take(&mut self.staged) {
+            if self.in_flight.contains_key(&task_id) {
+                self.staged.insert(task_id, edit);
+                continue;
+            }
+            // A deleted task has nothing left to save.
+            let Some(index) = self.tasks.iter().position(|task| task.id == task_id) else {
+                continue;
+            };
+            self.in_flight.insert(
+                task_id,
+                Rollback {
+                    task: edit.before,
+                    index,
+                },
+            );
+            requests.push(TaskRequest {
+                task_id,
+                change: TaskChange::Fields(edit.fields),
+            });
+        }
+        if !requests.is_empty() {
+            self.set_status(format!(
+                "Saving {} edited task{}...",
+                requests.len(),
+                if requests.len() == 1 { "" } else { "s" }
+            ));
+        }
+        requests
+    }
+
+    /// `:q` and Esc: quits unless edits are staged, which `:wq` saves and `:q!` drops.
+    fn quit(&mut self) -> AppAction {
+        if self.staged.is_empty() {
+            return AppAction::Quit;
+        }
+        let count = self.staged.len();
+        self.set_status(format!(
+            "{count} task{} with unsaved edits: :w saves, :wq saves and quits, :q! discards.",
+            if count == 1 { "" } else { "s" }
+        ));
+        AppAction::None
+    }
 }
 
-pub async fn run_dashboard(api: &impl ReclaimApi, include_all: bool) -> Result<(), CliError> {
+pub async fn run_dashboard(
+    api: &impl ReclaimApi,
+    include_all: bool,
+    query: TaskQuery,
+    time: TimeContext,
+    event_category: EventCategory,
+) -> Result<(), CliError> {
     let filter = if include_all {
         TaskFilter::All
     } else {
         TaskFilter::Active
     };
-    let tasks = api.list_tasks(filter).await?;
-    let mut app = DashboardApp::new(tasks, filter);
+    // Start from cached tasks when there are any and refresh once the first frame is up.
+    let mut app = match api.cached_tasks(filter).await {
+        Some(cached) => {
+            let age = cached.age();
+            let mut tasks = cached.items;
+            query.apply(&mut tasks);
+            let mut app = DashboardApp::new(tasks, filter)
+                .with_query(query)
+                .with_time(time)
+                .with_event_category(event_category)
+                .with_history(CommandHistory::load());
+            app.refresh_pending = true;
+            app.set_status(format!(
+                "Showing tasks cached {} ago. Refreshing...",
+                cache::format_age(age)
+            ));
+            app
+        }
+        None => {
+            let tasks = fetch_tasks(api, filter, &query).await?;
+            DashboardApp::new(tasks, filter)
+                .with_query(query)
+                .with_time(time)
+                .with_event_category(event_category)
+                .with_history(CommandHistory::load())
+        }
+    };
 
     let mut terminal = setup_terminal()?;
     let loop_result = run_event_loop(&mut terminal, api, &mut app).await;
@@ -245,35 +1539,291 @@ async fn run_event_loop(
     api: &impl ReclaimApi,
     app: &mut DashboardApp,
 ) -> Result<(), CliError> {
+    let mut changes = FuturesUnordered::new();
     loop {
         terminal
             .draw(|frame| draw_dashboard(frame, app))
             .map_err(|error| map_tui_error("Failed to draw dashboard frame", error))?;
 
-        if !event::poll(POLL_INTERVAL).map_err(|error| map_tui_error("TUI poll failed", error))? {
-            continue;
-        }
+        if app.refresh_pending {
+            app.refresh_pending = false;
+            refresh(api, app).await;
+            continue;
+        }
+        if app.agenda_pending {
+            app.agenda_pending = false;
+            match api.list_events(app.agenda.query()).await {
+                Ok(events) => {
+                    app.agenda.load(events, &app.time);
+                    let count = app.agenda.entry_count();
+                    app.set_status(format!(
+                        "{}: {count} event{}.",
+                        app.agenda.label(),
+                        if count == 1 { "" } else { "s" }
+                    ));
+                }
+                Err(error) => {
+                    app.set_status(format!("Could not load agenda: {}", first_line(&error)))
+                }
+            }
+            continue;
+        }
+        if let Some(request) = app.create_pending.take() {
+            match api.create_task(request).await {
+                Ok(Outcome::Sent(task)) => app.insert_created(task),
+                Ok(Outcome::Planned(planned)) => app.create_planned(&planned),
+                Err(error) => app.create_failed(&error),
+            }
+            continue;
+        }
+
+        // Task changes run while the loop keeps reading keys; each finished one is shown
+        // before the next key is handled.
+        let key_timeout = if changes.is_empty() {
+            POLL_INTERVAL
+        } else {
+            if let Ok(Some((request, result))) =
+                tokio::time::timeout(CHANGE_POLL_INTERVAL, changes.next()).await
+            {
+                app.finish_change(request, result);
+                continue;
+            }
+            Duration::ZERO
+        };
+        if !event::poll(key_timeout).map_err(|error| map_tui_error("TUI poll failed", error))? {
+            continue;
+        }
+
+        let event = event::read().map_err(|error| map_tui_error("TUI event read failed", error))?;
+        if let Event::Key(key) = event {
+            if !matches!(key.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+                continue;
+            }
+
+            app.time = app.time.refreshed();
+            match app.handle_key(key) {
+                AppAction::None => {}
+                AppAction::Quit => {
+                    let failures = flush_changes(terminal, app, &mut changes).await?;
+                    return if failures.is_empty() {
+                        Ok(())
+                    } else {
+                        Err(CliError::Output(failures.join("\n")))
+                    };
+                }
+                AppAction::WriteQuit(requests) => {
+                    changes.extend(
+                        requests
+                            .into_iter()
+                            .map(|request| send_change(api, request)),
+                    );
+                    let failures = flush_changes(terminal, app, &mut changes).await?;
+                    return if failures.is_empty() {
+                        Ok(())
+                    } else {
+                        Err(CliError::Output(failures.join("\n")))
+                    };
+                }
+                AppAction::Write(requests) => {
+                    changes.extend(
+                        requests
+                            .into_iter()
+                            .map(|request| send_change(api, request)),
+                    );
+                    let failures = flush_changes(terminal, app, &mut changes).await?;
+                    match failures.as_slice() {
+                        [] => app.set_status("All changes saved."),
+                        [failure] => app.set_status(failure.clone()),
+                        [.., last] => app.set_status(format!(
+                            "{} changes failed and were undone; last: {last}",
+                            failures.len()
+                        )),
+                    }
+                }
+                AppAction::Refresh => refresh(api, app).await,
+                AppAction::Send(request) => changes.push(send_change(api, request)),
+            }
+        }
+    }
+}
+
+/// Waits for every change still on its way (`:w`, and before quitting so none is cut off
+/// mid-request). Returns the status line of each change that failed.
+async fn flush_changes(
+    terminal: &mut DashboardTerminal,
+    app: &mut DashboardApp,
+    changes: &mut FuturesUnordered<impl std::future::Future<Output = ChangeOutcome>>,
+) -> Result<Vec<String>, CliError> {
+    if changes.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let count = changes.len();
+    app.set_status(format!(
+        "Waiting for {count} change{} to finish...",
+        if count == 1 { "" } else { "s" }
+    ));
+    terminal
+        .draw(|frame| draw_dashboard(frame, app))
+        .map_err(|error| map_tui_error("Failed to draw dashboard frame", error))?;
+
+    let mut failures = Vec::new();
+    while let Some((request, result)) = changes.next().await {
+        let failed = result.is_err();
+        app.finish_change(request, result);
+        if failed {
+            failures.extend(app.status_message.clone());
+        }
+    }
+    Ok(failures)
+}
+
+type ChangeOutcome = (TaskRequest, Result<Outcome<Option<Task>>, CliError>);
+
+async fn send_change(api: &impl ReclaimApi, request: TaskRequest) -> ChangeOutcome {
+    let task_id = request.task_id;
+    let patch = |body: Value| api.patch_task(task_id, body, None);
+    let result = match &request.change {
+        TaskChange::Done => api.run_planner_action(task_id, PlannerAction::Done).await,
+        TaskChange::Start => api.run_planner_action(task_id, PlannerAction::Start).await,
+        TaskChange::Stop => api.run_planner_action(task_id, PlannerAction::Stop).await,
+        TaskChange::Snooze(until) => api.snooze_task(task_id, until).await,
+        TaskChange::Priority(priority) => patch(json!({ "priority": priority })).await,
+        TaskChange::Due(due) => patch(json!({ "due": due })).await,
+        TaskChange::Fields(fields) => patch(Value::Object(fields.clone())).await,
+        TaskChange::Delete => {
+            let result = api
+                .delete_task(task_id, None)
+                .await
+                .map(|outcome| outcome.map(|_| None));
+            return (request, result);
+        }
+    };
+
+    (request, result.map(|outcome| outcome.map(Some)))
+}
+
+async fn refresh(api: &impl ReclaimApi, app: &mut DashboardApp) {
+    match fetch_tasks(api, app.filter, &app.query).await {
+        Ok(tasks) => app.replace_tasks(tasks),
+        Err(error) => app.set_status(format!("Refresh failed: {}", first_line(&error))),
+    }
+}
+
+fn first_line(error: &CliError) -> String {
+    error
+        .to_string()
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Order for `:sort`. Tasks without a due date or priority go last; ties keep their order.
+fn compare_tasks(a: &Task, b: &Task, key: SortKey) -> Ordering {
+    const STATUS_ORDER: [&str; 6] = [
+        "IN_PROGRESS",
+        "SCHEDULED",
+        "NEW",
+        "COMPLETE",
+        "CANCELLED",
+        "ARCHIVED",
+    ];
+    let rank = |order: &[&str], value: Option<&str>| {
+        value
+            .and_then(|value| {
+                order
+                    .iter()
+                    .position(|known| known.eq_ignore_ascii_case(value))
+            })
+            .unwrap_or(order.len())
+    };
+    let due = |task: &Task| {
+        task.due
+            .as_deref()
+            .and_then(|due| chrono::DateTime::parse_from_rfc3339(due).ok())
+    };
+
+    match key {
+        SortKey::Due => match (due(a), due(b)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        },
+        SortKey::Priority => {
+            rank(&PRIORITIES, a.priority.as_deref()).cmp(&rank(&PRIORITIES, b.priority.as_deref()))
+        }
+        SortKey::Title => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+        SortKey::Status => {
+            rank(&STATUS_ORDER, a.status.as_deref()).cmp(&rank(&STATUS_ORDER, b.status.as_deref()))
+        }
+        SortKey::Id => a.id.cmp(&b.id),
+    }
+}
+
+fn task_matches_search(task: &Task, search: &str) -> bool {
+    search_positions(search, &task.title).is_some()
+        || task
+            .notes
+            .as_deref()
+            .is_some_and(|notes| search_positions(search, notes).is_some())
+}
+
+/// Char positions in `text` that match `search`, ignoring case and spaces in `search`.
+/// A contiguous match wins; otherwise the search letters may be spread out in order, so
+/// "shpt" finds "Ship it".
+fn search_positions(search: &str, text: &str) -> Option<Vec<usize>> {
+    let needle = search
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .map(lowercase_char)
+        .collect::<Vec<_>>();
+    if needle.is_empty() {
+        return None;
+    }
+    let haystack = text.chars().map(lowercase_char).collect::<Vec<_>>();
 
-        let event = event::read().map_err(|error| map_tui_error("TUI event read failed", error))?;
-        if let Event::Key(key) = event {
-            if !matches!(key.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
-                continue;
-            }
+    if let Some(start) = haystack
+        .windows(needle.len())
+        .position(|window| window == needle.as_slice())
+    {
+        return Some((start..start + needle.len()).collect());
+    }
 
-            match app.handle_key(key) {
-                AppAction::None => {}
-                AppAction::Quit => return Ok(()),
-                AppAction::Refresh => match api.list_tasks(app.filter).await {
-                    Ok(tasks) => app.replace_tasks(tasks),
-                    Err(error) => {
-                        let summary = error.to_string();
-                        let first_line = summary.lines().next().unwrap_or("Refresh failed.");
-                        app.set_status(format!("Refresh failed: {first_line}"));
-                    }
-                },
-            }
+    let mut positions = Vec::with_capacity(needle.len());
+    let mut wanted = needle.iter().peekable();
+    for (index, ch) in haystack.iter().enumerate() {
+        if wanted.next_if(|wanted| *wanted == ch).is_some() {
+            positions.push(index);
         }
     }
+    wanted.peek().is_none().then_some(positions)
+}
+
+/// Lowercases one char to one char, so positions in the lowercased text match the original.
+fn lowercase_char(ch: char) -> char {
+    ch.to_lowercase().next().unwrap_or(ch)
+}
+
+/// The priority `steps` places after the task's current one, wrapping from P4 to P1.
+fn cycle_priority(task: &Task, steps: usize) -> TaskChange {
+    let current = PRIORITIES
+        .iter()
+        .position(|priority| Some(*priority) == task.priority.as_deref());
+    let next = match current {
+        Some(index) => (index + steps) % PRIORITIES.len(),
+        None => 0,
+    };
+    TaskChange::Priority(PRIORITIES[next])
+}
+
+async fn fetch_tasks(
+    api: &impl ReclaimApi,
+    filter: TaskFilter,
+    query: &TaskQuery,
+) -> Result<Vec<Task>, CliError> {
+    let mut tasks = api.list_tasks(filter).await?;
+    query.apply(&mut tasks);
+    Ok(tasks)
 }
 
 fn draw_dashboard(frame: &mut Frame<'_>, app: &mut DashboardApp) {
@@ -293,25 +1843,98 @@ fn draw_dashboard(frame: &mut Frame<'_>, app: &mut DashboardApp) {
     if app.show_help {
         draw_help_popup(frame);
     }
+    if let Some(popup) = &app.popup {
+        draw_action_popup(frame, popup, app.event_category);
+    }
 }
 
 fn draw_header(frame: &mut Frame<'_>, app: &DashboardApp, area: Rect) {
-    let filter_label = match app.filter {
-        TaskFilter::Active => "active",
-        TaskFilter::All => "all",
+    let filter_label = match (app.filter, app.query.is_empty()) {
+        (TaskFilter::Active, true) => "active",
+        (TaskFilter::All, true) => "all",
+        (TaskFilter::Active, false) => "active, filtered",
+        (TaskFilter::All, false) => "all, filtered",
     };
 
-    let header = Paragraph::new(Line::from(vec![
+    let shown = if app.view.len() == app.tasks.len() {
+        app.tasks.len().to_string()
+    } else {
+        format!("{} of {}", app.view.len(), app.tasks.len())
+    };
+    let tab_style = |tab| {
+        if app.tab == tab {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        }
+    };
+    let mut title = vec![
         Span::styled(
             "Reclaim Task Dashboard",
             Style::default().add_modifier(Modifier::BOLD),
         ),
-        Span::raw(format!(
-            "  |  {} task{} ({filter_label})",
-            app.tasks.len(),
-            if app.tasks.len() == 1 { "" } else { "s" }
-        )),
-    ]));
+        Span::raw("  "),
+        Span::styled("[1 Tasks]", tab_style(Tab::Tasks)),
+        Span::raw(" "),
+        Span::styled("[2 Agenda]", tab_style(Tab::Agenda)),
+    ];
+    if app.tab == Tab::Agenda {
+        title.push(Span::raw(format!("  |  {}", app.agenda.label())));
+        let legend = Line::from(vec![
+            Span::styled("◆ task block", Style::default().fg(Color::Cyan)),
+            Span::raw("  "),
+            Span::styled("◇ Reclaim block", Style::default().fg(Color::Magenta)),
+            Span::raw("  ● meeting"),
+        ]);
+        frame.render_widget(Paragraph::new(vec![Line::from(title), legend]), area);
+        return;
+    }
+    title.extend([Span::raw(format!(
+        "  |  {shown} task{} ({filter_label})",
+        if app.tasks.len() == 1 { "" } else { "s" }
+    ))]);
+    if let Some(order) = app.sort {
+        title.push(Span::raw(format!("  |  sorted by {}", order.label())));
+    }
+    if !app.search.is_empty() {
+        let matches = app.search_matches();
+        let position = app
+            .selected_index()
+            .and_then(|selected| matches.iter().position(|index| *index == selected))
+            .map(|position| format!(", {} of {}", position + 1, matches.len()))
+            .unwrap_or_default();
+        title.push(Span::raw(format!(
+            "  |  /{}: {} match{}{position}",
+            app.search,
+            matches.len(),
+            if matches.len() == 1 { "" } else { "es" }
+        )));
+    }
+
+    let filters = if app.view_filters.is_empty() {
+        "none".to_string()
+    } else {
+        app.view_filters.summary()
+    };
+    let filter_bar = if app.filter_bar {
+        Line::from(vec![
+            Span::styled(
+                format!("Filters: {filters}"),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  s status  1-4 priority  c category  0 clear  f done"),
+        ])
+    } else if app.view_filters.is_empty() {
+        Line::from("")
+    } else {
+        Line::from(format!("Filters: {filters}  (f to change)"))
+    };
+
+    let header = Paragraph::new(vec![Line::from(title), filter_bar]);
     frame.render_widget(header, area);
 }
 
@@ -320,19 +1943,31 @@ fn draw_body(frame: &mut Frame<'_>, app: &mut DashboardApp, area: Rect) {
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
         .split(area);
+    if app.tab == Tab::Agenda {
+        draw_agenda(frame, app, columns[0], columns[1]);
+        return;
+    }
 
-    let items: Vec<ListItem<'_>> = if app.tasks.is_empty() {
+    let items: Vec<ListItem<'_>> = if app.view.is_empty() {
         vec![ListItem::new("No tasks found for this filter.")]
     } else {
-        app.tasks
-            .iter()
+        app.listed_tasks()
             .map(|task| {
                 let status = task.status.as_deref().unwrap_or("UNKNOWN");
+                let priority = task.priority.as_deref().unwrap_or("-");
                 let due = task.due.as_deref().unwrap_or("-");
-                ListItem::new(format!(
-                    "#{:<6} [{:<10}] {} (due: {due})",
-                    task.id, status, task.title
-                ))
+                let saving = if app.in_flight.contains_key(&task.id) {
+                    " [saving]"
+                } else {
+                    ""
+                };
+                let mut spans = vec![Span::raw(format!(
+                    "#{:<6} [{:<10}] {priority:<2} ",
+                    task.id, status
+                ))];
+                spans.extend(highlighted_title(&task.title, &app.search));
+                spans.push(Span::raw(format!(" (due: {due}){saving}")));
+                ListItem::new(Line::from(spans))
             })
             .collect()
     };
@@ -354,11 +1989,66 @@ fn draw_body(frame: &mut Frame<'_>, app: &mut DashboardApp, area: Rect) {
     frame.render_widget(details, columns[1]);
 }
 
+fn draw_agenda(frame: &mut Frame<'_>, app: &mut DashboardApp, list_area: Rect, details_area: Rect) {
+    let (rows, selected_row) = app.agenda.rows(&app.time);
+    app.agenda_list.select(selected_row);
+    let agenda = List::new(rows)
+        .block(Block::default().title("Agenda").borders(Borders::ALL))
+        .highlight_symbol(">> ")
+        .highlight_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
+    frame.render_stateful_widget(agenda, list_area, &mut app.agenda_list);
+
+    let details = Paragraph::new(app.agenda.detail_lines(&app.time))
+        .block(Block::default().title("Event").borders(Borders::ALL))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(details, details_area);
+}
+
+/// The title with the characters matching `search` highlighted.
+fn highlighted_title(title: &str, search: &str) -> Vec<Span<'static>> {
+    let Some(positions) = search_positions(search, title) else {
+        return vec![Span::raw(title.to_string())];
+    };
+
+    let highlight = Style::default()
+        .fg(Color::Black)
+        .bg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (index, ch) in title.chars().enumerate() {
+        let matched = positions.contains(&index);
+        if matched != run_matched && !run.is_empty() {
+            let text = std::mem::take(&mut run);
+            spans.push(if run_matched {
+                Span::styled(text, highlight)
+            } else {
+                Span::raw(text)
+            });
+        }
+        run_matched = matched;
+        run.push(ch);
+    }
+    spans.push(if run_matched {
+        Span::styled(run, highlight)
+    } else {
+        Span::raw(run)
+    });
+    spans
+}
+
 fn draw_footer(frame: &mut Frame<'_>, app: &DashboardApp, area: Rect) {
     let text = if !app.command_buffer.is_empty() {
         format!("Command: {}", app.command_buffer)
     } else if let Some(status) = app.status_message.as_deref() {
         status.to_string()
+    } else if app.tab == Tab::Agenda {
+        AGENDA_HINT.to_string()
     } else {
         DASHBOARD_HINT.to_string()
     };
@@ -368,7 +2058,7 @@ fn draw_footer(frame: &mut Frame<'_>, app: &DashboardApp, area: Rect) {
 }
 
 fn draw_help_popup(frame: &mut Frame<'_>) {
-    let area = centered_rect(72, 70, frame.area());
+    let area = centered_rect(72, 90, frame.area());
     frame.render_widget(Clear, area);
 
     let help_lines = vec![
@@ -383,13 +2073,43 @@ fn draw_help_popup(frame: &mut Frame<'_>) {
         Line::from("  g / Home        Jump to first task"),
         Line::from("  G / End         Jump to last task"),
         Line::from(""),
+        Line::from("Tabs"),
+        Line::from("  Tab / 1 / 2     Switch between tasks and the agenda"),
+        Line::from("  h / l           Agenda: previous / next day or week"),
+        Line::from("  w / t           Agenda: toggle day/week, jump to today"),
+        Line::from("  Enter           Agenda: open the task behind a task block"),
+        Line::from(""),
+        Line::from("Search and filters"),
+        Line::from("  /               Search titles and notes as you type"),
+        Line::from("  n / N           Next / previous match (while searching)"),
+        Line::from("  Esc             Clear the search"),
+        Line::from("  f               Filter bar: s status, 1-4 priority, c category, 0 clear"),
+        Line::from(""),
+        Line::from("Task actions"),
+        Line::from("  a               Add a task (form)"),
+        Line::from("  x               Mark selected task done"),
+        Line::from("  s               Start or stop selected task"),
+        Line::from("  z               Snooze (pick a preset or enter a time)"),
+        Line::from("  p / P           Cycle priority P1-P4 forward / back"),
+        Line::from("  e               Edit due date (empty clears it)"),
+        Line::from("  D               Delete after confirmation"),
+        Line::from("  Changes show at once and are undone if Reclaim rejects them."),
+        Line::from(""),
         Line::from("Actions"),
         Line::from("  r               Refresh tasks from API"),
         Line::from("  ?               Toggle this help"),
         Line::from(""),
+        Line::from("Commands (Tab completes, Up/Down browse history)"),
+        Line::from("  :sort due|priority|title|status|id [desc]   :sort none"),
+        Line::from("  :filter status=open priority=P1,P2 category=WORK   :filter clear"),
+        Line::from("  :set priority=P2 due=friday   :unset notes   (same as patch --set)"),
+        Line::from("  :snooze 2h   :done   :start   :stop   :new Title here"),
+        Line::from("  :set and :unset stage edits on the row; :w sends them and waits"),
+        Line::from("  :help           Show this help"),
+        Line::from(""),
         Line::from("Exit"),
-        Line::from("  :q              Vim-style quit command"),
-        Line::from("  Esc             Quit immediately"),
+        Line::from("  :q / :wq / :q!  Quit / save edits and quit / quit dropping edits"),
+        Line::from("  Esc             Quit, unless edits are unsaved"),
         Line::from("  Ctrl+C          Quit immediately"),
         Line::from(""),
         Line::from("Press ? or Enter to close this panel."),
@@ -401,6 +2121,139 @@ fn draw_help_popup(frame: &mut Frame<'_>) {
     frame.render_widget(help, area);
 }
 
+fn draw_action_popup(frame: &mut Frame<'_>, popup: &Popup, event_category: EventCategory) {
+    let (title, lines) = match popup {
+        Popup::Snooze { task_id, selected } => {
+            let mut lines = vec![
+                Line::from(format!("Snooze #{task_id} until:")),
+                Line::from(""),
+            ];
+            let labels = SNOOZE_PRESETS
+                .iter()
+                .map(|(label, _)| *label)
+                .chain(["Custom..."]);
+            for (index, label) in labels.enumerate() {
+                let line = format!("  {}. {label}", index + 1);
+                lines.push(if index == *selected {
+                    Line::from(Span::styled(
+                        line,
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                } else {
+                    Line::from(line)
+                });
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from("j/k or 1-5 choose  Enter snooze  Esc cancel"));
+            ("Snooze", lines)
+        }
+        Popup::Input {
+            task_id,
+            field,
+            text,
+            error,
+        } => {
+            let (title, prompt) = match field {
+                InputField::Due => ("Due date", format!("New due date for #{task_id}:")),
+                InputField::Snooze => ("Snooze", format!("Snooze #{task_id} until:")),
+            };
+            let mut lines = vec![
+                Line::from(prompt),
+                Line::from(""),
+                Line::from(format!("> {text}_")),
+                Line::from(""),
+            ];
+            if let Some(error) = error {
+                lines.push(Line::from(Span::styled(
+                    format!("Invalid time: {error}"),
+                    Style::default().fg(Color::Red),
+                )));
+            }
+            lines.push(Line::from(
+                "e.g. 2026-03-01, tomorrow 5pm, +2d. Enter save  Esc cancel",
+            ));
+            if *field == InputField::Due {
+                lines.push(Line::from("Leave empty to clear the due date."));
+            }
+            (title, lines)
+        }
+        Popup::Create(form) => {
+            let area = centered_rect(70, 60, frame.area());
+            frame.render_widget(Clear, area);
+            let create = Paragraph::new(create_form_lines(form, event_category))
+                .block(Block::default().title("New task").borders(Borders::ALL))
+                .wrap(Wrap { trim: false });
+            frame.render_widget(create, area);
+            return;
+        }
+        Popup::ConfirmDelete { task_id } => (
+            "Delete",
+            vec![
+                Line::from(format!("Delete task #{task_id}?")),
+                Line::from(""),
+                Line::from("y delete  n/Esc keep"),
+            ],
+        ),
+    };
+
+    let area = centered_rect(60, 40, frame.area());
+    frame.render_widget(Clear, area);
+    let popup = Paragraph::new(lines)
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(popup, area);
+}
+
+fn create_form_lines(form: &CreateForm, event_category: EventCategory) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for (index, field) in FormField::ALL.into_iter().enumerate() {
+        let focused = index == form.focus;
+        let label = format!("{} {:<10} ", if focused { ">" } else { " " }, field.label());
+        let value = &form.values[index];
+        let mut spans = vec![Span::styled(
+            label,
+            if focused {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            },
+        )];
+        spans.push(Span::raw(value.clone()));
+        if focused {
+            spans.push(Span::raw("_"));
+        }
+        if value.is_empty() {
+            let placeholder = match field {
+                FormField::Category => {
+                    format!("WORK or PERSONAL (default {})", event_category.as_str())
+                }
+                field => field.placeholder().to_string(),
+            };
+            spans.push(Span::styled(
+                format!(" {placeholder}"),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::from(""));
+    if let Some(error) = &form.error {
+        lines.push(Line::from(Span::styled(
+            error.clone(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    lines.push(Line::from(
+        "Tab/Down next  Shift+Tab/Up previous  Enter create  Esc cancel",
+    ));
+    lines
+}
+
 fn selected_task_lines(app: &DashboardApp) -> Vec<Line<'static>> {
     let Some(task) = app.selected_task() else {
         return vec![
@@ -421,6 +2274,9 @@ fn selected_task_lines(app: &DashboardApp) -> Vec<Line<'static>> {
         )),
         Line::from(format!("due: {}", task.due.as_deref().unwrap_or("-"))),
     ];
+    if let Some(until) = task.extra.get("snoozeUntil").and_then(Value::as_str) {
+        lines.push(Line::from(format!("snoozed until: {until}")));
+    }
 
     if let Some(notes) = task
         .notes
@@ -497,18 +2353,17 @@ fn map_tui_error(context: &str, error: io::Error) -> CliError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use crate::test_support::{task, utc_time};
+
+    const NOW: &str = "2026-02-20T12:00:00Z";
 
     fn test_task(id: u64, title: &str) -> Task {
         Task {
-            id,
-            title: title.to_string(),
             status: Some("NEW".to_string()),
             due: Some("2026-02-23T17:00:00Z".to_string()),
             priority: Some("P3".to_string()),
             notes: Some("note".to_string()),
-            deleted: false,
-            extra: HashMap::new(),
+            ..task(id, title)
         }
     }
 
@@ -530,10 +2385,16 @@ mod tests {
     }
 
     #[test]
-    fn colon_q_quits_dashboard() {
+    fn colon_q_quits_dashboard_on_enter() {
         let mut app = DashboardApp::new(vec![test_task(1, "One")], TaskFilter::Active);
         assert_eq!(app.handle_key(key(KeyCode::Char(':'))), AppAction::None);
-        assert_eq!(app.handle_key(key(KeyCode::Char('q'))), AppAction::Quit);
+        assert_eq!(app.handle_key(key(KeyCode::Char('q'))), AppAction::None);
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), AppAction::Quit);
+
+        app.handle_key(key(KeyCode::Char(':')));
+        type_text(&mut app, "quit");
+        assert_eq!(app.command_buffer, ":quit");
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), AppAction::Quit);
     }
 
     #[test]
@@ -561,4 +2422,652 @@ mod tests {
         app.handle_key(key(KeyCode::Char('k')));
         assert_eq!(app.selected_index(), Some(1));
     }
+
+    fn saved(request: &TaskRequest, task: &Task) -> Result<Outcome<Option<Task>>, CliError> {
+        let mut task = task.clone();
+        request.change.apply(&mut task);
+        Ok(Outcome::Sent(Some(task)))
+    }
+
+    fn sent(action: AppAction) -> TaskRequest {
+        match action {
+            AppAction::Send(request) => request,
+            other => panic!("expected a request, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn done_updates_row_at_once_and_rolls_back_on_failure() {
+        let mut app = DashboardApp::new(vec![test_task(1, "One")], TaskFilter::Active);
+
+        let request = sent(app.handle_key(key(KeyCode::Char('x'))));
+        assert_eq!(request.change, TaskChange::Done);
+        assert_eq!(app.tasks[0].status.as_deref(), Some("COMPLETE"));
+        assert!(app.in_flight.contains_key(&1));
+
+        app.finish_change(request, Err(CliError::Output("HTTP 500".to_string())));
+        assert_eq!(app.tasks[0].status.as_deref(), Some("NEW"));
+        assert!(app.in_flight.is_empty());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Could not save #1 (done): HTTP 500. Change undone.")
+        );
+    }
+
+    #[test]
+    fn dry_run_change_is_undone_without_reporting_a_failure() {
+        let mut app = DashboardApp::new(vec![test_task(1, "One")], TaskFilter::Active);
+
+        let request = sent(app.handle_key(key(KeyCode::Char('x'))));
+        let planned = PlannedRequest {
+            method: "POST".to_string(),
+            url: "https://api.test/api/planner/done/task/1".to_string(),
+            body: None,
+        };
+        app.finish_change(request, Ok(Outcome::Planned(planned)));
+        assert_eq!(app.tasks[0].status.as_deref(), Some("NEW"));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some(
+                "Dry run: #1 (done) not sent; would POST https://api.test/api/planner/done/task/1."
+            )
+        );
+    }
+
+    #[test]
+    fn start_toggles_to_stop_and_server_task_replaces_row() {
+        let mut app = DashboardApp::new(vec![test_task(1, "One")], TaskFilter::Active);
+
+        let request = sent(app.handle_key(key(KeyCode::Char('s'))));
+        assert_eq!(request.change, TaskChange::Start);
+        let result = saved(&request, &app.tasks[0]);
+        app.finish_change(request, result);
+        assert_eq!(app.tasks[0].status.as_deref(), Some("IN_PROGRESS"));
+        assert_eq!(app.status_message.as_deref(), Some("#1 One: started."));
+
+        let request = sent(app.handle_key(key(KeyCode::Char('s'))));
+        assert_eq!(request.change, TaskChange::Stop);
+    }
+
+    #[test]
+    fn priority_cycles_and_waits_for_the_previous_change() {
+        let mut app = DashboardApp::new(vec![test_task(1, "One")], TaskFilter::Active);
+
+        let request = sent(app.handle_key(key(KeyCode::Char('p'))));
+        assert_eq!(request.change, TaskChange::Priority("P4"));
+        assert_eq!(app.handle_key(key(KeyCode::Char('p'))), AppAction::None);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Task #1 is still saving; try again in a moment.")
+        );
+
+        let result = saved(&request, &app.tasks[0]);
+        app.finish_change(request, result);
+        let request = sent(app.handle_key(key(KeyCode::Char('p'))));
+        assert_eq!(request.change, TaskChange::Priority("P1"));
+        app.finish_change(request, Err(CliError::Output("offline".to_string())));
+        assert_eq!(app.tasks[0].priority.as_deref(), Some("P4"));
+
+        let request = sent(app.handle_key(key(KeyCode::Char('P'))));
+        assert_eq!(request.change, TaskChange::Priority("P3"));
+    }
+
+    #[test]
+    fn delete_needs_confirmation_and_failed_delete_restores_row() {
+        let mut app = DashboardApp::new(
+            vec![test_task(1, "One"), test_task(2, "Two")],
+            TaskFilter::Active,
+        );
+        app.select_next();
+
+        app.handle_key(key(KeyCode::Char('D')));
+        assert_eq!(app.popup, Some(Popup::ConfirmDelete { task_id: 2 }));
+        assert_eq!(app.handle_key(key(KeyCode::Esc)), AppAction::None);
+        assert_eq!(app.popup, None);
+        assert_eq!(app.tasks.len(), 2);
+
+        app.handle_key(key(KeyCode::Char('D')));
+        let request = sent(app.handle_key(key(KeyCode::Char('y'))));
+        assert_eq!(request.change, TaskChange::Delete);
+        assert_eq!(app.tasks.len(), 1);
+        assert_eq!(app.selected_index(), Some(0));
+
+        app.finish_change(request, Err(CliError::Output("HTTP 403".to_string())));
+        let ids = app.tasks.iter().map(|task| task.id).collect::<Vec<_>>();
+        assert_eq!(ids, [1, 2]);
+    }
+
+    #[test]
+    fn snooze_picker_and_due_input_parse_times() {
+        let mut app = DashboardApp::new(vec![test_task(1, "One")], TaskFilter::Active)
+            .with_time(utc_time(NOW));
+
+        app.handle_key(key(KeyCode::Char('z')));
+        app.handle_key(key(KeyCode::Char('j')));
+        let request = sent(app.handle_key(key(KeyCode::Enter)));
+        assert_eq!(
+            request.change,
+            TaskChange::Snooze("2026-02-20T15:00:00Z".to_string())
+        );
+        assert_eq!(
+            app.tasks[0].extra.get("snoozeUntil"),
+            Some(&json!("2026-02-20T15:00:00Z"))
+        );
+        app.finish_change(request, Ok(Outcome::Sent(None)));
+
+        app.handle_key(key(KeyCode::Char('e')));
+        for _ in 0.."2026-02-23T17:00:00Z".len() {
+            app.handle_key(key(KeyCode::Backspace));
+        }
+        for ch in "someday".chars() {
+            app.handle_key(key(KeyCode::Char(ch)));
+        }
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), AppAction::None);
+        assert!(matches!(
+            app.popup,
+            Some(Popup::Input { error: Some(_), .. })
+        ));
+
+        for _ in 0.."someday".len() {
+            app.handle_key(key(KeyCode::Backspace));
+        }
+        for ch in "tomorrow 5pm".chars() {
+            app.handle_key(key(KeyCode::Char(ch)));
+        }
+        let request = sent(app.handle_key(key(KeyCode::Enter)));
+        assert_eq!(
+            request.change,
+            TaskChange::Due(Some("2026-02-21T17:00:00Z".to_string()))
+        );
+        assert_eq!(app.tasks[0].due.as_deref(), Some("2026-02-21T17:00:00Z"));
+    }
+
+    #[test]
+    fn snooze_presets_parse() {
+        let time = utc_time(NOW);
+        for (label, input) in SNOOZE_PRESETS {
+            assert!(time.parse_instant(input).is_ok(), "{label}: {input}");
+        }
+    }
+
+    #[test]
+    fn esc_in_a_popup_closes_it_instead_of_quitting() {
+        let mut app = DashboardApp::new(vec![test_task(1, "One")], TaskFilter::Active);
+        app.handle_key(key(KeyCode::Char('z')));
+        assert_eq!(app.handle_key(key(KeyCode::Esc)), AppAction::None);
+        assert_eq!(app.popup, None);
+        assert!(app.in_flight.is_empty());
+        assert_eq!(app.tasks[0].extra.get("snoozeUntil"), None);
+    }
+
+    fn type_text(app: &mut DashboardApp, text: &str) {
+        for ch in text.chars() {
+            app.handle_key(key(KeyCode::Char(ch)));
+        }
+    }
+
+    fn form(app: &DashboardApp) -> &CreateForm {
+        match &app.popup {
+            Some(Popup::Create(form)) => form,
+            other => panic!("expected the create form, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn create_form_builds_request_like_create_command() {
+        let mut app = DashboardApp::new(vec![test_task(1, "One")], TaskFilter::Active)
+            .with_time(utc_time(NOW))
+            .with_event_category(EventCategory::Personal);
+
+        app.handle_key(key(KeyCode::Char('a')));
+        type_text(&mut app, "Write report");
+        app.handle_key(key(KeyCode::Tab));
+        app.handle_key(key(KeyCode::Tab));
+        type_text(&mut app, "p2");
+        app.handle_key(key(KeyCode::Tab));
+        type_text(&mut app, "tomorrow 5pm");
+        app.handle_key(key(KeyCode::Tab));
+        type_text(&mut app, "2h");
+        app.handle_key(key(KeyCode::Tab));
+        app.handle_key(key(KeyCode::Tab));
+        type_text(&mut app, "30m");
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), AppAction::None);
+
+        let request = app.create_pending.take().expect("request queued");
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "title": "Write report",
+                "priority": "P2",
+                "due": "2026-02-21T17:00:00Z",
+                "timeChunksRequired": 8,
+                "eventCategory": "PERSONAL",
+                "minChunkSize": 2,
+                "maxChunkSize": 8,
+                "alwaysPrivate": true
+            })
+        );
+
+        let mut created = test_task(9, "Write report");
+        created.priority = Some("P2".to_string());
+        app.insert_created(created);
+        assert_eq!(app.popup, None);
+        assert_eq!(app.selected_task().map(|task| task.id), Some(9));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Created task #9: Write report")
+        );
+    }
+
+    #[test]
+    fn create_form_points_at_the_invalid_field() {
+        let time = utc_time(NOW);
+        let mut form = CreateForm::default();
+        let error = |form: &CreateForm| {
+            form.build_request(&time, EventCategory::Work)
+                .map(|_| ())
+                .unwrap_err()
+        };
+
+        assert_eq!(
+            error(&form),
+            (FormField::Title, "Title is required.".to_string())
+        );
+        form.values[FormField::Title as usize] = "Plan".to_string();
+        form.values[FormField::Priority as usize] = "P5".to_string();
+        assert_eq!(error(&form).0, FormField::Priority);
+        form.values[FormField::Priority as usize].clear();
+        form.values[FormField::Duration as usize] = "50m".to_string();
+        assert!(error(&form).1.starts_with("Invalid duration value '50m'"));
+        form.values[FormField::Duration as usize].clear();
+        form.values[FormField::MinChunk as usize] = "30m".to_string();
+        assert_eq!(error(&form).0, FormField::Duration);
+        form.values[FormField::Duration as usize] = "1h".to_string();
+        form.values[FormField::MaxChunk as usize] = "2h".to_string();
+        assert_eq!(error(&form).0, FormField::MaxChunk);
+        form.values[FormField::MaxChunk as usize] = "15m".to_string();
+        assert_eq!(
+            error(&form),
+            (
+                FormField::MinChunk,
+                "Invalid chunk bounds: minimum (30m) cannot exceed maximum (15m).".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn create_form_keeps_input_after_errors_and_esc_discards_it() {
+        let mut app = DashboardApp::new(vec![test_task(1, "One")], TaskFilter::Active);
+        app.handle_key(key(KeyCode::Char('a')));
+        app.handle_key(key(KeyCode::Tab));
+        type_text(&mut app, "notes with q and x");
+        app.handle_key(key(KeyCode::Enter));
+        assert_eq!(form(&app).focus, FormField::Title as usize);
+        assert_eq!(form(&app).error.as_deref(), Some("Title is required."));
+        assert!(app.create_pending.is_none());
+
+        type_text(&mut app, "Plan");
+        app.handle_key(key(KeyCode::Enter));
+        assert!(app.create_pending.take().is_some());
+        app.create_failed(&CliError::Output("HTTP 500".to_string()));
+        assert_eq!(form(&app).error.as_deref(), Some("HTTP 500"));
+        assert_eq!(form(&app).values[0], "Plan");
+        assert_eq!(app.tasks.len(), 1);
+
+        assert_eq!(app.handle_key(key(KeyCode::Esc)), AppAction::None);
+        assert_eq!(app.popup, None);
+    }
+
+    #[test]
+    fn search_positions_prefer_contiguous_matches() {
+        assert_eq!(search_positions("IT", "Ship it"), Some(vec![5, 6]));
+        assert_eq!(search_positions("shpt", "Ship it"), Some(vec![0, 1, 3, 6]));
+        assert_eq!(search_positions("s it", "Ship it"), Some(vec![0, 2, 6]));
+        assert_eq!(search_positions("tis", "Ship it"), None);
+        assert_eq!(search_positions(" ", "Ship it"), None);
+    }
+
+    #[test]
+    fn slash_searches_incrementally_and_n_jumps_between_matches() {
+        let mut notes_match = test_task(3, "Three");
+        notes_match.notes = Some("ask about the report".to_string());
+        let mut app = DashboardApp::new(
+            vec![
+                test_task(1, "Write report"),
+                test_task(2, "Two"),
+                notes_match,
+                test_task(4, "Report back"),
+            ],
+            TaskFilter::Active,
+        );
+        app.select_next();
+
+        app.handle_key(key(KeyCode::Char('/')));
+        type_text(&mut app, "rep");
+        assert_eq!(app.selected_index(), Some(2));
+        assert_eq!(app.search_matches(), [0, 2, 3]);
+        app.handle_key(key(KeyCode::Enter));
+        assert!(!app.search_input);
+
+        assert_eq!(app.handle_key(key(KeyCode::Char('n'))), AppAction::None);
+        assert_eq!(app.selected_index(), Some(3));
+        app.handle_key(key(KeyCode::Char('n')));
+        assert_eq!(app.selected_index(), Some(0));
+        app.handle_key(key(KeyCode::Char('N')));
+        assert_eq!(app.selected_index(), Some(3));
+        assert_eq!(app.popup, None);
+
+        assert_eq!(app.handle_key(key(KeyCode::Esc)), AppAction::None);
+        assert!(app.search.is_empty());
+        app.handle_key(key(KeyCode::Char('n')));
+        assert_eq!(app.popup, None);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No search to repeat; press / to search.")
+        );
+        assert_eq!(app.handle_key(key(KeyCode::Esc)), AppAction::Quit);
+    }
+
+    #[test]
+    fn filter_bar_narrows_the_list_and_keeps_the_selection() {
+        let mut work = test_task(1, "One");
+        work.extra
+            .insert("eventCategory".to_string(), json!("WORK"));
+        let mut done = test_task(2, "Two");
+        done.status = Some("COMPLETE".to_string());
+        let mut urgent = test_task(3, "Three");
+        urgent.priority = Some("P1".to_string());
+        urgent
+            .extra
+            .insert("eventCategory".to_string(), json!("WORK"));
+        let mut app = DashboardApp::new(vec![work, done, urgent], TaskFilter::All);
+        app.select_last();
+
+        app.handle_key(key(KeyCode::Char('f')));
+        app.handle_key(key(KeyCode::Char('s')));
+        assert_eq!(app.view_filters.status, Some(TaskStatusFilter::Open));
+        assert_eq!(app.view, [1, 3]);
+        assert_eq!(app.selected_task().map(|task| task.id), Some(3));
+
+        app.handle_key(key(KeyCode::Char('1')));
+        app.handle_key(key(KeyCode::Char('c')));
+        assert_eq!(app.view, [3]);
+        assert_eq!(app.view_filters.summary(), "status open, P1, WORK");
+        app.handle_key(key(KeyCode::Char('1')));
+        app.handle_key(key(KeyCode::Char('j')));
+        assert_eq!(app.view, [1, 3]);
+        assert_eq!(app.handle_key(key(KeyCode::Esc)), AppAction::None);
+        assert!(!app.filter_bar);
+
+        app.handle_key(key(KeyCode::Char('x')));
+        assert_eq!(app.view, [1]);
+        assert_eq!(app.selected_task().map(|task| task.id), Some(1));
+
+        app.handle_key(key(KeyCode::Char('f')));
+        app.handle_key(key(KeyCode::Char('0')));
+        assert_eq!(app.view, [1, 2, 3]);
+    }
+
+    fn run(app: &mut DashboardApp, line: &str) -> AppAction {
+        app.handle_key(key(KeyCode::Char(':')));
+        type_text(app, line);
+        app.handle_key(key(KeyCode::Enter))
+    }
+
+    #[test]
+    fn sort_and_filter_commands_reorder_and_narrow_the_list() {
+        let mut first = test_task(1, "Later");
+        first.due = Some("2026-03-01T00:00:00Z".to_string());
+        let mut second = test_task(2, "No due");
+        second.due = None;
+        second.priority = Some("P1".to_string());
+        let third = test_task(3, "Sooner");
+        let mut app = DashboardApp::new(vec![first, second, third], TaskFilter::Active);
+
+        assert_eq!(run(&mut app, "sort due"), AppAction::None);
+        assert_eq!(app.view, [3, 1, 2]);
+        assert_eq!(app.selected_task().map(|task| task.id), Some(1));
+        run(&mut app, "sort priority");
+        assert_eq!(app.view, [2, 1, 3]);
+        run(&mut app, "sort title desc");
+        assert_eq!(app.view, [3, 2, 1]);
+        let titles = app
+            .listed_tasks()
+            .map(|task| task.title.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, ["Sooner", "No due", "Later"]);
+        run(&mut app, "sort none");
+        assert_eq!(app.view, [1, 2, 3]);
+
+        run(&mut app, "filter priority=P3");
+        assert_eq!(app.view, [1, 3]);
+        assert_eq!(app.status_message.as_deref(), Some("Filters: P3."));
+        run(&mut app, "filter clear");
+        assert_eq!(app.view, [1, 2, 3]);
+
+        run(&mut app, "sort size");
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Cannot sort by 'size': use due, priority, title, status, id or none.")
+        );
+    }
+
+    #[test]
+    fn set_command_patches_the_selected_task_like_patch_set() {
+        let mut app = DashboardApp::new(vec![test_task(1, "One")], TaskFilter::Active)
+            .with_time(utc_time(NOW));
+
+        assert_eq!(run(&mut app, "set priority=P1"), AppAction::None);
+        assert_eq!(run(&mut app, "set due=tomorrow 5pm"), AppAction::None);
+        assert_eq!(app.tasks[0].priority.as_deref(), Some("P1"));
+        assert!(app.in_flight.is_empty());
+        let request = written(run(&mut app, "w"));
+        assert_eq!(
+            request.change,
+            TaskChange::Fields(
+                json!({ "priority": "P1", "due": "2026-02-21T17:00:00Z" })
+                    .as_object()
+                    .unwrap()
+                    .clone()
+            )
+        );
+        assert_eq!(app.tasks[0].priority.as_deref(), Some("P1"));
+        assert_eq!(app.tasks[0].due.as_deref(), Some("2026-02-21T17:00:00Z"));
+        app.finish_change(request, Err(CliError::Output("HTTP 422".to_string())));
+        assert_eq!(app.tasks[0].priority.as_deref(), Some("P3"));
+
+        assert!(app.staged.is_empty());
+
+        run(&mut app, "unset notes");
+        assert_eq!(app.tasks[0].notes, None);
+        let request = written(run(&mut app, "w"));
+        app.finish_change(request, Ok(Outcome::Sent(None)));
+
+        app.tasks[0].extra.insert(
+            "assist".to_string(),
+            json!({ "type": "TASK", "locked": true }),
+        );
+        run(&mut app, "set assist.locked=false");
+        let request = written(run(&mut app, "w"));
+        assert_eq!(
+            request.change,
+            TaskChange::Fields(
+                json!({ "assist": { "type": "TASK", "locked": false } })
+                    .as_object()
+                    .unwrap()
+                    .clone()
+            )
+        );
+        app.finish_change(request, Ok(Outcome::Sent(None)));
+
+        assert_eq!(run(&mut app, "set title=42"), AppAction::None);
+        assert!(app
+            .status_message
+            .as_deref()
+            .is_some_and(|status| status.starts_with("Cannot update #1:")));
+        assert!(app.in_flight.is_empty());
+        assert!(app.staged.is_empty());
+    }
+
+    /// The one request a `:w` sends.
+    fn written(action: AppAction) -> TaskRequest {
+        match action {
+            AppAction::Write(mut requests) if requests.len() == 1 => requests.remove(0),
+            other => panic!("expected one staged request, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn staged_edits_survive_refresh_and_block_plain_quit() {
+        let mut app = DashboardApp::new(vec![test_task(1, "One")], TaskFilter::Active)
+            .with_time(utc_time(NOW));
+        run(&mut app, "set priority=P1");
+
+        app.replace_tasks(vec![test_task(1, "Renamed")]);
+        assert_eq!(app.tasks[0].title, "Renamed");
+        assert_eq!(app.tasks[0].priority.as_deref(), Some("P1"));
+
+        assert_eq!(run(&mut app, "q"), AppAction::None);
+        assert_eq!(app.handle_key(key(KeyCode::Esc)), AppAction::None);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("1 task with unsaved edits: :w saves, :wq saves and quits, :q! discards.")
+        );
+        assert_eq!(run(&mut app, "q!"), AppAction::Quit);
+
+        let AppAction::WriteQuit(requests) = run(&mut app, "wq") else {
+            panic!("expected :wq to save and quit");
+        };
+        assert_eq!(requests.len(), 1);
+        app.finish_change(
+            requests.into_iter().next().unwrap(),
+            Err(CliError::Output("HTTP 422".to_string())),
+        );
+        assert_eq!(app.tasks[0].title, "Renamed");
+        assert_eq!(app.tasks[0].priority.as_deref(), Some("P3"));
+    }
+
+    #[test]
+    fn snooze_done_and_new_commands_match_their_keys() {
+        let mut app = DashboardApp::new(vec![test_task(1, "One")], TaskFilter::Active)
+            .with_time(utc_time(NOW));
+
+        let request = sent(run(&mut app, "snooze 2h"));
+        assert_eq!(
+            request.change,
+            TaskChange::Snooze("2026-02-20T14:00:00Z".to_string())
+        );
+        app.finish_change(request, Ok(Outcome::Sent(None)));
+        assert_eq!(sent(run(&mut app, "done")).change, TaskChange::Done);
+
+        assert_eq!(run(&mut app, "new Write the report"), AppAction::None);
+        let request = app.create_pending.take().expect("request queued");
+        assert_eq!(request.title, "Write the report");
+        assert_eq!(request.event_category.as_deref(), Some("WORK"));
+
+        assert_eq!(run(&mut app, "w"), AppAction::Write(Vec::new()));
+        assert_eq!(run(&mut app, "wq"), AppAction::WriteQuit(Vec::new()));
+    }
+
+    #[test]
+    fn command_line_completes_with_tab_and_recalls_history() {
+        let mut app = DashboardApp::new(vec![test_task(1, "One")], TaskFilter::Active);
+        run(&mut app, "sort title");
+
+        app.handle_key(key(KeyCode::Char(':')));
+        type_text(&mut app, "fil");
+        app.handle_key(key(KeyCode::Tab));
+        assert_eq!(app.command_buffer, ":filter ");
+        type_text(&mut app, "cat");
+        app.handle_key(key(KeyCode::Tab));
+        assert_eq!(app.command_buffer, ":filter category=");
+
+        app.handle_key(key(KeyCode::Up));
+        assert_eq!(app.command_buffer, ":sort title");
+        app.handle_key(key(KeyCode::Down));
+        assert_eq!(app.command_buffer, ":filter category=");
+
+        assert_eq!(app.handle_key(key(KeyCode::Esc)), AppAction::None);
+        assert!(app.command_buffer.is_empty());
+        assert_eq!(run(&mut app, "frobnicate"), AppAction::None);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Unknown command :frobnicate. Try :help or press Tab for the list.")
+        );
+    }
+
+    #[test]
+    fn agenda_tab_loads_on_first_visit_and_pages_by_day() {
+        let mut app = DashboardApp::new(vec![test_task(1, "One")], TaskFilter::Active)
+            .with_time(utc_time(NOW));
+        app.handle_key(key(KeyCode::Tab));
+        assert_eq!(app.tab, Tab::Agenda);
+        assert!(app.agenda_pending);
+        assert_eq!(app.agenda.query().start.as_deref(), Some("2026-02-20"));
+
+        app.agenda_pending = false;
+        app.agenda.load(Vec::new(), &app.time);
+        app.handle_key(key(KeyCode::Char('1')));
+        app.handle_key(key(KeyCode::Char('2')));
+        assert!(!app.agenda_pending);
+
+        app.handle_key(key(KeyCode::Char('l')));
+        assert!(app.agenda_pending);
+        assert_eq!(app.agenda.query().start.as_deref(), Some("2026-02-21"));
+        app.handle_key(key(KeyCode::Char('h')));
+        app.handle_key(key(KeyCode::Char('h')));
+        assert_eq!(app.agenda.query().start.as_deref(), Some("2026-02-19"));
+
+        // Task keys do nothing on the agenda tab.
+        assert_eq!(app.handle_key(key(KeyCode::Char('x'))), AppAction::None);
+        assert!(app.popup.is_none());
+    }
+
+    #[test]
+    fn enter_on_a_task_block_opens_its_task() {
+        let mut app = DashboardApp::new(
+            vec![test_task(1, "One"), test_task(2, "Two")],
+            TaskFilter::Active,
+        )
+        .with_time(utc_time(NOW));
+        let mut block: crate::reclaim_api::Event = serde_json::from_value(serde_json::json!({
+            "title": "Two",
+            "eventStart": "2026-02-20T13:00:00Z",
+            "eventEnd": "2026-02-20T14:00:00Z",
+            "reclaimManaged": true,
+            "assist": { "taskId": 2 },
+        }))
+        .unwrap();
+        let meeting = serde_json::from_value(serde_json::json!({
+            "title": "Standup",
+            "eventStart": "2026-02-20T15:00:00Z",
+            "eventEnd": "2026-02-20T15:15:00Z",
+        }))
+        .unwrap();
+        app.handle_key(key(KeyCode::Char('2')));
+        app.agenda.load(vec![block.clone(), meeting], &app.time);
+
+        app.handle_key(key(KeyCode::Char('j')));
+        app.handle_key(key(KeyCode::Enter));
+        assert_eq!(app.tab, Tab::Agenda);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Only task blocks link to a task.")
+        );
+
+        app.handle_key(key(KeyCode::Char('k')));
+        app.handle_key(key(KeyCode::Enter));
+        assert_eq!(app.tab, Tab::Tasks);
+        assert_eq!(app.selected_task().map(|task| task.id), Some(2));
+
+        block.assist.as_mut().unwrap().task_id = Some(9);
+        app.handle_key(key(KeyCode::Tab));
+        app.agenda.load(vec![block], &app.time);
+        app.handle_key(key(KeyCode::Enter));
+        assert_eq!(app.tab, Tab::Agenda);
+        assert!(app
+            .status_message
+            .as_deref()
+            .is_some_and(|message| message.starts_with("Task #9 is not in the list")));
+    }
 }
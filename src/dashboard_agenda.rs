@@ -0,0 +1,516 @@
+use crate::{
+    datetime::TimeContext,
+    duration,
+    reclaim_api::{Event, EventListQuery},
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::ListItem,
+};
+
+/// Width of the duration bar, one cell per 15 minutes; longer blocks end in `+`.
+const BAR_CELLS: usize = 16;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AgendaSpan {
+    Day,
+    Week,
+}
+
+impl AgendaSpan {
+    fn days(self) -> i64 {
+        match self {
+            AgendaSpan::Day => 1,
+            AgendaSpan::Week => 7,
+        }
+    }
+}
+
+/// How a block is drawn: task blocks and other Reclaim-managed blocks (habits, buffers)
+/// stand apart from ordinary meetings.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BlockKind {
+    Task(u64),
+    Reclaim,
+    Meeting,
+}
+
+impl BlockKind {
+    fn marker(self) -> &'static str {
+        match self {
+            BlockKind::Task(_) => "◆",
+            BlockKind::Reclaim => "◇",
+            BlockKind::Meeting => "●",
+        }
+    }
+
+    fn style(self) -> Style {
+        match self {
+            BlockKind::Task(_) => Style::default().fg(Color::Cyan),
+            BlockKind::Reclaim => Style::default().fg(Color::Magenta),
+            BlockKind::Meeting => Style::default(),
+        }
+    }
+}
+
+/// One event placed on the agenda.
+#[derive(Debug, Clone)]
+pub struct AgendaEntry {
+    pub day: NaiveDate,
+    /// `None` for all-day events.
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub event: Event,
+}
+
+impl AgendaEntry {
+    /// Places `event` on its first day, or `None` when it has no readable start.
+    fn new(event: Event, time: &TimeContext) -> Option<Self> {
+        let raw_start = event.start()?;
+        let (day, start) = match parse_instant(&raw_start) {
+            Some(start) => (time.local_date(start), Some(start)),
+            None => (
+                NaiveDate::parse_from_str(&raw_start, "%Y-%m-%d").ok()?,
+                None,
+            ),
+        };
+        let end = event.end().as_deref().and_then(parse_instant);
+        Some(Self {
+            day,
+            start,
+            end,
+            event,
+        })
+    }
+
+    pub fn kind(&self) -> BlockKind {
+        match self.event.task_id() {
+            Some(task_id) => BlockKind::Task(task_id),
+            None if self.event.is_reclaim_managed() => BlockKind::Reclaim,
+            None => BlockKind::Meeting,
+        }
+    }
+
+    fn minutes(&self) -> Option<i64> {
+        Some((self.end? - self.start?).num_minutes()).filter(|minutes| *minutes > 0)
+    }
+
+    fn time_range(&self, time: &TimeContext) -> String {
+        let Some(start) = self.start else {
+            return "all day".to_string();
+        };
+        let clock = |instant| time.local_datetime(instant).format("%H:%M").to_string();
+        match self.end {
+            Some(end) => format!("{}-{}", clock(start), clock(end)),
+            None => clock(start),
+        }
+    }
+}
+
+/// The agenda tab: a day or week of events from `list_events`.
+#[derive(Debug)]
+pub struct Agenda {
+    first_day: NaiveDate,
+    span: AgendaSpan,
+    entries: Vec<AgendaEntry>,
+    selected: Option<usize>,
+    /// The range `entries` were loaded for.
+    loaded: Option<(NaiveDate, AgendaSpan)>,
+}
+
+impl Agenda {
+    pub fn new(today: NaiveDate) -> Self {
+        Self {
+            first_day: today,
+            span: AgendaSpan::Day,
+            entries: Vec::new(),
+            selected: None,
+            loaded: None,
+        }
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.loaded == Some((self.first_day, self.span))
+    }
+
+    fn days(&self) -> impl Iterator<Item = NaiveDate> {
+        let first_day = self.first_day;
+        (0..self.span.days()).map(move |offset| first_day + Duration::days(offset))
+    }
+
+    fn last_day(&self) -> NaiveDate {
+        self.first_day + Duration::days(self.span.days() - 1)
+    }
+
+    /// Dates for `list_events`. The end is the day after the range, and entries are
+    /// placed by their local start date anyway.
+    pub fn query(&self) -> EventListQuery {
+        EventListQuery {
+            start: Some(self.first_day.format("%Y-%m-%d").to_string()),
+            end: Some(
+                (self.last_day() + Duration::days(1))
+                    .format("%Y-%m-%d")
+                    .to_string(),
+            ),
+            ..EventListQuery::default()
+        }
+    }
+
+    /// Takes the events for the current range and selects the first one that has not ended.
+    pub fn load(&mut self, events: Vec<Event>, time: &TimeContext) {
+        let (first_day, last_day) = (self.first_day, self.last_day());
+        self.entries = events
+            .into_iter()
+            .filter_map(|event| AgendaEntry::new(event, time))
+            .filter(|entry| (first_day..=last_day).contains(&entry.day))
+            .collect();
+        self.entries
+            .sort_by_key(|entry| (entry.day, entry.start.is_some(), entry.start));
+        self.loaded = Some((self.first_day, self.span));
+
+        let now = time.now();
+        self.selected = self
+            .entries
+            .iter()
+            .position(|entry| entry.end.or(entry.start).is_some_and(|end| end > now))
+            .or((!self.entries.is_empty()).then_some(0));
+    }
+
+    pub fn entry_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Moves the range by `pages` days or weeks.
+    pub fn page(&mut self, pages: i64) {
+        self.first_day += Duration::days(pages * self.span.days());
+        self.clear();
+    }
+
+    pub fn go_to(&mut self, day: NaiveDate) {
+        if self.first_day != day {
+            self.first_day = day;
+            self.clear();
+        }
+    }
+
+    pub fn toggle_span(&mut self) {
+        self.span = match self.span {
+            AgendaSpan::Day => AgendaSpan::Week,
+            AgendaSpan::Week => AgendaSpan::Day,
+        };
+        self.clear();
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.selected = None;
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.entries.is_empty() {
+            self.selected = Some(
+                self.selected
+                    .map_or(0, |index| (index + 1) % self.entries.len()),
+            );
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.entries.is_empty() {
+            let last = self.entries.len() - 1;
+            self.selected = Some(
+                self.selected
+                    .map_or(last, |index| index.checked_sub(1).unwrap_or(last)),
+            );
+        }
+    }
+
+    pub fn select_first(&mut self) {
+        self.selected = (!self.entries.is_empty()).then_some(0);
+    }
+
+    pub fn select_last(&mut self) {
+        self.selected = self.entries.len().checked_sub(1);
+    }
+
+    pub fn selected_entry(&self) -> Option<&AgendaEntry> {
+        self.entries.get(self.selected?)
+    }
+
+    /// e.g. "Mon 23 Feb" or "Mon 23 Feb - Sun 1 Mar".
+    pub fn label(&self) -> String {
+        let day = |date: NaiveDate| date.format("%a %-d %b").to_string();
+        match self.span {
+            AgendaSpan::Day => day(self.first_day),
+            AgendaSpan::Week => format!("{} - {}", day(self.first_day), day(self.last_day())),
+        }
+    }
+
+    /// List rows for every day in the range, with the row of the selected entry. Day
+    /// headers and free-time gaps are not selectable.
+    pub fn rows(&self, time: &TimeContext) -> (Vec<ListItem<'static>>, Option<usize>) {
+        let mut rows = Vec::new();
+        let mut selected_row = None;
+        let today = time.today();
+
+        for day in self.days() {
+            let heading = day.format("%a %Y-%m-%d").to_string();
+            rows.push(ListItem::new(Line::from(Span::styled(
+                if day == today {
+                    format!("{heading} (today)")
+                } else {
+                    heading
+                },
+                Style::default().add_modifier(Modifier::BOLD),
+            ))));
+
+            let mut previous_end: Option<DateTime<Utc>> = None;
+            let mut any = false;
+            for (index, entry) in self
+                .entries
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| entry.day == day)
+            {
+                any = true;
+                if let (Some(end), Some(start)) = (previous_end, entry.start) {
+                    let gap = (start - end).num_minutes();
+                    if gap >= i64::from(duration::CHUNK_MINUTES) {
+                        rows.push(ListItem::new(Line::from(Span::styled(
+                            format!("  {:<11} free {}", "", duration::format_minutes(gap as u32)),
+                            Style::default().fg(Color::DarkGray),
+                        ))));
+                    }
+                }
+                if entry.start.is_some() {
+                    previous_end = entry.end.or(entry.start).max(previous_end);
+                }
+
+                if self.selected == Some(index) {
+                    selected_row = Some(rows.len());
+                }
+                rows.push(ListItem::new(entry_line(entry, time)));
+            }
+            if !any {
+                rows.push(ListItem::new(Line::from(Span::styled(
+                    if self.is_loaded() {
+                        "  No events."
+                    } else {
+                        "  Loading..."
+                    },
+                    Style::default().fg(Color::DarkGray),
+                ))));
+            }
+        }
+
+        (rows, selected_row)
+    }
+
+    pub fn detail_lines(&self, time: &TimeContext) -> Vec<Line<'static>> {
+        let Some(entry) = self.selected_entry() else {
+            return vec![
+                Line::from("No event selected."),
+                Line::from("h/l page, w switches day/week, t jumps to today."),
+            ];
+        };
+        let event = &entry.event;
+
+        let mut lines = vec![
+            Line::from(
+                event
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| "<untitled>".to_string()),
+            ),
+            Line::from(format!(
+                "when: {} {}",
+                entry.day.format("%a %Y-%m-%d"),
+                entry.time_range(time)
+            )),
+        ];
+        if let Some(minutes) = entry.minutes() {
+            lines.push(Line::from(format!(
+                "length: {}",
+                duration::format_minutes(minutes as u32)
+            )));
+        }
+        lines.push(Line::from(match entry.kind() {
+            BlockKind::Task(task_id) => format!("type: task block for #{task_id} (Enter opens it)"),
+            BlockKind::Reclaim => format!(
+                "type: Reclaim block{}",
+                event
+                    .reclaim_event_type
+                    .as_deref()
+                    .map(|kind| format!(" ({kind})"))
+                    .unwrap_or_default()
+            ),
+            BlockKind::Meeting => "type: meeting".to_string(),
+        }));
+        if let Some(priority) = event.priority.as_deref() {
+            lines.push(Line::from(format!("priority: {priority}")));
+        }
+        if let Some(key) = event.display_key() {
+            lines.push(Line::from(format!("key: {key}")));
+        }
+        if let Some(attendees) = event.attendees.as_ref().filter(|list| !list.is_empty()) {
+            lines.push(Line::from(format!("attendees: {}", attendees.len())));
+        }
+        lines
+    }
+}
+
+fn entry_line(entry: &AgendaEntry, time: &TimeContext) -> Line<'static> {
+    let kind = entry.kind();
+    let cells = entry.minutes().map_or(0, |minutes| {
+        (minutes as usize).div_ceil(duration::CHUNK_MINUTES as usize)
+    });
+    let bar = if cells > BAR_CELLS {
+        format!("{}+", "█".repeat(BAR_CELLS - 1))
+    } else {
+        "█".repeat(cells)
+    };
+    let title = entry
+        .event
+        .title
+        .clone()
+        .unwrap_or_else(|| "<untitled>".to_string());
+    let link = match kind {
+        BlockKind::Task(task_id) => format!("  #{task_id}"),
+        _ => String::new(),
+    };
+
+    Line::from(vec![
+        Span::raw(format!("  {:<11} ", entry.time_range(time))),
+        Span::styled(format!("{bar:<width$} ", width = BAR_CELLS), kind.style()),
+        Span::styled(format!("{} {title}{link}", kind.marker()), kind.style()),
+    ])
+}
+
+fn parse_instant(raw: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|instant| instant.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{reclaim_api::EventAssist, test_support::utc_time};
+    use serde_json::json;
+
+    fn event(title: &str, start: &str, end: &str) -> Event {
+        serde_json::from_value(json!({
+            "title": title,
+            "eventStart": start,
+            "eventEnd": end,
+        }))
+        .unwrap()
+    }
+
+    fn task_block(task_id: u64, start: &str, end: &str) -> Event {
+        let mut block = event("Write report", start, end);
+        block.reclaim_managed = Some(true);
+        block.assist = Some(EventAssist {
+            task_id: Some(task_id),
+            habit_id: None,
+            event_type: None,
+            extra: Default::default(),
+        });
+        block
+    }
+
+    #[test]
+    fn load_places_events_on_their_day_in_start_order() {
+        let time = utc_time("2026-02-23T12:00:00Z");
+        let mut agenda = Agenda::new(time.today());
+        agenda.toggle_span();
+        assert_eq!(agenda.label(), "Mon 23 Feb - Sun 1 Mar");
+        let query = agenda.query();
+        assert_eq!(query.start.as_deref(), Some("2026-02-23"));
+        assert_eq!(query.end.as_deref(), Some("2026-03-02"));
+
+        let mut habit = event("Lunch", "2026-02-23T12:00:00Z", "2026-02-23T13:00:00Z");
+        habit.reclaim_managed = Some(true);
+        agenda.load(
+            vec![
+                task_block(12, "2026-02-24T09:00:00Z", "2026-02-24T11:00:00Z"),
+                habit,
+                event("Standup", "2026-02-23T09:00:00Z", "2026-02-23T09:15:00Z"),
+                event("Offsite", "2026-02-23", "2026-02-24"),
+                event("Too late", "2026-03-02T09:00:00Z", "2026-03-02T10:00:00Z"),
+            ],
+            &time,
+        );
+        assert!(agenda.is_loaded());
+
+        let titles = agenda
+            .entries
+            .iter()
+            .map(|entry| (entry.event.title.clone().unwrap(), entry.kind()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            titles,
+            [
+                ("Offsite".to_string(), BlockKind::Meeting),
+                ("Standup".to_string(), BlockKind::Meeting),
+                ("Lunch".to_string(), BlockKind::Reclaim),
+                ("Write report".to_string(), BlockKind::Task(12)),
+            ]
+        );
+        // The first entry that has not ended yet at noon.
+        assert_eq!(
+            agenda
+                .selected_entry()
+                .and_then(|entry| entry.event.title.clone()),
+            Some("Lunch".to_string())
+        );
+
+        let (rows, selected_row) = agenda.rows(&time);
+        // Monday heading, Offsite, Standup, free gap, Lunch, Tuesday heading, block, then
+        // one "No events." line for each of the other five days.
+        assert_eq!(rows.len(), 7 + 2 * 5);
+        assert_eq!(selected_row, Some(4));
+    }
+
+    #[test]
+    fn paging_moves_by_the_span_and_needs_a_reload() {
+        let time = utc_time("2026-02-23T12:00:00Z");
+        let mut agenda = Agenda::new(time.today());
+        agenda.load(Vec::new(), &time);
+        assert!(agenda.is_loaded());
+
+        agenda.page(1);
+        assert_eq!(agenda.label(), "Tue 24 Feb");
+        assert!(!agenda.is_loaded());
+        agenda.toggle_span();
+        agenda.page(-1);
+        assert_eq!(agenda.label(), "Tue 17 Feb - Mon 23 Feb");
+        agenda.go_to(time.today());
+        assert_eq!(agenda.label(), "Mon 23 Feb - Sun 1 Mar");
+    }
+
+    #[test]
+    fn entry_line_draws_a_bar_per_quarter_hour() {
+        let time = utc_time("2026-02-23T12:00:00Z");
+        let entry = AgendaEntry::new(
+            task_block(12, "2026-02-23T09:00:00Z", "2026-02-23T10:30:00Z"),
+            &time,
+        )
+        .unwrap();
+        let text = entry_line(&entry, &time)
+            .spans
+            .iter()
+            .map(|span| span.content.to_string())
+            .collect::<String>();
+        assert_eq!(
+            text,
+            format!(
+                "  09:00-10:30 {}{} ◆ Write report  #12",
+                "█".repeat(6),
+                " ".repeat(10)
+            )
+        );
+    }
+}
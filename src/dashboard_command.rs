@@ -0,0 +1,590 @@
+use crate::{
+    cache,
+    cli::{EventCategory, Priority, TaskStatusFilter},
+    config::write_private_file,
+    error::CliError,
+    fields::{self, FieldUpdate},
+};
+use clap::ValueEnum;
+use std::{fs, path::PathBuf};
+
+/// Commands offered by Tab on an empty `:` line, in the order they are listed.
+const COMMANDS: [&str; 15] = [
+    "done", "filter", "help", "new", "q", "q!", "quit", "set", "snooze", "sort", "start", "stop",
+    "unset", "w", "wq",
+];
+const SORT_KEYS: [&str; 6] = ["due", "priority", "title", "status", "id", "none"];
+const FILTER_KEYS: [&str; 4] = ["status=", "priority=", "category=", "clear"];
+/// Task fields offered by Tab after `:set` and `:unset`.
+const TASK_FIELDS: [&str; 11] = [
+    "title",
+    "notes",
+    "priority",
+    "due",
+    "snoozeUntil",
+    "startAfter",
+    "eventCategory",
+    "timeChunksRequired",
+    "minChunkSize",
+    "maxChunkSize",
+    "alwaysPrivate",
+];
+const HISTORY_LIMIT: usize = 200;
+const HISTORY_FILE_NAME: &str = "dashboard-history";
+
+/// One line typed after `:` in the dashboard.
+#[derive(Debug)]
+pub enum ExCommand {
+    /// `:q`: quit, unless `:set` edits are still staged.
+    Quit,
+    /// `:q!`: quit and drop staged edits.
+    ForceQuit,
+    /// `:w`: send the staged edits and wait until every change has finished.
+    Write,
+    /// `:wq`: `:w`, then quit.
+    WriteQuit,
+    Help,
+    Sort(Option<SortOrder>),
+    /// Filter bar changes; no terms clears every filter.
+    Filter(Vec<FilterTerm>),
+    /// `:set` and `:unset`, parsed like the `--set`/`--unset` flags of `patch`.
+    Set(Vec<FieldUpdate>),
+    Snooze(String),
+    Done,
+    Start,
+    Stop,
+    New(String),
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SortKey {
+    Due,
+    Priority,
+    Title,
+    Status,
+    Id,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SortOrder {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl SortOrder {
+    pub fn label(self) -> String {
+        let key = match self.key {
+            SortKey::Due => "due",
+            SortKey::Priority => "priority",
+            SortKey::Title => "title",
+            SortKey::Status => "status",
+            SortKey::Id => "id",
+        };
+        if self.descending {
+            format!("{key} desc")
+        } else {
+            key.to_string()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum FilterTerm {
+    Status(Option<TaskStatusFilter>),
+    Priorities(Vec<&'static str>),
+    Category(Option<EventCategory>),
+}
+
+/// Parses a command line without its leading `:`.
+pub fn parse(line: &str) -> Result<ExCommand, String> {
+    let line = line.trim();
+    let (name, rest) = line
+        .split_once(char::is_whitespace)
+        .map_or((line, ""), |(name, rest)| (name, rest.trim()));
+    let words = rest.split_whitespace().collect::<Vec<_>>();
+    let no_arguments = |command: ExCommand| {
+        if words.is_empty() {
+            Ok(command)
+        } else {
+            Err(format!(":{name} takes no arguments."))
+        }
+    };
+
+    match name {
+        "q" | "quit" => no_arguments(ExCommand::Quit),
+        "q!" | "quit!" => no_arguments(ExCommand::ForceQuit),
+        "w" | "write" => no_arguments(ExCommand::Write),
+        "wq" | "x" => no_arguments(ExCommand::WriteQuit),
+        "h" | "help" => Ok(ExCommand::Help),
+        "done" => no_arguments(ExCommand::Done),
+        "start" => no_arguments(ExCommand::Start),
+        "stop" => no_arguments(ExCommand::Stop),
+        "sort" => parse_sort(&words).map(ExCommand::Sort),
+        "filter" => parse_filter(&words).map(ExCommand::Filter),
+        "set" => {
+            let entries = join_set_entries(&words)?;
+            fields::parse_updates(&entries, &[], &[], None)
+                .map(ExCommand::Set)
+                .map_err(|error| error.to_string())
+        }
+        "unset" => {
+            if words.is_empty() {
+                return Err(":unset needs at least one field, e.g. :unset notes".to_string());
+            }
+            let keys = words
+                .iter()
+                .map(|word| word.to_string())
+                .collect::<Vec<_>>();
+            fields::parse_updates(&[], &[], &keys, None)
+                .map(ExCommand::Set)
+                .map_err(|error| error.to_string())
+        }
+        "snooze" if rest.is_empty() => {
+            Err(":snooze needs a time, e.g. :snooze 2h or :snooze tomorrow 9am".to_string())
+        }
+        "snooze" => Ok(ExCommand::Snooze(rest.to_string())),
+        "new" if rest.is_empty() => Err(":new needs a title, e.g. :new Write report".to_string()),
+        "new" => Ok(ExCommand::New(rest.to_string())),
+        "" => Err("Command cancelled.".to_string()),
+        other => Err(format!(
+            "Unknown command :{other}. Try :help or press Tab for the list."
+        )),
+    }
+}
+
+fn parse_sort(words: &[&str]) -> Result<Option<SortOrder>, String> {
+    let (key, direction) = match words {
+        [] | ["none"] => return Ok(None),
+        [key] => (*key, None),
+        [key, direction] => (*key, Some(*direction)),
+        _ => return Err("Use :sort KEY [asc|desc], e.g. :sort due".to_string()),
+    };
+    let key = match key.to_ascii_lowercase().as_str() {
+        "due" => SortKey::Due,
+        "priority" => SortKey::Priority,
+        "title" => SortKey::Title,
+        "status" => SortKey::Status,
+        "id" => SortKey::Id,
+        other => {
+            return Err(format!(
+                "Cannot sort by '{other}': use due, priority, title, status, id or none."
+            ))
+        }
+    };
+    let descending = match direction.map(str::to_ascii_lowercase).as_deref() {
+        None | Some("asc") => false,
+        Some("desc") => true,
+        Some(other) => {
+            return Err(format!(
+                "Unknown sort direction '{other}': use asc or desc."
+            ))
+        }
+    };
+
+    Ok(Some(SortOrder { key, descending }))
+}
+
+fn parse_filter(words: &[&str]) -> Result<Vec<FilterTerm>, String> {
+    if matches!(words, [] | ["clear"]) {
+        return Ok(Vec::new());
+    }
+
+    words
+        .iter()
+        .map(|word| {
+            let Some((key, value)) = word.split_once('=') else {
+                return Err(format!(
+                    "Invalid filter '{word}': use status=, priority= or category=, e.g. :filter priority=P1,P2"
+                ));
+            };
+            let any = value.is_empty() || value.eq_ignore_ascii_case("any");
+            match key {
+                "status" if any => Ok(FilterTerm::Status(None)),
+                "status" => TaskStatusFilter::from_str(value, true)
+                    .map(|status| FilterTerm::Status(Some(status)))
+                    .map_err(|_| {
+                        let buckets = TaskStatusFilter::value_variants()
+                            .iter()
+                            .map(|bucket| bucket.as_str())
+                            .collect::<Vec<_>>();
+                        format!("Unknown status '{value}': use {} or any.", buckets.join(", "))
+                    }),
+                "priority" if any => Ok(FilterTerm::Priorities(Vec::new())),
+                "priority" => value
+                    .split(',')
+                    .map(|priority| {
+                        Priority::from_str(priority.trim(), true)
+                            .map(Priority::as_str)
+                            .map_err(|_| format!("Unknown priority '{priority}': use P1-P4."))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(|mut priorities| {
+                        priorities.sort_unstable();
+                        priorities.dedup();
+                        FilterTerm::Priorities(priorities)
+                    }),
+                "category" if any => Ok(FilterTerm::Category(None)),
+                "category" => EventCategory::from_str(value, true)
+                    .map(|category| FilterTerm::Category(Some(category)))
+                    .map_err(|_| format!("Unknown category '{value}': use WORK, PERSONAL or any.")),
+                other => Err(format!(
+                    "Cannot filter by '{other}': use status, priority or category."
+                )),
+            }
+        })
+        .collect()
+}
+
+/// Splits `title=Write the report priority=P1` into one `KEY=VALUE` entry per field:
+/// words without `=` belong to the value before them.
+fn join_set_entries(words: &[&str]) -> Result<Vec<String>, String> {
+    let mut entries: Vec<String> = Vec::new();
+    for word in words {
+        match entries.last_mut() {
+            Some(entry) if !word.contains('=') => {
+                entry.push(' ');
+                entry.push_str(word);
+            }
+            _ => entries.push(word.to_string()),
+        }
+    }
+    if entries.is_empty() {
+        return Err(":set needs KEY=VALUE, e.g. :set priority=P2".to_string());
+    }
+    Ok(entries)
+}
+
+/// Result of Tab on the command line.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Completion {
+    /// The line with the word under the cursor completed as far as it is unambiguous.
+    pub line: String,
+    /// Every candidate when more than one fits.
+    pub candidates: Vec<String>,
+}
+
+/// Completes the last word of `line` (without the leading `:`).
+pub fn complete(line: &str) -> Completion {
+    let word_start = line.rfind(char::is_whitespace).map_or(0, |index| index + 1);
+    let (head, word) = line.split_at(word_start);
+    let command = head.split_whitespace().next();
+
+    let (value_prefix, options): (&str, Vec<String>) = match command {
+        None => ("", owned(&COMMANDS)),
+        Some("sort") if head.split_whitespace().count() == 1 => ("", owned(&SORT_KEYS)),
+        Some("sort") => ("", owned(&["asc", "desc"])),
+        Some("filter") => match word.split_once('=') {
+            Some(("status", _)) => (
+                "status=",
+                TaskStatusFilter::value_variants()
+                    .iter()
+                    .map(|bucket| bucket.as_str().to_string())
+                    .chain(["any".to_string()])
+                    .collect(),
+            ),
+            Some(("priority", _)) => ("priority=", owned(&["P1", "P2", "P3", "P4", "any"])),
+            Some(("category", _)) => ("category=", owned(&["WORK", "PERSONAL", "any"])),
+            Some(_) => ("", Vec::new()),
+            None => ("", owned(&FILTER_KEYS)),
+        },
+        Some("set") if !word.contains('=') => (
+            "",
+            TASK_FIELDS
+                .iter()
+                .map(|field| format!("{field}="))
+                .collect(),
+        ),
+        Some("unset") => ("", owned(&TASK_FIELDS)),
+        Some(_) => ("", Vec::new()),
+    };
+
+    let typed = &word[value_prefix.len().min(word.len())..];
+    let candidates = options
+        .into_iter()
+        .filter(|option| {
+            option
+                .to_ascii_lowercase()
+                .starts_with(&typed.to_ascii_lowercase())
+        })
+        .collect::<Vec<_>>();
+
+    let completed = match candidates.as_slice() {
+        [] => word.to_string(),
+        [only] if only.ends_with('=') => format!("{value_prefix}{only}"),
+        [only] => format!("{value_prefix}{only} "),
+        many => format!("{value_prefix}{}", common_prefix(many, typed)),
+    };
+    Completion {
+        line: format!("{head}{completed}"),
+        candidates: if candidates.len() > 1 {
+            candidates
+        } else {
+            Vec::new()
+        },
+    }
+}
+
+fn owned(options: &[&str]) -> Vec<String> {
+    options.iter().map(|option| option.to_string()).collect()
+}
+
+/// Longest prefix shared by all candidates, keeping what was typed if they share less.
+fn common_prefix(candidates: &[String], typed: &str) -> String {
+    let first = &candidates[0];
+    let shared = candidates[1..]
+        .iter()
+        .fold(first.len(), |length, candidate| {
+            first
+                .chars()
+                .zip(candidate.chars())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a.len_utf8())
+                .sum::<usize>()
+                .min(length)
+        });
+    if shared > typed.len() {
+        first[..shared].to_string()
+    } else {
+        typed.to_string()
+    }
+}
+
+/// Command lines entered in earlier sessions, oldest first, with Up/Down browsing.
+#[derive(Debug, Default)]
+pub struct CommandHistory {
+    entries: Vec<String>,
+    /// Index into `entries` while browsing with Up/Down.
+    cursor: Option<usize>,
+    /// What was typed before browsing started, restored by Down past the newest entry.
+    draft: String,
+    path: Option<PathBuf>,
+}
+
+impl CommandHistory {
+    /// Loads `$XDG_CACHE_HOME/reclaim/dashboard-history`. A missing or unreadable file
+    /// starts an empty history.
+    pub fn load() -> Self {
+        let path = cache::cache_dir()
+            .ok()
+            .map(|dir| dir.join(HISTORY_FILE_NAME));
+        let entries = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|contents| {
+                contents
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            entries,
+            path,
+            ..Self::default()
+        }
+    }
+
+    /// Adds a command line and writes the history file.
+    pub fn push(&mut self, line: &str) -> Result<(), CliError> {
+        self.cursor = None;
+        self.draft.clear();
+        let line = line.trim();
+        if line.is_empty() || self.entries.last().is_some_and(|last| last == line) {
+            return Ok(());
+        }
+        self.entries.push(line.to_string());
+        if self.entries.len() > HISTORY_LIMIT {
+            self.entries.drain(..self.entries.len() - HISTORY_LIMIT);
+        }
+        self.save()
+    }
+
+    /// Command lines can hold titles and notes, so the file is 0600 like the task cache.
+    fn save(&self) -> Result<(), CliError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut contents = self.entries.join("\n");
+        contents.push('\n');
+        path.parent()
+            .map_or(Ok(()), cache::create_private_dir)
+            .and_then(|()| write_private_file(path, contents.as_bytes()))
+            .map_err(|error| {
+                CliError::Output(format!(
+                    "Could not save command history to {}: {error}",
+                    path.display()
+                ))
+            })
+    }
+
+    /// The entry before the one shown (Up), remembering `current` as the draft.
+    pub fn previous(&mut self, current: &str) -> Option<&str> {
+        let index = match self.cursor {
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(index) => index.saturating_sub(1),
+        };
+        self.cursor = Some(index);
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// The entry after the one shown (Down); past the newest entry, the draft.
+    pub fn next(&mut self) -> Option<&str> {
+        let index = self.cursor?;
+        if index + 1 < self.entries.len() {
+            self.cursor = Some(index + 1);
+            self.entries.get(index + 1).map(String::as_str)
+        } else {
+            self.cursor = None;
+            Some(self.draft.as_str())
+        }
+    }
+
+    /// Stops browsing, e.g. when the line is edited or cancelled.
+    pub fn reset(&mut self) {
+        self.cursor = None;
+        self.draft.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    #[test]
+    fn parse_reads_commands_and_arguments() {
+        assert!(matches!(parse("q"), Ok(ExCommand::Quit)));
+        assert!(matches!(parse(" wq "), Ok(ExCommand::WriteQuit)));
+        assert!(matches!(parse("q!"), Ok(ExCommand::ForceQuit)));
+        assert!(matches!(parse("w"), Ok(ExCommand::Write)));
+        assert!(matches!(
+            parse("sort due desc"),
+            Ok(ExCommand::Sort(Some(SortOrder {
+                key: SortKey::Due,
+                descending: true
+            })))
+        ));
+        assert!(matches!(parse("sort none"), Ok(ExCommand::Sort(None))));
+        assert!(
+            matches!(parse("new Write the report"), Ok(ExCommand::New(title)) if title == "Write the report")
+        );
+        assert!(
+            matches!(parse("snooze tomorrow 9am"), Ok(ExCommand::Snooze(input)) if input == "tomorrow 9am")
+        );
+
+        let Ok(ExCommand::Filter(terms)) = parse("filter priority=p2,P1 status=open category=any")
+        else {
+            panic!("expected a filter");
+        };
+        assert_eq!(
+            terms,
+            [
+                FilterTerm::Priorities(vec!["P1", "P2"]),
+                FilterTerm::Status(Some(TaskStatusFilter::Open)),
+                FilterTerm::Category(None),
+            ]
+        );
+        assert!(matches!(parse("filter"), Ok(ExCommand::Filter(terms)) if terms.is_empty()));
+
+        assert_eq!(
+            parse("sort size").unwrap_err(),
+            "Cannot sort by 'size': use due, priority, title, status, id or none."
+        );
+        assert_eq!(parse("done now").unwrap_err(), ":done takes no arguments.");
+        assert!(parse("filter priority=P5")
+            .unwrap_err()
+            .contains("Unknown priority 'P5'"));
+        assert!(parse("frobnicate")
+            .unwrap_err()
+            .starts_with("Unknown command :frobnicate."));
+    }
+
+    #[test]
+    fn set_uses_field_update_parsing() {
+        let Ok(ExCommand::Set(updates)) = parse("set title=Write the report priority=P1") else {
+            panic!("expected updates");
+        };
+        let mut body = serde_json::Map::new();
+        fields::apply_updates(&mut body, updates, fields::UnsetMode::Null).unwrap();
+        assert_eq!(
+            serde_json::Value::Object(body),
+            serde_json::json!({ "title": "Write the report", "priority": "P1" })
+        );
+
+        let Ok(ExCommand::Set(updates)) = parse("unset notes") else {
+            panic!("expected updates");
+        };
+        let mut body = serde_json::Map::new();
+        fields::apply_updates(&mut body, updates, fields::UnsetMode::Null).unwrap();
+        assert_eq!(
+            serde_json::Value::Object(body),
+            serde_json::json!({ "notes": null })
+        );
+
+        assert!(parse("set =x").is_err());
+        assert!(parse("set priority").is_err());
+    }
+
+    #[test]
+    fn complete_fills_in_commands_keys_and_values() {
+        let complete_line = |line: &str| complete(line).line;
+        assert_eq!(complete_line("so"), "sort ");
+        assert_eq!(complete_line("sort p"), "sort priority ");
+        assert_eq!(complete_line("sort due d"), "sort due desc ");
+        assert_eq!(complete_line("filter pr"), "filter priority=");
+        assert_eq!(
+            complete_line("filter status=in"),
+            "filter status=IN_PROGRESS "
+        );
+        assert_eq!(complete_line("set snooze"), "set snoozeUntil=");
+        assert_eq!(complete_line("unset no"), "unset notes ");
+
+        let ambiguous = complete("s");
+        assert_eq!(ambiguous.line, "s");
+        assert_eq!(
+            ambiguous.candidates,
+            ["set", "snooze", "sort", "start", "stop"]
+        );
+        assert_eq!(complete("st").line, "st");
+        assert_eq!(complete("sta").line, "start ");
+        assert_eq!(complete("set max").line, "set maxChunkSize=");
+        assert_eq!(complete("set m").line, "set m");
+        assert_eq!(
+            complete("set m").candidates,
+            ["minChunkSize=", "maxChunkSize="]
+        );
+    }
+
+    #[test]
+    fn history_browses_and_persists_entries() {
+        let path = temp_dir("history").join("dashboard-history");
+        let mut history = CommandHistory {
+            path: Some(path.clone()),
+            ..CommandHistory::default()
+        };
+        history.push("sort due").unwrap();
+        history.push("done").unwrap();
+        history.push("done").unwrap();
+
+        assert_eq!(history.previous("sn"), Some("done"));
+        assert_eq!(history.previous("ignored"), Some("sort due"));
+        assert_eq!(history.previous("ignored"), Some("sort due"));
+        assert_eq!(history.next(), Some("done"));
+        assert_eq!(history.next(), Some("sn"));
+        assert_eq!(history.next(), None);
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "sort due\ndone\n");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,511 @@
+use crate::error::CliError;
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, LocalResult, NaiveDate, NaiveDateTime,
+    NaiveTime, SecondsFormat, TimeZone, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use std::{fmt, str::FromStr};
+
+/// Hour used for `eod`/`eow` shortcuts.
+const END_OF_DAY_HOUR: u32 = 17;
+
+const TIME_INPUT_HINT: &str = "Use ISO 8601 (2026-02-21T18:30:00Z), local time (2026-02-21 09:30), or relative input like `tomorrow 5pm`, `+2h`, `next friday`, `eod`.";
+
+/// Timezone used to interpret local and relative time input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Zone {
+    Local,
+    Named(Tz),
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Zone::Local => write!(f, "system"),
+            Zone::Named(tz) => write!(f, "{}", tz.name()),
+        }
+    }
+}
+
+impl Zone {
+    pub fn parse(name: &str) -> Result<Self, CliError> {
+        Tz::from_str(name.trim())
+            .map(Zone::Named)
+            .map_err(|_| CliError::InvalidInput {
+                message: format!("Unknown timezone '{}'.", name.trim()),
+                hint: Some(
+                    "Use an IANA timezone name, e.g. America/New_York or Europe/Berlin."
+                        .to_string(),
+                ),
+            })
+    }
+
+    fn offset_at(self, instant: DateTime<Utc>) -> FixedOffset {
+        match self {
+            Zone::Local => instant.with_timezone(&Local).fixed_offset().timezone(),
+            Zone::Named(tz) => instant.with_timezone(&tz).fixed_offset().timezone(),
+        }
+    }
+
+    fn localize(self, naive: NaiveDateTime) -> LocalResult<DateTime<Utc>> {
+        match self {
+            Zone::Local => Local
+                .from_local_datetime(&naive)
+                .map(|value| value.with_timezone(&Utc)),
+            Zone::Named(tz) => tz
+                .from_local_datetime(&naive)
+                .map(|value| value.with_timezone(&Utc)),
+        }
+    }
+}
+
+/// Reference point for parsing user-supplied times: "now" plus the zone that naive and
+/// relative inputs are interpreted in.
+#[derive(Debug, Clone, Copy)]
+pub struct TimeContext {
+    now: DateTime<Utc>,
+    zone: Zone,
+}
+
+impl TimeContext {
+    pub fn new(zone: Zone) -> Self {
+        Self {
+            now: Utc::now(),
+            zone,
+        }
+    }
+
+    /// The same zone with "now" moved to the current time, for long-running sessions.
+    pub fn refreshed(self) -> Self {
+        Self::new(self.zone)
+    }
+
+    #[cfg(test)]
+    pub fn fixed(now: DateTime<Utc>, zone: Zone) -> Self {
+        Self { now, zone }
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        self.now
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.now
+            .with_timezone(&self.zone.offset_at(self.now))
+            .date_naive()
+    }
+
+    /// Calendar date of `instant` in the configured zone.
+    pub fn local_date(&self, instant: DateTime<Utc>) -> NaiveDate {
+        instant
+            .with_timezone(&self.zone.offset_at(instant))
+            .date_naive()
+    }
+
+    /// Wall-clock time of `instant` in the configured zone.
+    pub fn local_datetime(&self, instant: DateTime<Utc>) -> NaiveDateTime {
+        instant
+            .with_timezone(&self.zone.offset_at(instant))
+            .naive_local()
+    }
+
+    /// Parses absolute, local or relative input into a UTC instant.
+    pub fn parse_instant(&self, input: &str) -> Result<DateTime<Utc>, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err("it cannot be empty".to_string());
+        }
+
+        if let Ok(value) = DateTime::parse_from_rfc3339(input) {
+            return Ok(value.with_timezone(&Utc));
+        }
+        if let Ok(value) = DateTime::parse_from_str(input, "%Y-%m-%d %H:%M:%S%:z") {
+            return Ok(value.with_timezone(&Utc));
+        }
+
+        let lowered = input.to_ascii_lowercase();
+        let words = lowered
+            .split_whitespace()
+            .filter(|word| *word != "at")
+            .collect::<Vec<_>>();
+
+        if let Some(offset) = parse_relative_offset(&words)? {
+            return self
+                .now
+                .checked_add_signed(offset)
+                .ok_or_else(|| "it is too far from now".to_string());
+        }
+
+        if let Some(naive) = parse_naive_datetime(input) {
+            return self.localize(naive);
+        }
+
+        if let [single] = words.as_slice() {
+            match *single {
+                "now" => return Ok(self.now),
+                "eod" => return self.upcoming(self.today(), end_of_day_time(), 1),
+                "eow" => {
+                    let today = self.today();
+                    let days_until_friday = (Weekday::Fri.num_days_from_monday() + 7
+                        - today.weekday().num_days_from_monday())
+                        % 7;
+                    let friday = today + Duration::days(i64::from(days_until_friday));
+                    return self.upcoming(friday, end_of_day_time(), 7);
+                }
+                _ => {}
+            }
+        }
+
+        let (date, rest) = self.parse_date_words(&words)?;
+        let time = match rest {
+            [] => None,
+            words => match parse_time_of_day(&words.join("")) {
+                Some(time) => Some(time),
+                None if date.is_some() => {
+                    return Err(format!("'{}' is not a time of day", words.join(" ")));
+                }
+                None => return Err("unrecognized date/time".to_string()),
+            },
+        };
+
+        match (date, time) {
+            (Some(date), time) => self.localize(date.and_time(time.unwrap_or(NaiveTime::MIN))),
+            (None, Some(time)) => self.upcoming(self.today(), time, 1),
+            (None, None) => Err("unrecognized date/time".to_string()),
+        }
+    }
+
+    /// Parses input that names a calendar day (e.g. `2026-02-21`, `today`, `+7d`).
+    pub fn parse_date(&self, input: &str) -> Result<NaiveDate, String> {
+        let trimmed = input.trim();
+        if let Ok(date) = NaiveDate::parse_from_str(trimmed, "%Y-%m-%d") {
+            return Ok(date);
+        }
+
+        self.parse_instant(trimmed)
+            .map(|instant| self.local_date(instant))
+    }
+
+    /// Splits leading date words (`tomorrow`, `next friday`, `2026-02-21`) from the rest.
+    fn parse_date_words<'a>(
+        &self,
+        words: &'a [&'a str],
+    ) -> Result<(Option<NaiveDate>, &'a [&'a str]), String> {
+        let today = self.today();
+
+        match words {
+            [] => Ok((None, words)),
+            ["today", rest @ ..] => Ok((Some(today), rest)),
+            ["tomorrow", rest @ ..] => Ok((Some(today + Duration::days(1)), rest)),
+            ["yesterday", rest @ ..] => Ok((Some(today - Duration::days(1)), rest)),
+            ["next", day, rest @ ..] | ["this", day, rest @ ..] => {
+                let weekday = parse_weekday(day)
+                    .ok_or_else(|| format!("'{day}' is not a day of the week"))?;
+                Ok((Some(next_weekday(today, weekday)), rest))
+            }
+            [first, rest @ ..] => {
+                if let Some(weekday) = parse_weekday(first) {
+                    return Ok((Some(next_weekday(today, weekday)), rest));
+                }
+                if let Ok(date) = NaiveDate::parse_from_str(first, "%Y-%m-%d") {
+                    return Ok((Some(date), rest));
+                }
+                Ok((None, words))
+            }
+        }
+    }
+
+    /// `time` on `date`, or `step_days` later if that has already passed, so `eod` and `9am`
+    /// never name a moment in the past.
+    fn upcoming(
+        &self,
+        date: NaiveDate,
+        time: NaiveTime,
+        step_days: i64,
+    ) -> Result<DateTime<Utc>, String> {
+        let instant = self.localize(date.and_time(time))?;
+        if instant >= self.now {
+            return Ok(instant);
+        }
+        self.localize((date + Duration::days(step_days)).and_time(time))
+    }
+
+    fn localize(&self, naive: NaiveDateTime) -> Result<DateTime<Utc>, String> {
+        match self.zone.localize(naive) {
+            LocalResult::Single(value) => Ok(value),
+            LocalResult::Ambiguous(earliest, _) => Ok(earliest),
+            LocalResult::None => Err(format!(
+                "{} does not exist in this timezone (daylight saving gap)",
+                naive.format("%Y-%m-%d %H:%M")
+            )),
+        }
+    }
+}
+
+/// Validates a time flag and normalizes it to the RFC 3339 UTC form the API expects.
+pub fn normalize_timestamp(
+    context: &TimeContext,
+    flag_name: &str,
+    raw: &str,
+) -> Result<String, CliError> {
+    context
+        .parse_instant(raw)
+        .map(format_timestamp)
+        .map_err(|reason| invalid_time_error(flag_name, raw, &reason))
+}
+
+/// Validates a date flag and normalizes it to `YYYY-MM-DD`.
+pub fn normalize_date(
+    context: &TimeContext,
+    flag_name: &str,
+    raw: &str,
+) -> Result<String, CliError> {
+    context
+        .parse_date(raw)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .map_err(|reason| invalid_time_error(flag_name, raw, &reason))
+}
+
+pub fn format_timestamp(instant: DateTime<Utc>) -> String {
+    instant.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+pub fn invalid_time_error(flag_name: &str, raw: &str, reason: &str) -> CliError {
+    CliError::InvalidInput {
+        message: format!("Invalid {flag_name} value '{}': {reason}.", raw.trim()),
+        hint: Some(TIME_INPUT_HINT.to_string()),
+    }
+}
+
+fn end_of_day_time() -> NaiveTime {
+    NaiveTime::from_hms_opt(END_OF_DAY_HOUR, 0, 0).expect("valid end-of-day time")
+}
+
+fn parse_naive_datetime(input: &str) -> Option<NaiveDateTime> {
+    [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%dT%H:%M:%S%.f",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(input, format).ok())
+}
+
+/// Parses `+2h`, `-15m`, `+1h30m`, `in 3 days` and similar offsets.
+fn parse_relative_offset(words: &[&str]) -> Result<Option<Duration>, String> {
+    let (sign, body) = match words {
+        ["in", rest @ ..] if !rest.is_empty() => (1i64, rest.join("")),
+        [first, rest @ ..] if first.starts_with('+') || first.starts_with('-') => {
+            let sign = if first.starts_with('-') { -1 } else { 1 };
+            let mut body = first[1..].to_string();
+            for word in rest {
+                body.push_str(word);
+            }
+            (sign, body)
+        }
+        _ => return Ok(None),
+    };
+
+    let minutes = parse_offset_minutes(&body)
+        .ok_or_else(|| format!("'{body}' is not a relative offset like 2h, 30m or 3d"))?;
+    sign.checked_mul(minutes)
+        .and_then(Duration::try_minutes)
+        .map(Some)
+        .ok_or_else(|| "it is too far from now".to_string())
+}
+
+fn parse_offset_minutes(input: &str) -> Option<i64> {
+    let mut total = 0i64;
+    let mut rest = input;
+    if rest.is_empty() {
+        return None;
+    }
+
+    while !rest.is_empty() {
+        let digits = rest.chars().take_while(char::is_ascii_digit).count();
+        if digits == 0 {
+            return None;
+        }
+        let amount = rest[..digits].parse::<i64>().ok()?;
+        rest = &rest[digits..];
+
+        let unit_len = rest.chars().take_while(char::is_ascii_alphabetic).count();
+        let unit = &rest[..unit_len];
+        rest = &rest[unit_len..];
+
+        let factor = match unit {
+            "m" | "min" | "mins" | "minute" | "minutes" => 1,
+            "h" | "hr" | "hrs" | "hour" | "hours" => 60,
+            "d" | "day" | "days" => 60 * 24,
+            "w" | "wk" | "wks" | "week" | "weeks" => 60 * 24 * 7,
+            _ => return None,
+        };
+        total = total.checked_add(amount.checked_mul(factor)?)?;
+    }
+
+    Some(total)
+}
+
+fn parse_time_of_day(input: &str) -> Option<NaiveTime> {
+    match input {
+        "noon" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" => return Some(NaiveTime::MIN),
+        "eod" => return Some(end_of_day_time()),
+        _ => {}
+    }
+
+    let (clock, meridiem) = if let Some(clock) = input.strip_suffix("am") {
+        (clock, Some(false))
+    } else if let Some(clock) = input.strip_suffix("pm") {
+        (clock, Some(true))
+    } else {
+        (input, None)
+    };
+
+    let mut parts = clock.split(':');
+    let hour = parts.next()?.parse::<u32>().ok()?;
+    let minute = parts.next().map(str::parse::<u32>).unwrap_or(Ok(0)).ok()?;
+    let second = parts.next().map(str::parse::<u32>).unwrap_or(Ok(0)).ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let hour = match meridiem {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some(false) => hour % 12,
+        Some(true) => hour % 12 + 12,
+        None if clock.contains(':') => hour,
+        // A bare number like "5" is too ambiguous to treat as a time.
+        None => return None,
+    };
+
+    NaiveTime::from_hms_opt(hour, minute, second)
+}
+
+fn parse_weekday(input: &str) -> Option<Weekday> {
+    match input {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thur" | "thurs" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// First `weekday` strictly after `today`.
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+    let ahead = if ahead == 0 { 7 } else { ahead };
+    today + Duration::days(i64::from(ahead))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_time;
+
+    fn parse(input: &str) -> String {
+        format_timestamp(test_time().parse_instant(input).unwrap())
+    }
+
+    #[test]
+    fn absolute_timestamps_are_normalized_to_utc() {
+        assert_eq!(parse("2026-02-21T18:30:00Z"), "2026-02-21T18:30:00Z");
+        assert_eq!(parse("2026-02-21T18:30:00+02:00"), "2026-02-21T16:30:00Z");
+        assert_eq!(parse("2026-02-21T18:30:00.250Z"), "2026-02-21T18:30:00Z");
+    }
+
+    #[test]
+    fn local_inputs_use_the_configured_zone() {
+        assert_eq!(parse("2026-02-21T09:30"), "2026-02-21T14:30:00Z");
+        assert_eq!(parse("2026-02-21 9:30am"), "2026-02-21T14:30:00Z");
+        assert_eq!(parse("2026-02-21"), "2026-02-21T05:00:00Z");
+        assert_eq!(parse("5pm"), "2026-02-18T22:00:00Z");
+    }
+
+    #[test]
+    fn relative_inputs_are_resolved_against_now() {
+        assert_eq!(parse("now"), "2026-02-18T15:00:00Z");
+        assert_eq!(parse("+2h"), "2026-02-18T17:00:00Z");
+        assert_eq!(parse("+1h30m"), "2026-02-18T16:30:00Z");
+        assert_eq!(parse("-1d"), "2026-02-17T15:00:00Z");
+        assert_eq!(parse("in 3 days"), "2026-02-21T15:00:00Z");
+        assert_eq!(parse("tomorrow 5pm"), "2026-02-19T22:00:00Z");
+        assert_eq!(parse("tomorrow at 17:00"), "2026-02-19T22:00:00Z");
+        assert_eq!(parse("next friday"), "2026-02-20T05:00:00Z");
+        assert_eq!(parse("wed noon"), "2026-02-25T17:00:00Z");
+        assert_eq!(parse("eod"), "2026-02-18T22:00:00Z");
+        assert_eq!(parse("EOW"), "2026-02-20T22:00:00Z");
+    }
+
+    #[test]
+    fn times_of_day_that_have_passed_roll_forward() {
+        // 18:30 on Wednesday in New York, after the end of the working day.
+        let evening = TimeContext::fixed(
+            "2026-02-18T23:30:00Z".parse().unwrap(),
+            Zone::parse("America/New_York").unwrap(),
+        );
+        let parse = |input| format_timestamp(evening.parse_instant(input).unwrap());
+        assert_eq!(parse("eod"), "2026-02-19T22:00:00Z");
+        assert_eq!(parse("noon"), "2026-02-19T17:00:00Z");
+        assert_eq!(parse("9am"), "2026-02-19T14:00:00Z");
+        assert_eq!(parse("11pm"), "2026-02-19T04:00:00Z");
+        assert_eq!(parse("eow"), "2026-02-20T22:00:00Z");
+        assert_eq!(parse("today 9am"), "2026-02-18T14:00:00Z");
+
+        // 18:30 on Friday: the end of this week has passed too.
+        let friday = TimeContext::fixed(
+            "2026-02-20T23:30:00Z".parse().unwrap(),
+            Zone::parse("America/New_York").unwrap(),
+        );
+        assert_eq!(
+            format_timestamp(friday.parse_instant("eow").unwrap()),
+            "2026-02-27T22:00:00Z"
+        );
+    }
+
+    #[test]
+    fn offsets_too_far_from_now_are_rejected() {
+        let error = normalize_timestamp(&test_time(), "--due", "+9999999999w").unwrap_err();
+        assert!(matches!(error, CliError::InvalidInput { .. }));
+        assert!(error.to_string().contains("too far from now"));
+        assert!(test_time().parse_instant("-9999999999w").is_err());
+        assert!(test_time().parse_instant("+99999999999999999w").is_err());
+    }
+
+    #[test]
+    fn daylight_saving_gaps_are_rejected() {
+        let error = test_time().parse_instant("2026-03-08T02:30").unwrap_err();
+        assert!(error.contains("daylight saving gap"));
+    }
+
+    #[test]
+    fn invalid_inputs_produce_actionable_errors() {
+        let error = normalize_timestamp(&test_time(), "--due", "tmorrow").unwrap_err();
+        assert!(error.to_string().contains("Invalid --due value 'tmorrow'"));
+        assert!(error.hint().unwrap().contains("tomorrow 5pm"));
+
+        assert!(test_time().parse_instant("tomorrow 25pm").is_err());
+        assert!(test_time().parse_instant("+2 parsecs").is_err());
+    }
+
+    #[test]
+    fn dates_accept_relative_input() {
+        let context = test_time();
+        assert_eq!(
+            normalize_date(&context, "--start", "2026-02-01").unwrap(),
+            "2026-02-01"
+        );
+        assert_eq!(
+            normalize_date(&context, "--start", "+7d").unwrap(),
+            "2026-02-25"
+        );
+        assert_eq!(
+            normalize_date(&context, "--end", "tomorrow").unwrap(),
+            "2026-02-19"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// One field that differs between two versions of a document, by dot path.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+/// Fields that differ between `before` and `after`. Nested objects are compared key by key
+/// (`assist.type`); arrays and scalars as a whole. A missing field counts as `null`.
+/// Fields are listed in key order.
+pub fn diff_fields(before: &Value, after: &Value) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    collect_changes("", before, after, &mut changes);
+    changes
+}
+
+fn collect_changes(path: &str, before: &Value, after: &Value, changes: &mut Vec<FieldChange>) {
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => {
+            let keys = after.keys().chain(before.keys()).collect::<BTreeSet<_>>();
+            for key in keys {
+                let field = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                collect_changes(
+                    &field,
+                    before.get(key).unwrap_or(&Value::Null),
+                    after.get(key).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+        (before, after) if before != after => changes.push(FieldChange {
+            field: path.to_string(),
+            before: before.clone(),
+            after: after.clone(),
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_reports_changed_added_and_removed_fields_by_path() {
+        let before = json!({
+            "id": 7,
+            "title": "Ship it",
+            "priority": "P2",
+            "notes": "draft",
+            "assist": { "type": "TASK", "locked": false },
+            "tags": ["a"]
+        });
+        let after = json!({
+            "id": 7,
+            "title": "Ship it",
+            "priority": "P1",
+            "assist": { "type": "TASK", "locked": true },
+            "tags": ["a", "b"],
+            "due": "2026-02-20T22:00:00Z"
+        });
+
+        let changes = diff_fields(&before, &after)
+            .into_iter()
+            .map(|change| (change.field, change.before, change.after))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            [
+                ("assist.locked".to_string(), json!(false), json!(true)),
+                (
+                    "due".to_string(),
+                    Value::Null,
+                    json!("2026-02-20T22:00:00Z")
+                ),
+                ("notes".to_string(), json!("draft"), Value::Null),
+                ("priority".to_string(), json!("P2"), json!("P1")),
+                ("tags".to_string(), json!(["a"]), json!(["a", "b"])),
+            ]
+        );
+        assert!(diff_fields(&before, &before).is_empty());
+    }
+}
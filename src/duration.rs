@@ -0,0 +1,251 @@
+use crate::{cli::RoundMode, error::CliError};
+
+/// Reclaim schedules task time in 15-minute chunks.
+pub const CHUNK_MINUTES: u32 = 15;
+
+const DURATION_INPUT_HINT: &str =
+    "Use hours and minutes, e.g. 45m, 1h, 2h30m or 1.5h. Values are scheduled in 15-minute chunks.";
+
+/// Chunk counts sent with a new task, after defaults and validation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChunkPlan {
+    pub time_chunks_required: Option<u32>,
+    pub min_chunk_size: Option<u32>,
+    pub max_chunk_size: Option<u32>,
+}
+
+/// Parses `2h30m`, `90m`, `1.5h` or `1h 15m` into whole minutes.
+pub fn parse_minutes(input: &str) -> Result<u32, String> {
+    let compact = input
+        .split_whitespace()
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if compact.is_empty() {
+        return Err("it cannot be empty".to_string());
+    }
+
+    let mut total = 0f64;
+    let mut rest = compact.as_str();
+    while !rest.is_empty() {
+        let number_len = rest
+            .chars()
+            .take_while(|ch| ch.is_ascii_digit() || *ch == '.')
+            .count();
+        let amount = rest[..number_len]
+            .parse::<f64>()
+            .map_err(|_| format!("'{}' is not a duration", input.trim()))?;
+        rest = &rest[number_len..];
+
+        let unit_len = rest.chars().take_while(char::is_ascii_alphabetic).count();
+        let factor = match &rest[..unit_len] {
+            "h" | "hr" | "hrs" | "hour" | "hours" => 60.0,
+            "m" | "min" | "mins" | "minute" | "minutes" => 1.0,
+            "" => return Err(format!("'{}' is missing a unit (h or m)", input.trim())),
+            unit => return Err(format!("unknown duration unit '{unit}'")),
+        };
+        rest = &rest[unit_len..];
+        total += amount * factor;
+    }
+
+    // Drop float noise so inputs like 0.1h land on whole minutes.
+    let total = (total * 1e6).round() / 1e6;
+    if total.fract() != 0.0 {
+        return Err(format!(
+            "'{}' is not a whole number of minutes",
+            input.trim()
+        ));
+    }
+    if total > f64::from(u32::MAX) {
+        return Err(format!("'{}' is too long", input.trim()));
+    }
+
+    Ok(total as u32)
+}
+
+/// Converts minutes to 15-minute chunks. `RoundMode::Strict` rejects unaligned values.
+pub fn minutes_to_chunks(minutes: u32, round: RoundMode) -> Result<u32, String> {
+    let whole = minutes / CHUNK_MINUTES;
+    let remainder = minutes % CHUNK_MINUTES;
+
+    let chunks = match round {
+        _ if remainder == 0 => whole,
+        RoundMode::Strict => {
+            return Err(format!(
+                "{} is not a multiple of 15 minutes (nearest: {} or {})",
+                format_minutes(minutes),
+                format_minutes(whole * CHUNK_MINUTES),
+                format_minutes((whole + 1) * CHUNK_MINUTES)
+            ));
+        }
+        RoundMode::Up => whole + 1,
+        RoundMode::Down => whole,
+        RoundMode::Nearest if remainder * 2 >= CHUNK_MINUTES => whole + 1,
+        RoundMode::Nearest => whole,
+    };
+
+    if chunks == 0 {
+        return Err(format!(
+            "{} rounds to zero; the minimum is 15m",
+            format_minutes(minutes)
+        ));
+    }
+
+    Ok(chunks)
+}
+
+/// Parses a duration flag and converts it to chunks, reporting failures against `flag_name`.
+pub fn parse_chunks(flag_name: &str, raw: &str, round: RoundMode) -> Result<u32, CliError> {
+    let invalid = |reason: String, hint: &str| CliError::InvalidInput {
+        message: format!("Invalid {flag_name} value '{}': {reason}.", raw.trim()),
+        hint: Some(hint.to_string()),
+    };
+
+    let minutes = parse_minutes(raw).map_err(|reason| invalid(reason, DURATION_INPUT_HINT))?;
+    minutes_to_chunks(minutes, round).map_err(|reason| {
+        invalid(
+            reason,
+            "Pass a multiple of 15 minutes, or choose rounding with --round up|down|nearest.",
+        )
+    })
+}
+
+/// Applies defaults to chunk options and checks that they are consistent.
+///
+/// Chunk sizes need a total; when one is given, the minimum defaults to one chunk and the
+/// maximum to the total.
+pub fn plan_chunks(
+    total: Option<u32>,
+    min: Option<u32>,
+    max: Option<u32>,
+) -> Result<ChunkPlan, CliError> {
+    let Some(total) = total else {
+        if min.is_some() || max.is_some() {
+            return Err(CliError::InvalidInput {
+                message: "Invalid chunk options: chunk sizes require a total (--duration or --time-chunks-required)."
+                    .to_string(),
+                hint: Some(
+                    "Pass a total with chunk sizes, e.g. --duration 1h --min-chunk 30m --max-chunk 1h"
+                        .to_string(),
+                ),
+            });
+        }
+        return Ok(ChunkPlan::default());
+    };
+
+    let min = min.unwrap_or(1);
+    let max = max.unwrap_or(total);
+
+    if min > total {
+        return Err(CliError::InvalidInput {
+            message: format!(
+                "Invalid minimum chunk: {} exceeds the total duration ({}).",
+                format_chunks(min),
+                format_chunks(total)
+            ),
+            hint: Some("Use a minimum chunk no longer than --duration.".to_string()),
+        });
+    }
+    if max > total {
+        return Err(CliError::InvalidInput {
+            message: format!(
+                "Invalid maximum chunk: {} exceeds the total duration ({}).",
+                format_chunks(max),
+                format_chunks(total)
+            ),
+            hint: Some("Use a maximum chunk no longer than --duration.".to_string()),
+        });
+    }
+    if min > max {
+        return Err(CliError::InvalidInput {
+            message: format!(
+                "Invalid chunk bounds: minimum ({}) cannot exceed maximum ({}).",
+                format_chunks(min),
+                format_chunks(max)
+            ),
+            hint: Some("Choose chunk sizes where min <= max.".to_string()),
+        });
+    }
+
+    Ok(ChunkPlan {
+        time_chunks_required: Some(total),
+        min_chunk_size: Some(min),
+        max_chunk_size: Some(max),
+    })
+}
+
+/// Formats minutes as `45m`, `2h` or `2h30m`.
+pub fn format_minutes(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{minutes}m"),
+        (hours, 0) => format!("{hours}h"),
+        (hours, minutes) => format!("{hours}h{minutes}m"),
+    }
+}
+
+pub fn format_chunks(chunks: u32) -> String {
+    format_minutes(chunks.saturating_mul(CHUNK_MINUTES))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_minutes_accepts_common_forms() {
+        assert_eq!(parse_minutes("45m"), Ok(45));
+        assert_eq!(parse_minutes("2h30m"), Ok(150));
+        assert_eq!(parse_minutes("2h 30m"), Ok(150));
+        assert_eq!(parse_minutes("1.5h"), Ok(90));
+        assert_eq!(parse_minutes("90 minutes"), Ok(90));
+        assert!(parse_minutes("90").unwrap_err().contains("missing a unit"));
+        assert!(parse_minutes("2d")
+            .unwrap_err()
+            .contains("unknown duration unit"));
+        assert_eq!(parse_minutes("0.1h"), Ok(6));
+        assert!(parse_minutes("0.01h").is_err());
+    }
+
+    #[test]
+    fn minutes_to_chunks_applies_rounding_rules() {
+        assert_eq!(minutes_to_chunks(150, RoundMode::Strict), Ok(10));
+        assert!(minutes_to_chunks(50, RoundMode::Strict)
+            .unwrap_err()
+            .contains("nearest: 45m or 1h"));
+        assert_eq!(minutes_to_chunks(50, RoundMode::Up), Ok(4));
+        assert_eq!(minutes_to_chunks(50, RoundMode::Down), Ok(3));
+        assert_eq!(minutes_to_chunks(50, RoundMode::Nearest), Ok(3));
+        assert_eq!(minutes_to_chunks(53, RoundMode::Nearest), Ok(4));
+        assert!(minutes_to_chunks(10, RoundMode::Down)
+            .unwrap_err()
+            .contains("rounds to zero"));
+    }
+
+    #[test]
+    fn plan_chunks_fills_defaults_and_checks_bounds() {
+        assert_eq!(
+            plan_chunks(Some(8), None, None).unwrap(),
+            ChunkPlan {
+                time_chunks_required: Some(8),
+                min_chunk_size: Some(1),
+                max_chunk_size: Some(8),
+            }
+        );
+        assert_eq!(plan_chunks(None, None, None).unwrap(), ChunkPlan::default());
+
+        let error = plan_chunks(None, Some(2), None).unwrap_err();
+        assert!(error.to_string().contains("require a total"));
+        let error = plan_chunks(Some(4), Some(6), None).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("1h30m exceeds the total duration (1h)"));
+        let error = plan_chunks(Some(8), Some(4), Some(2)).unwrap_err();
+        assert!(error.to_string().contains("cannot exceed maximum"));
+    }
+
+    #[test]
+    fn format_minutes_uses_compact_units() {
+        assert_eq!(format_minutes(45), "45m");
+        assert_eq!(format_minutes(120), "2h");
+        assert_eq!(format_chunks(10), "2h30m");
+    }
+}
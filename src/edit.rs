@@ -0,0 +1,599 @@
+use crate::{
+    cli::{EventCategory, Priority, RoundMode},
+    datetime::{self, TimeContext},
+    duration,
+    error::CliError,
+    reclaim_api::Task,
+};
+use clap::ValueEnum;
+use serde_json::{Map, Value};
+use std::{
+    collections::hash_map::RandomState,
+    env, fs,
+    hash::{BuildHasher, Hasher},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+/// Marks lines of the error block written above the fields after an invalid edit.
+const ERROR_PREFIX: &str = "# error: ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    Text,
+    Priority,
+    Category,
+    Timestamp,
+    Chunks,
+    Bool,
+}
+
+/// One editable task field: its key in the edited file and in the task JSON.
+struct Field {
+    key: &'static str,
+    api_field: &'static str,
+    kind: FieldKind,
+    clearable: bool,
+    help: &'static str,
+}
+
+const FIELDS: [Field; 11] = [
+    Field {
+        key: "title",
+        api_field: "title",
+        kind: FieldKind::Text,
+        clearable: false,
+        help: "Task title.",
+    },
+    Field {
+        key: "priority",
+        api_field: "priority",
+        kind: FieldKind::Priority,
+        clearable: false,
+        help: "P1 (highest), P2, P3 or P4.",
+    },
+    Field {
+        key: "eventCategory",
+        api_field: "eventCategory",
+        kind: FieldKind::Category,
+        clearable: false,
+        help: "WORK or PERSONAL.",
+    },
+    Field {
+        key: "due",
+        api_field: "due",
+        kind: FieldKind::Timestamp,
+        clearable: true,
+        help: "Due date, e.g. \"2026-03-06T17:00:00Z\", \"friday 5pm\" or \"+2d\". Delete the line to clear it.",
+    },
+    Field {
+        key: "snoozeUntil",
+        api_field: "snoozeUntil",
+        kind: FieldKind::Timestamp,
+        clearable: true,
+        help: "Do not schedule before this time. Same formats as due. Delete the line to clear it.",
+    },
+    Field {
+        key: "startAfter",
+        api_field: "startAfter",
+        kind: FieldKind::Timestamp,
+        clearable: true,
+        help: "Earliest start. Same formats as due. Delete the line to clear it.",
+    },
+    Field {
+        key: "duration",
+        api_field: "timeChunksRequired",
+        kind: FieldKind::Chunks,
+        clearable: false,
+        help: "Total time, e.g. \"45m\", \"2h\" or \"2h30m\". Multiples of 15 minutes.",
+    },
+    Field {
+        key: "minChunk",
+        api_field: "minChunkSize",
+        kind: FieldKind::Chunks,
+        clearable: false,
+        help: "Shortest block to schedule. Multiples of 15 minutes.",
+    },
+    Field {
+        key: "maxChunk",
+        api_field: "maxChunkSize",
+        kind: FieldKind::Chunks,
+        clearable: false,
+        help: "Longest block to schedule. Multiples of 15 minutes.",
+    },
+    Field {
+        key: "alwaysPrivate",
+        api_field: "alwaysPrivate",
+        kind: FieldKind::Bool,
+        clearable: false,
+        help: "true to hide the title of scheduled events from others.",
+    },
+    Field {
+        key: "notes",
+        api_field: "notes",
+        kind: FieldKind::Text,
+        clearable: true,
+        help: "Notes. Use \"\"\"...\"\"\" for several lines. Delete the line to clear them.",
+    },
+];
+
+/// An edit that cannot be sent, with the line it refers to when known.
+#[derive(Debug, PartialEq, Eq)]
+struct EditError {
+    location: Option<(usize, usize)>,
+    message: String,
+}
+
+impl EditError {
+    fn new(message: String) -> Self {
+        Self {
+            location: None,
+            message,
+        }
+    }
+}
+
+/// Opens `task` in the user's editor until the edit is valid, and returns the changed
+/// fields as a PATCH body. `None` when nothing changed or the file was emptied.
+pub fn edit_task(task: &Task, time: &TimeContext) -> Result<Option<Map<String, Value>>, CliError> {
+    let original = match serde_json::to_value(task) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new(),
+    };
+    let file = TempFile::create(task.id)?;
+    let initial = render_document(task.id, &task.title, &original);
+    let mut document = initial.clone();
+
+    loop {
+        file.write(&document)?;
+        run_editor(file.path())?;
+        let saved = fs::read_to_string(file.path()).map_err(|error| file.error("read", error))?;
+        let content = strip_error_block(&saved);
+        if content == initial || is_blank(&content) {
+            return Ok(None);
+        }
+
+        match parse_edit(&content, &original, time) {
+            Ok(patch) if patch.is_empty() => return Ok(None),
+            Ok(patch) => return Ok(Some(patch)),
+            // Saved without touching the file after an error: give up like `git commit`.
+            Err(error) if saved == document => {
+                return Err(CliError::InvalidInput {
+                    message: format!("Invalid edit: {}", error.message.trim_end_matches('.')),
+                    hint: Some(format!(
+                        "Task #{} was not changed. Run `reclaim edit {}` to try again.",
+                        task.id, task.id
+                    )),
+                });
+            }
+            Err(error) => document = with_error_block(&error, &content),
+        }
+    }
+}
+
+/// The editable fields as commented TOML. Fields the task does not have are commented
+/// out so they can be filled in.
+fn render_document(task_id: u64, title: &str, task: &Map<String, Value>) -> String {
+    let mut document = format!(
+        "# Editing task #{task_id}: {title}\n\
+         # Save and close the editor to apply the changes. Lines starting with '#' are ignored.\n\
+         # Leave the file unchanged, or delete everything, to cancel.\n"
+    );
+
+    for field in &FIELDS {
+        document.push('\n');
+        document.push_str(&format!("# {}\n", field.help));
+        match task
+            .get(field.api_field)
+            .filter(|value| !value.is_null())
+            .and_then(|value| render_value(field.kind, value))
+        {
+            Some(rendered) => document.push_str(&format!("{} = {rendered}\n", field.key)),
+            None => document.push_str(&format!("# {} = {}\n", field.key, placeholder(field.kind))),
+        }
+    }
+
+    document
+}
+
+fn render_value(kind: FieldKind, value: &Value) -> Option<String> {
+    match kind {
+        FieldKind::Bool => value.as_bool().map(|flag| flag.to_string()),
+        FieldKind::Chunks => value
+            .as_u64()
+            .and_then(|chunks| u32::try_from(chunks).ok())
+            .map(|chunks| toml_string(&duration::format_chunks(chunks))),
+        _ => value.as_str().map(toml_string),
+    }
+}
+
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+fn placeholder(kind: FieldKind) -> &'static str {
+    match kind {
+        FieldKind::Text | FieldKind::Timestamp => "\"\"",
+        FieldKind::Priority => "\"P3\"",
+        FieldKind::Category => "\"WORK\"",
+        FieldKind::Chunks => "\"1h\"",
+        FieldKind::Bool => "false",
+    }
+}
+
+/// Compares the edited file with `task` and returns only the fields that changed.
+fn parse_edit(
+    content: &str,
+    task: &Map<String, Value>,
+    time: &TimeContext,
+) -> Result<Map<String, Value>, EditError> {
+    let table = content.parse::<toml::Table>().map_err(|error| {
+        let location = error
+            .span()
+            .map(|span| line_and_column(content, span.start));
+        EditError {
+            location,
+            message: error.message().replace('\n', " "),
+        }
+    })?;
+
+    if let Some(unknown) = table
+        .keys()
+        .find(|key| !FIELDS.iter().any(|field| field.key == key.as_str()))
+    {
+        let known = FIELDS.map(|field| field.key).join(", ");
+        return Err(EditError::new(format!(
+            "Unknown field '{unknown}'. Editable fields: {known}."
+        )));
+    }
+
+    let mut patch = Map::new();
+    for field in &FIELDS {
+        let original = task.get(field.api_field).cloned().unwrap_or(Value::Null);
+        let edited = match table.get(field.key) {
+            Some(value) => parse_value(field, value, &original, time)?,
+            None if field.clearable || original.is_null() => Value::Null,
+            None => {
+                return Err(EditError::new(format!(
+                    "{} cannot be removed. Put the line back to keep the current value.",
+                    field.key
+                )));
+            }
+        };
+        if edited != original {
+            patch.insert(field.api_field.to_string(), edited);
+        }
+    }
+
+    Ok(patch)
+}
+
+fn parse_value(
+    field: &Field,
+    value: &toml::Value,
+    original: &Value,
+    time: &TimeContext,
+) -> Result<Value, EditError> {
+    let invalid = |reason: String| EditError::new(format!("Invalid {}: {reason}.", field.key));
+
+    if field.kind == FieldKind::Bool {
+        return value
+            .as_bool()
+            .map(Value::Bool)
+            .ok_or_else(|| invalid("use true or false".to_string()));
+    }
+
+    // Unquoted TOML dates are fine for timestamps; everything else must be a string.
+    let raw = match value {
+        toml::Value::String(raw) => raw.clone(),
+        toml::Value::Datetime(raw) if field.kind == FieldKind::Timestamp => raw.to_string(),
+        _ => return Err(invalid("put the value in double quotes".to_string())),
+    };
+    let invalid_value = |reason: String| {
+        EditError::new(format!("Invalid {} '{}': {reason}.", field.key, raw.trim()))
+    };
+
+    match field.kind {
+        FieldKind::Text if raw.trim().is_empty() && !field.clearable => {
+            Err(invalid("it cannot be empty".to_string()))
+        }
+        FieldKind::Text if raw.is_empty() => Ok(Value::Null),
+        FieldKind::Text => Ok(Value::String(raw)),
+        FieldKind::Priority => choice::<Priority>(&raw, Priority::as_str).map_err(invalid_value),
+        FieldKind::Category => {
+            choice::<EventCategory>(&raw, EventCategory::as_str).map_err(invalid_value)
+        }
+        FieldKind::Timestamp if raw.trim().is_empty() => Ok(Value::Null),
+        // Unchanged text keeps its exact value instead of being renormalized.
+        FieldKind::Timestamp if original.as_str() == Some(raw.as_str()) => Ok(original.clone()),
+        FieldKind::Timestamp => time
+            .parse_instant(&raw)
+            .map(|instant| Value::String(datetime::format_timestamp(instant)))
+            .map_err(invalid_value),
+        FieldKind::Chunks => duration::parse_minutes(&raw)
+            .and_then(|minutes| duration::minutes_to_chunks(minutes, RoundMode::Strict))
+            .map(Value::from)
+            .map_err(invalid_value),
+        FieldKind::Bool => unreachable!("handled above"),
+    }
+}
+
+fn choice<T: ValueEnum>(raw: &str, as_str: fn(T) -> &'static str) -> Result<Value, String> {
+    let parsed = T::from_str(raw.trim(), true).map_err(|_| {
+        let allowed = T::value_variants()
+            .iter()
+            .map(|variant| as_str(variant.clone()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("use one of {allowed}")
+    })?;
+
+    Ok(Value::String(as_str(parsed).to_string()))
+}
+
+/// 1-based line and column of a byte offset.
+fn line_and_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Puts the error above the edited content. Line numbers are shifted by the block's own
+/// length so they match what the editor shows.
+fn with_error_block(error: &EditError, content: &str) -> String {
+    let mut lines = Vec::new();
+    match error.location {
+        Some((line, column)) => lines.push(format!(
+            "line {}, column {column}: {}",
+            line + 3,
+            error.message
+        )),
+        None => lines.push(error.message.clone()),
+    }
+    lines.push("Fix the file and save again, or delete everything to cancel.".to_string());
+
+    let mut document = lines
+        .iter()
+        .map(|line| format!("{ERROR_PREFIX}{line}\n"))
+        .collect::<String>();
+    document.push('\n');
+    document.push_str(content);
+    document
+}
+
+fn strip_error_block(saved: &str) -> String {
+    let mut lines = saved.lines().peekable();
+    let mut stripped = false;
+    while lines
+        .peek()
+        .is_some_and(|line| line.starts_with(ERROR_PREFIX))
+    {
+        lines.next();
+        stripped = true;
+    }
+    if stripped && lines.peek().is_some_and(|line| line.is_empty()) {
+        lines.next();
+    }
+
+    let mut content = lines.collect::<Vec<_>>().join("\n");
+    if saved.ends_with('\n') {
+        content.push('\n');
+    }
+    content
+}
+
+fn is_blank(content: &str) -> bool {
+    content
+        .lines()
+        .map(str::trim)
+        .all(|line| line.is_empty() || line.starts_with('#'))
+}
+
+/// Runs `$VISUAL`, then `$EDITOR`, then `vi` on `path` and waits for it to exit. The
+/// variable may include arguments, e.g. `code --wait`.
+fn run_editor(path: &Path) -> Result<(), CliError> {
+    let editor = ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+
+    let status = process::Command::new(program)
+        .args(words)
+        .arg(path)
+        .status()
+        .map_err(|error| CliError::InvalidInput {
+            message: format!("Could not start the editor '{editor}': {error}"),
+            hint: Some("Set EDITOR to an installed editor, e.g. EDITOR=nano.".to_string()),
+        })?;
+    if !status.success() {
+        return Err(CliError::InvalidInput {
+            message: format!("The editor '{editor}' exited with {status}."),
+            hint: Some("The task was not changed.".to_string()),
+        });
+    }
+
+    Ok(())
+}
+
+/// The edited file, removed once editing ends.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    /// Creates an empty owner-only file under a random name. `create_new` refuses a path
+    /// that already exists, including a planted symlink.
+    fn create(task_id: u64) -> Result<Self, CliError> {
+        let dir = env::temp_dir();
+        let mut attempts = 0;
+        loop {
+            let suffix = RandomState::new().build_hasher().finish();
+            let path = dir.join(format!("reclaim-task-{task_id}-{suffix:016x}.toml"));
+            let mut options = fs::OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::OpenOptionsExt;
+                options.mode(0o600);
+            }
+            match options.open(&path) {
+                Ok(_) => return Ok(Self(path)),
+                Err(error) if error.kind() == ErrorKind::AlreadyExists && attempts < 8 => {
+                    attempts += 1;
+                }
+                Err(error) => return Err(temp_file_error("create", &path, error)),
+            }
+        }
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Replaces the contents without creating the file again.
+    fn write(&self, contents: &str) -> Result<(), CliError> {
+        fs::OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .open(&self.0)
+            .and_then(|mut handle| handle.write_all(contents.as_bytes()))
+            .map_err(|error| self.error("write", error))
+    }
+
+    fn error(&self, action: &str, error: std::io::Error) -> CliError {
+        temp_file_error(action, &self.0, error)
+    }
+}
+
+fn temp_file_error(action: &str, path: &Path, error: std::io::Error) -> CliError {
+    CliError::InvalidInput {
+        message: format!("Could not {action} {}: {error}", path.display()),
+        hint: Some("Check that the temporary directory is writable, or set TMPDIR.".to_string()),
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_time;
+    use serde_json::json;
+
+    fn task() -> Map<String, Value> {
+        let Value::Object(task) = json!({
+            "id": 12,
+            "title": "Ship it",
+            "priority": "P2",
+            "due": "2026-02-20T22:00:00Z",
+            "timeChunksRequired": 8,
+            "minChunkSize": 2,
+            "maxChunkSize": 8,
+            "notes": "first line\nsecond line",
+            "status": "SCHEDULED"
+        }) else {
+            unreachable!()
+        };
+        task
+    }
+
+    #[test]
+    fn unchanged_document_round_trips_to_an_empty_patch() {
+        let task = task();
+        let document = render_document(12, "Ship it", &task);
+
+        assert!(document.contains("title = \"Ship it\"\n"));
+        assert!(document.contains("duration = \"2h\"\n"));
+        assert!(document.contains("# eventCategory = \"WORK\"\n"));
+        assert!(document.contains("# P1 (highest), P2, P3 or P4.\n"));
+        assert_eq!(parse_edit(&document, &task, &test_time()), Ok(Map::new()));
+    }
+
+    #[test]
+    fn edits_produce_a_minimal_patch() {
+        let task = task();
+        let document = render_document(12, "Ship it", &task)
+            .replace("priority = \"P2\"", "priority = \"p1\"")
+            .replace("due = \"2026-02-20T22:00:00Z\"", "due = \"tomorrow 5pm\"")
+            .replace("duration = \"2h\"", "duration = \"2h30m\"")
+            .replace("# alwaysPrivate = false", "alwaysPrivate = true");
+        let document = document[..document.find("notes =").unwrap()].to_string();
+
+        let patch = parse_edit(&document, &task, &test_time()).unwrap();
+        assert_eq!(
+            Value::Object(patch),
+            json!({
+                "priority": "P1",
+                "due": "2026-02-19T22:00:00Z",
+                "timeChunksRequired": 10,
+                "alwaysPrivate": true,
+                "notes": null
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_edits_are_reported_with_their_location() {
+        let task = task();
+        let document = render_document(12, "Ship it", &task);
+        let time = test_time();
+
+        let error = parse_edit(
+            &document.replace("priority = \"P2\"", "priority = \"P5\""),
+            &task,
+            &time,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.message,
+            "Invalid priority 'P5': use one of P1, P2, P3, P4."
+        );
+
+        let error =
+            parse_edit(&document.replace("title = \"Ship it\"\n", ""), &task, &time).unwrap_err();
+        assert!(error.message.starts_with("title cannot be removed"));
+
+        let error = parse_edit("title = \"Ship it\"\nowner = \"me\"\n", &task, &time).unwrap_err();
+        assert!(error.message.starts_with("Unknown field 'owner'"));
+
+        let broken = "title = \"Ship it\"\npriority = P2\n";
+        let error = parse_edit(broken, &task, &time).unwrap_err();
+        assert_eq!(error.location, Some((2, 12)));
+
+        let with_block = with_error_block(&error, broken);
+        assert!(with_block.starts_with("# error: line 5, column 12: "));
+        assert_eq!(with_block.lines().nth(4), Some("priority = P2"));
+        assert_eq!(strip_error_block(&with_block), broken);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn temp_file_is_new_and_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let first = TempFile::create(7).unwrap();
+        let second = TempFile::create(7).unwrap();
+        assert_ne!(first.path(), second.path());
+
+        first.write("title = \"Ship it\"\n").unwrap();
+        let mode = fs::metadata(first.path()).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(
+            fs::read_to_string(first.path()).unwrap(),
+            "title = \"Ship it\"\n"
+        );
+
+        let path = first.path().to_path_buf();
+        drop(first);
+        assert!(!path.exists());
+    }
+}
@@ -22,13 +22,18 @@ pub enum CliError {
         hint: Option<String>,
     },
     Output(String),
+    /// Some operations of `reclaim batch` failed; the report already lists them.
+    BatchFailed {
+        failed: usize,
+        total: usize,
+    },
 }
 
 impl CliError {
     pub fn hint(&self) -> Option<&str> {
         match self {
             CliError::MissingApiKey => Some(
-                "Set RECLAIM_API_KEY or pass --api-key. You can find your key in Reclaim settings.",
+                "Set RECLAIM_API_KEY, pass --api-key, or run `reclaim config set api-key <KEY>`. You can find your key in Reclaim settings.",
             ),
             CliError::InvalidBaseUrl(_) => {
                 Some("Use a valid URL, e.g. --base-url https://api.app.reclaim.ai/api")
@@ -37,6 +42,9 @@ impl CliError {
             | CliError::Transport { hint, .. }
             | CliError::Api { hint, .. }
             | CliError::ResponseParse { hint, .. } => hint.as_deref(),
+            CliError::BatchFailed { .. } => Some(
+                "Operations that succeeded were applied. Fix the failed lines and rerun only those.",
+            ),
             CliError::Output(_) => None,
         }
     }
@@ -60,6 +68,9 @@ impl fmt::Display for CliError {
             }
             CliError::ResponseParse { message, .. } => write!(f, "{message}"),
             CliError::Output(message) => write!(f, "{message}"),
+            CliError::BatchFailed { failed, total } => {
+                write!(f, "{failed} of {total} batch operations failed.")
+            }
         }
     }
 }
@@ -0,0 +1,603 @@
+use crate::error::CliError;
+use serde_json::Value;
+use std::cmp::Ordering;
+
+const TEMPLATE_HINT: &str =
+    "Use {{.field}} or {{.nested.field}} placeholders, e.g. --template '{{.id}}\\t{{.title}}'";
+const QUERY_HINT: &str = "Supported filters: ., .field, .[], .[N], select(.field == \"value\"), length and keys, joined with |";
+
+/// `--template` or `--query`, applied to the JSON a command would print with `--format json`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Extract {
+    Template(Template),
+    Query(Query),
+}
+
+impl Extract {
+    pub fn from_flags(
+        template: Option<&str>,
+        query: Option<&str>,
+    ) -> Result<Option<Self>, CliError> {
+        if let Some(raw) = template {
+            let template = Template::parse(raw).map_err(|reason| CliError::InvalidInput {
+                message: format!("Invalid --template: {reason}."),
+                hint: Some(TEMPLATE_HINT.to_string()),
+            })?;
+            return Ok(Some(Self::Template(template)));
+        }
+        if let Some(raw) = query {
+            let query = Query::parse(raw).map_err(|reason| CliError::InvalidInput {
+                message: format!("Invalid --query: {reason}."),
+                hint: Some(QUERY_HINT.to_string()),
+            })?;
+            return Ok(Some(Self::Query(query)));
+        }
+        Ok(None)
+    }
+
+    /// Output lines for `value`. Templates render once per list item; queries print one line
+    /// per result, with strings unquoted.
+    pub fn render(&self, value: &Value) -> Result<Vec<String>, CliError> {
+        match self {
+            Self::Template(template) => Ok(match value {
+                Value::Array(items) => items.iter().map(|item| template.render(item)).collect(),
+                value => vec![template.render(value)],
+            }),
+            Self::Query(query) => query
+                .evaluate(value)
+                .map(|results| results.iter().map(text).collect())
+                .map_err(|reason| CliError::InvalidInput {
+                    message: format!("--query failed: {reason}."),
+                    hint: Some(
+                        "Compare the filter with the --format json output of the same command."
+                            .to_string(),
+                    ),
+                }),
+        }
+    }
+}
+
+/// Go-template-style text with `{{.field}}` placeholders. Missing fields render empty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    parts: Vec<TemplatePart>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TemplatePart {
+    Text(String),
+    /// Dot path into the value; empty for `{{.}}`. Numeric segments index arrays.
+    Field(Vec<String>),
+}
+
+impl Template {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut rest = raw;
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                parts.push(TemplatePart::Text(unescape(&rest[..start])));
+            }
+            let after = &rest[start + 2..];
+            let end = after
+                .find("}}")
+                .ok_or_else(|| "unclosed '{{'".to_string())?;
+            parts.push(TemplatePart::Field(parse_template_action(
+                after[..end].trim(),
+            )?));
+            rest = &after[end + 2..];
+        }
+        if !rest.is_empty() {
+            parts.push(TemplatePart::Text(unescape(rest)));
+        }
+        Ok(Self { parts })
+    }
+
+    pub fn render(&self, value: &Value) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Text(text) => text.clone(),
+                TemplatePart::Field(path) => {
+                    let found = path
+                        .iter()
+                        .try_fold(value, |current, segment| match current {
+                            Value::Object(object) => object.get(segment),
+                            Value::Array(items) => segment
+                                .parse::<usize>()
+                                .ok()
+                                .and_then(|index| items.get(index)),
+                            _ => None,
+                        });
+                    found.map(text).unwrap_or_default()
+                }
+            })
+            .collect()
+    }
+}
+
+fn parse_template_action(action: &str) -> Result<Vec<String>, String> {
+    let unsupported = || format!("unsupported placeholder '{{{{{action}}}}}'");
+    let path = action.strip_prefix('.').ok_or_else(unsupported)?;
+    if path.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    path.split('.')
+        .map(|segment| {
+            let valid = !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|ch| ch.is_alphanumeric() || matches!(ch, '_' | '-'));
+            valid.then(|| segment.to_string()).ok_or_else(unsupported)
+        })
+        .collect()
+}
+
+/// Interprets `\n`, `\t` and `\\` in template text, which are awkward to pass through a shell.
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            unescaped.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some('\\') => unescaped.push('\\'),
+            Some(other) => {
+                unescaped.push('\\');
+                unescaped.push(other);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// A small subset of jq: paths, iteration, `select`, `length` and `keys`, joined with `|`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    stages: Vec<Stage>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Stage {
+    Path(Vec<Step>),
+    /// Alternatives joined with `or`, each a list of comparisons joined with `and`.
+    Select(Vec<Vec<Comparison>>),
+    Length,
+    Keys,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Step {
+    Field(String),
+    Index(i64),
+    Iterate,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Comparison {
+    path: Vec<Step>,
+    /// Without an operator the path is tested for truthiness (not null or false).
+    test: Option<(CompareOp, Value)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Ge,
+    Le,
+    Gt,
+    Lt,
+}
+
+impl CompareOp {
+    // Two-character operators first so `>=` is not read as `>`.
+    const ALL: [(&'static str, CompareOp); 6] = [
+        ("==", CompareOp::Eq),
+        ("!=", CompareOp::Ne),
+        (">=", CompareOp::Ge),
+        ("<=", CompareOp::Le),
+        (">", CompareOp::Gt),
+        ("<", CompareOp::Lt),
+    ];
+}
+
+impl Query {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let stages = split_top_level(raw, "|")
+            .into_iter()
+            .map(|stage| parse_stage(stage.trim()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { stages })
+    }
+
+    pub fn evaluate(&self, input: &Value) -> Result<Vec<Value>, String> {
+        let mut values = vec![input.clone()];
+        for stage in &self.stages {
+            let mut next = Vec::new();
+            for value in &values {
+                match stage {
+                    Stage::Path(steps) => next.extend(walk(value, steps)?),
+                    Stage::Select(alternatives) => {
+                        if select_matches(value, alternatives)? {
+                            next.push(value.clone());
+                        }
+                    }
+                    Stage::Length => next.push(length(value)?),
+                    Stage::Keys => next.push(keys(value)?),
+                }
+            }
+            values = next;
+        }
+        Ok(values)
+    }
+}
+
+fn parse_stage(stage: &str) -> Result<Stage, String> {
+    match stage {
+        "" => Err("empty filter".to_string()),
+        "length" => Ok(Stage::Length),
+        "keys" => Ok(Stage::Keys),
+        stage if stage.starts_with('.') => parse_path(stage).map(Stage::Path),
+        stage => {
+            let condition = stage
+                .strip_prefix("select")
+                .map(str::trim_start)
+                .and_then(|rest| rest.strip_prefix('('))
+                .and_then(|rest| rest.strip_suffix(')'))
+                .ok_or_else(|| format!("unsupported filter '{stage}'"))?;
+            let alternatives = split_top_level(condition, " or ")
+                .into_iter()
+                .map(|alternative| {
+                    split_top_level(alternative, " and ")
+                        .into_iter()
+                        .map(|comparison| parse_comparison(comparison.trim()))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Stage::Select(alternatives))
+        }
+    }
+}
+
+fn parse_comparison(input: &str) -> Result<Comparison, String> {
+    let operator = CompareOp::ALL
+        .iter()
+        .find_map(|(symbol, op)| find_top_level(input, symbol).map(|index| (index, *symbol, *op)));
+    let Some((index, symbol, op)) = operator else {
+        return Ok(Comparison {
+            path: parse_path(input)?,
+            test: None,
+        });
+    };
+
+    let literal = input[index + symbol.len()..].trim();
+    let value = serde_json::from_str::<Value>(literal)
+        .map_err(|_| format!("'{literal}' is not a literal; quote strings, e.g. \"P1\""))?;
+    Ok(Comparison {
+        path: parse_path(input[..index].trim())?,
+        test: Some((op, value)),
+    })
+}
+
+/// Parses `.`, `.field`, `.a.b`, `.[]`, `.[0]`, `.["key"]` and combinations like `.items[].id`.
+fn parse_path(input: &str) -> Result<Vec<Step>, String> {
+    let mut rest = input
+        .strip_prefix('.')
+        .ok_or_else(|| format!("expected a path starting with '.', found '{input}'"))?;
+    let mut steps = Vec::new();
+    if rest.is_empty() {
+        return Ok(steps);
+    }
+
+    loop {
+        if let Some(after) = rest.strip_prefix('[') {
+            let (inner, tail) = after
+                .split_once(']')
+                .ok_or_else(|| format!("unclosed '[' in '{input}'"))?;
+            let inner = inner.trim();
+            let step = if inner.is_empty() {
+                Step::Iterate
+            } else if let Some(key) = inner
+                .strip_prefix('"')
+                .and_then(|inner| inner.strip_suffix('"'))
+            {
+                Step::Field(key.to_string())
+            } else {
+                Step::Index(
+                    inner
+                        .parse()
+                        .map_err(|_| format!("invalid index '[{inner}]' in '{input}'"))?,
+                )
+            };
+            steps.push(step);
+            rest = tail;
+        } else {
+            let len = rest
+                .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                .unwrap_or(rest.len());
+            if len == 0 {
+                return Err(format!("unexpected '{rest}' in '{input}'"));
+            }
+            steps.push(Step::Field(rest[..len].to_string()));
+            rest = &rest[len..];
+        }
+
+        if rest.is_empty() {
+            return Ok(steps);
+        }
+        if rest.starts_with('[') {
+            continue;
+        }
+        match rest.strip_prefix('.') {
+            Some(after) if !after.is_empty() => rest = after,
+            _ => return Err(format!("unexpected '{rest}' in '{input}'")),
+        }
+    }
+}
+
+/// Splits on `separator` outside double-quoted strings and parentheses.
+fn split_top_level<'a>(input: &'a str, separator: &str) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let mut rest = input;
+    while let Some(index) = find_top_level(rest, separator) {
+        parts.push(&rest[..index]);
+        rest = &rest[index + separator.len()..];
+    }
+    parts.push(rest);
+    parts
+}
+
+fn find_top_level(input: &str, needle: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (index, ch) in input.char_indices() {
+        if in_string {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        if depth == 0 && input[index..].starts_with(needle) {
+            return Some(index);
+        }
+        match ch {
+            '"' => in_string = true,
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn walk(value: &Value, steps: &[Step]) -> Result<Vec<Value>, String> {
+    let Some((step, rest)) = steps.split_first() else {
+        return Ok(vec![value.clone()]);
+    };
+
+    let selected = match (step, value) {
+        (Step::Field(key), Value::Object(object)) => {
+            vec![object.get(key).cloned().unwrap_or(Value::Null)]
+        }
+        (Step::Field(_) | Step::Index(_), Value::Null) => vec![Value::Null],
+        (Step::Field(key), other) => {
+            return Err(format!("cannot index {} with \"{key}\"", type_name(other)))
+        }
+        (Step::Index(index), Value::Array(items)) => {
+            let position = if *index < 0 {
+                i64::try_from(items.len()).unwrap_or(i64::MAX) + index
+            } else {
+                *index
+            };
+            let item = usize::try_from(position)
+                .ok()
+                .and_then(|position| items.get(position));
+            vec![item.cloned().unwrap_or(Value::Null)]
+        }
+        (Step::Index(index), other) => {
+            return Err(format!("cannot index {} with {index}", type_name(other)))
+        }
+        (Step::Iterate, Value::Array(items)) => items.clone(),
+        (Step::Iterate, Value::Object(object)) => object.values().cloned().collect(),
+        (Step::Iterate, other) => return Err(format!("cannot iterate over {}", type_name(other))),
+    };
+
+    let mut results = Vec::new();
+    for value in &selected {
+        results.extend(walk(value, rest)?);
+    }
+    Ok(results)
+}
+
+fn select_matches(value: &Value, alternatives: &[Vec<Comparison>]) -> Result<bool, String> {
+    for comparisons in alternatives {
+        let mut all = true;
+        for comparison in comparisons {
+            if !comparison_matches(value, comparison)? {
+                all = false;
+                break;
+            }
+        }
+        if all {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn comparison_matches(value: &Value, comparison: &Comparison) -> Result<bool, String> {
+    let found = walk(value, &comparison.path)?;
+    Ok(found.iter().any(|found| match &comparison.test {
+        None => !matches!(found, Value::Null | Value::Bool(false)),
+        Some((op, expected)) => {
+            let ordering = compare(found, expected);
+            match op {
+                CompareOp::Eq => ordering == Some(Ordering::Equal),
+                CompareOp::Ne => ordering != Some(Ordering::Equal),
+                CompareOp::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+                CompareOp::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+                CompareOp::Gt => ordering == Some(Ordering::Greater),
+                CompareOp::Lt => ordering == Some(Ordering::Less),
+            }
+        }
+    }))
+}
+
+/// Numbers compare by value and strings lexically; other values are only equal or not.
+fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Number(left), Value::Number(right)) => left.as_f64()?.partial_cmp(&right.as_f64()?),
+        (Value::String(left), Value::String(right)) => Some(left.cmp(right)),
+        (left, right) if left == right => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+fn length(value: &Value) -> Result<Value, String> {
+    let length = match value {
+        Value::Null => 0,
+        Value::Array(items) => items.len(),
+        Value::Object(object) => object.len(),
+        Value::String(text) => text.chars().count(),
+        Value::Number(number) => return Ok(Value::from(number.as_f64().unwrap_or(0.0).abs())),
+        Value::Bool(_) => return Err("boolean has no length".to_string()),
+    };
+    Ok(Value::from(length))
+}
+
+fn keys(value: &Value) -> Result<Value, String> {
+    match value {
+        Value::Object(object) => {
+            let mut keys = object.keys().cloned().collect::<Vec<_>>();
+            keys.sort();
+            Ok(Value::from(keys))
+        }
+        Value::Array(items) => Ok(Value::from((0..items.len()).collect::<Vec<_>>())),
+        other => Err(format!("{} has no keys", type_name(other))),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Strings print raw (like `jq -r`), everything else as compact JSON.
+fn text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tasks() -> Value {
+        json!([
+            { "id": 1, "title": "Plan", "priority": "P1", "extra": { "tags": ["a", "b"] } },
+            { "id": 2, "title": "Ship", "priority": "P3", "timeChunksRemaining": 6 },
+            { "id": 3, "title": "Review", "priority": "P1", "timeChunksRemaining": 2 },
+        ])
+    }
+
+    fn run(query: &str, value: &Value) -> Vec<String> {
+        Extract::Query(Query::parse(query).unwrap())
+            .render(value)
+            .unwrap()
+    }
+
+    #[test]
+    fn template_renders_each_list_item() {
+        let template =
+            Extract::Template(Template::parse("{{.id}}\\t{{ .title }}{{.missing}}").unwrap());
+        assert_eq!(
+            template.render(&tasks()).unwrap(),
+            vec!["1\tPlan", "2\tShip", "3\tReview"]
+        );
+
+        let template =
+            Template::parse("#{{.id}} tags={{.extra.tags}} first={{.extra.tags.0}}").unwrap();
+        assert_eq!(
+            template.render(&tasks()[0]),
+            "#1 tags=[\"a\",\"b\"] first=a"
+        );
+        assert!(Template::parse("{{.id").unwrap_err().contains("unclosed"));
+        assert!(Template::parse("{{ title }}")
+            .unwrap_err()
+            .contains("unsupported placeholder"));
+    }
+
+    #[test]
+    fn query_supports_paths_select_and_builtins() {
+        assert_eq!(
+            run(r#".[] | select(.priority == "P1") | .id"#, &tasks()),
+            vec!["1", "3"]
+        );
+        assert_eq!(
+            run(
+                ".[] | select(.timeChunksRemaining > 3 or .extra) | .title",
+                &tasks()
+            ),
+            vec!["Plan", "Ship"]
+        );
+        assert_eq!(
+            run(
+                r#".[] | select(.priority == "P1" and .timeChunksRemaining <= 2) | .title"#,
+                &tasks()
+            ),
+            vec!["Review"]
+        );
+        assert_eq!(run(".[0].extra.tags[]", &tasks()), vec!["a", "b"]);
+        assert_eq!(run(".[-1].title", &tasks()), vec!["Review"]);
+        assert_eq!(
+            run(".[] | select(.title == \"a | b\") | .id", &tasks()),
+            Vec::<String>::new()
+        );
+        assert_eq!(run("length", &tasks()), vec!["3"]);
+        assert_eq!(
+            run(".[1] | keys", &tasks()),
+            vec![r#"["id","priority","timeChunksRemaining","title"]"#]
+        );
+        assert_eq!(
+            run(".", &json!({ "deleted": true })),
+            vec![r#"{"deleted":true}"#]
+        );
+    }
+
+    #[test]
+    fn query_reports_parse_and_evaluation_errors() {
+        assert!(Query::parse(".[] | map(.id)")
+            .unwrap_err()
+            .contains("unsupported filter"));
+        assert!(Query::parse("select(.priority == P1)")
+            .unwrap_err()
+            .contains("quote strings"));
+        assert!(Query::parse(".a[0").unwrap_err().contains("unclosed '['"));
+
+        let error = Extract::Query(Query::parse(".[].title[]").unwrap())
+            .render(&tasks())
+            .unwrap_err();
+        assert!(error.to_string().contains("cannot iterate over string"));
+    }
+}
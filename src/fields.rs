@@ -0,0 +1,832 @@
+use crate::error::CliError;
+use clap::ArgMatches;
+use serde_json::{Map, Value};
+use std::{
+    fs,
+    io::{self, Read},
+};
+
+const PATH_HINT: &str =
+    "Use a key or dot path with [N] or [+] (append), e.g. priority, dateRange.start or attendees[+].";
+
+/// How `--unset` clears an object field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsetMode {
+    /// Drop the key; for bodies that describe the whole object (PUT, new events).
+    Remove,
+    /// Send `null`; for partial updates, where a missing key means "unchanged".
+    Null,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+    Append,
+}
+
+/// A parsed `--set`/`--unset` key such as `dateRange.start` or `attendees[+]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FieldPath {
+    raw: String,
+    segments: Vec<Segment>,
+}
+
+impl FieldPath {
+    fn parse(raw: &str) -> Result<Self, String> {
+        let mut segments = Vec::new();
+        for part in raw.split('.') {
+            let key_len = part.find('[').unwrap_or(part.len());
+            let (key, mut rest) = part.split_at(key_len);
+            if key.is_empty() {
+                return Err("every path segment needs a key".to_string());
+            }
+            segments.push(Segment::Key(key.to_string()));
+
+            while !rest.is_empty() {
+                let Some((inner, after)) =
+                    rest.strip_prefix('[').and_then(|rest| rest.split_once(']'))
+                else {
+                    return Err(format!("unexpected '{rest}' after '{key}'"));
+                };
+                segments.push(match inner {
+                    "+" => Segment::Append,
+                    index => Segment::Index(
+                        index
+                            .parse()
+                            .map_err(|_| format!("'[{index}]' is not an index; use [N] or [+]"))?,
+                    ),
+                });
+                rest = after;
+            }
+        }
+
+        Ok(Self {
+            raw: raw.to_string(),
+            segments,
+        })
+    }
+
+    /// The path up to (not including) segment `len`, for error messages.
+    fn prefix(&self, len: usize) -> String {
+        let mut prefix = String::new();
+        for segment in &self.segments[..len] {
+            match segment {
+                Segment::Key(key) if prefix.is_empty() => prefix.push_str(key),
+                Segment::Key(key) => {
+                    prefix.push('.');
+                    prefix.push_str(key);
+                }
+                Segment::Index(index) => prefix.push_str(&format!("[{index}]")),
+                Segment::Append => prefix.push_str("[+]"),
+            }
+        }
+        prefix
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Change {
+    Set(Value),
+    Unset,
+}
+
+/// One `--set`, `--set-string` or `--unset` entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldUpdate {
+    flag: &'static str,
+    path: FieldPath,
+    change: Change,
+}
+
+impl FieldUpdate {
+    /// The field name and value of a `--set` on a plain top-level key, for callers that
+    /// normalize particular fields (e.g. task timestamps) before applying.
+    pub fn key_value_mut(&mut self) -> Option<(&str, &mut Value)> {
+        match (self.path.segments.as_slice(), &mut self.change) {
+            ([Segment::Key(key)], Change::Set(value)) => Some((key, value)),
+            _ => None,
+        }
+    }
+
+    /// The top-level field this update reaches below (`dateRange` for `dateRange.start`,
+    /// `attendees` for `attendees[+]`), or `None` for a plain key.
+    fn nested_key(&self) -> Option<&str> {
+        match self.path.segments.as_slice() {
+            [Segment::Key(key), _, ..] => Some(key),
+            _ => None,
+        }
+    }
+
+    fn error(&self, reason: &str, hint: &str) -> CliError {
+        CliError::InvalidInput {
+            message: format!("Cannot apply {} {}: {reason}.", self.flag, self.path.raw),
+            hint: Some(hint.to_string()),
+        }
+    }
+}
+
+/// Parses the `--set`, `--set-string` and `--unset` entries in the order they are applied:
+/// as typed when `matches` (the subcommand's parsed arguments) is given, otherwise every
+/// `--set`, then every `--set-string`, then every `--unset`.
+pub fn parse_updates(
+    set: &[String],
+    set_string: &[String],
+    unset: &[String],
+    matches: Option<&ArgMatches>,
+) -> Result<Vec<FieldUpdate>, CliError> {
+    let positions = |id: &str| -> Vec<usize> {
+        matches
+            .and_then(|matches| matches.indices_of(id))
+            .map(Iterator::collect)
+            .unwrap_or_default()
+    };
+
+    let mut updates = Vec::new();
+    for (entry, position) in set.iter().zip(with_positions(positions("set"))) {
+        let (path, raw_value) = parse_set_entry("--set", entry)?;
+        let update = FieldUpdate {
+            flag: "--set",
+            path,
+            change: Change::Set(match read_set_value("--set", raw_value)? {
+                Some(text) => Value::String(text),
+                None => parse_set_value(raw_value),
+            }),
+        };
+        updates.push((position, update));
+    }
+    for (entry, position) in set_string
+        .iter()
+        .zip(with_positions(positions("set_string")))
+    {
+        let (path, raw_value) = parse_set_entry("--set-string", entry)?;
+        let update = FieldUpdate {
+            flag: "--set-string",
+            path,
+            change: Change::Set(Value::String(
+                read_set_value("--set-string", raw_value)?.unwrap_or_else(|| raw_value.to_string()),
+            )),
+        };
+        updates.push((position, update));
+    }
+    for (entry, position) in unset.iter().zip(with_positions(positions("unset"))) {
+        let path = parse_path("--unset", entry, entry.trim())?;
+        if path.segments.contains(&Segment::Append) {
+            return Err(CliError::InvalidInput {
+                message: format!("Invalid --unset value '{entry}': [+] only works with --set."),
+                hint: Some(
+                    "Remove an array element by index, e.g. --unset attendees[0].".to_string(),
+                ),
+            });
+        }
+        let update = FieldUpdate {
+            flag: "--unset",
+            path,
+            change: Change::Unset,
+        };
+        updates.push((position, update));
+    }
+
+    // Stable, so entries without a position keep the flag-by-flag order.
+    updates.sort_by_key(|(position, _)| *position);
+    Ok(updates.into_iter().map(|(_, update)| update).collect())
+}
+
+/// `positions` followed by `None` for entries that did not come from the command line.
+fn with_positions(positions: Vec<usize>) -> impl Iterator<Item = Option<usize>> {
+    positions
+        .into_iter()
+        .map(Some)
+        .chain(std::iter::repeat(None))
+}
+
+fn parse_set_entry<'a>(flag: &str, entry: &'a str) -> Result<(FieldPath, &'a str), CliError> {
+    let (raw_key, raw_value) = entry
+        .split_once('=')
+        .ok_or_else(|| CliError::InvalidInput {
+            message: format!("Invalid {flag} value '{entry}'. Expected KEY=VALUE."),
+            hint: Some(format!(
+                "Examples: {flag} priority=P4 {flag} snoozeUntil=2026-02-25T17:00:00Z"
+            )),
+        })?;
+
+    Ok((parse_path(flag, entry, raw_key.trim())?, raw_value.trim()))
+}
+
+fn parse_path(flag: &str, entry: &str, raw_key: &str) -> Result<FieldPath, CliError> {
+    if raw_key.is_empty() {
+        return Err(CliError::InvalidInput {
+            message: format!("Invalid {flag} value '{entry}': key cannot be empty."),
+            hint: Some(format!("Use a non-empty key, e.g. {flag} priority=P4")),
+        });
+    }
+
+    FieldPath::parse(raw_key).map_err(|reason| CliError::InvalidInput {
+        message: format!("Invalid {flag} key '{raw_key}': {reason}."),
+        hint: Some(PATH_HINT.to_string()),
+    })
+}
+
+/// The text of a value that starts with `@`: `@path` reads a file, minus one trailing
+/// newline, and `@@` stands for a literal `@`. Either way the result is a string, never a
+/// JSON literal. `None` for any other value.
+fn read_set_value(flag: &str, raw_value: &str) -> Result<Option<String>, CliError> {
+    if let Some(literal) = raw_value.strip_prefix("@@") {
+        return Ok(Some(format!("@{literal}")));
+    }
+    let Some(path) = raw_value.strip_prefix('@') else {
+        return Ok(None);
+    };
+
+    let contents = read_file(
+        flag,
+        path,
+        "Check the path after @, or start the value with @@ to send a literal @.",
+    )?;
+    let contents = contents.strip_suffix('\n').unwrap_or(&contents);
+    Ok(Some(
+        contents.strip_suffix('\r').unwrap_or(contents).to_string(),
+    ))
+}
+
+/// `--set` values are JSON literals when they parse as JSON, and strings otherwise.
+pub fn parse_set_value(raw_value: &str) -> Value {
+    serde_json::from_str(raw_value).unwrap_or_else(|_| Value::String(raw_value.to_string()))
+}
+
+/// Whether an update reaches below a top-level field, so a partial update has to start from
+/// the object's current value of that field.
+pub fn reaches_below_top_level(updates: &[FieldUpdate]) -> bool {
+    updates.iter().any(|update| update.nested_key().is_some())
+}
+
+/// Fills in the current value of each top-level field `updates` reach below, with what
+/// `target` already sets there merged on top. Applying the updates afterwards changes one
+/// part of the field, and the whole field is sent.
+pub fn seed_nested(
+    target: &mut Map<String, Value>,
+    current: &Map<String, Value>,
+    updates: &[FieldUpdate],
+) {
+    for key in updates.iter().filter_map(FieldUpdate::nested_key) {
+        let Some(current) = current.get(key) else {
+            continue;
+        };
+        let seeded = match (target.remove(key), current.clone()) {
+            (None, current) => current,
+            (Some(Value::Object(set)), Value::Object(mut merged)) => {
+                deep_merge(&mut merged, set);
+                Value::Object(merged)
+            }
+            (Some(set), _) => set,
+        };
+        target.insert(key.to_string(), seeded);
+    }
+}
+
+/// Applies `updates` in order. Missing objects and arrays along a `--set` path are created;
+/// `[N]` must point at an existing element.
+pub fn apply_updates(
+    target: &mut Map<String, Value>,
+    updates: Vec<FieldUpdate>,
+    unset_mode: UnsetMode,
+) -> Result<(), CliError> {
+    let mut root = Value::Object(std::mem::take(target));
+    let result = updates
+        .into_iter()
+        .try_for_each(|update| match &update.change {
+            Change::Set(value) => {
+                let slot = walk(&mut root, &update, update.path.segments.len())?;
+                *slot = value.clone();
+                Ok(())
+            }
+            Change::Unset => unset(&mut root, &update, unset_mode),
+        });
+    if let Value::Object(fields) = root {
+        *target = fields;
+    }
+
+    result
+}
+
+fn unset(root: &mut Value, update: &FieldUpdate, unset_mode: UnsetMode) -> Result<(), CliError> {
+    let parent_len = update.path.segments.len() - 1;
+    let parent = match unset_mode {
+        UnsetMode::Null => walk(root, update, parent_len)?,
+        UnsetMode::Remove => match existing(root, &update.path.segments[..parent_len]) {
+            Some(parent) => parent,
+            None => return Ok(()),
+        },
+    };
+
+    match (&update.path.segments[parent_len], parent) {
+        (Segment::Index(index), Value::Array(items)) if *index < items.len() => {
+            items.remove(*index);
+            Ok(())
+        }
+        (Segment::Key(key), Value::Object(fields)) => {
+            match unset_mode {
+                UnsetMode::Null => {
+                    fields.insert(key.clone(), Value::Null);
+                }
+                UnsetMode::Remove => {
+                    fields.remove(key);
+                }
+            }
+            Ok(())
+        }
+        (Segment::Key(_), Value::Null) => Ok(()),
+        (segment, parent) => Err(mismatch(update, parent_len, segment, parent)),
+    }
+}
+
+/// The value at the first `len` segments of the update's path, creating missing objects
+/// and arrays on the way.
+fn walk<'a>(
+    root: &'a mut Value,
+    update: &FieldUpdate,
+    len: usize,
+) -> Result<&'a mut Value, CliError> {
+    let mut current = root;
+    for (depth, segment) in update.path.segments[..len].iter().enumerate() {
+        if current.is_null() {
+            *current = match segment {
+                Segment::Key(_) => Value::Object(Map::new()),
+                Segment::Index(_) | Segment::Append => Value::Array(Vec::new()),
+            };
+        }
+        if !holds(current, segment) {
+            return Err(mismatch(update, depth, segment, current));
+        }
+        current = match (segment, current) {
+            (Segment::Key(key), Value::Object(fields)) => {
+                fields.entry(key.clone()).or_insert(Value::Null)
+            }
+            (Segment::Index(index), Value::Array(items)) => &mut items[*index],
+            (Segment::Append, Value::Array(items)) => {
+                items.push(Value::Null);
+                items.last_mut().expect("an element was just pushed")
+            }
+            _ => unreachable!("checked by holds"),
+        };
+    }
+
+    Ok(current)
+}
+
+/// Whether `segment` can be looked up in `value` without replacing it.
+fn holds(value: &Value, segment: &Segment) -> bool {
+    match (segment, value) {
+        (Segment::Key(_), Value::Object(_)) | (Segment::Append, Value::Array(_)) => true,
+        (Segment::Index(index), Value::Array(items)) => *index < items.len(),
+        _ => false,
+    }
+}
+
+fn existing<'a>(root: &'a mut Value, segments: &[Segment]) -> Option<&'a mut Value> {
+    segments
+        .iter()
+        .try_fold(root, |current, segment| match segment {
+            Segment::Key(key) => current.get_mut(key.as_str()),
+            Segment::Index(index) => current.get_mut(*index),
+            Segment::Append => None,
+        })
+}
+
+fn mismatch(update: &FieldUpdate, depth: usize, segment: &Segment, parent: &Value) -> CliError {
+    let name = update.path.prefix(depth);
+    match (segment, parent) {
+        (Segment::Index(index), Value::Array(items)) => update.error(
+            &format!(
+                "{name} has {} item{}, so there is no [{index}]",
+                items.len(),
+                if items.len() == 1 { "" } else { "s" }
+            ),
+            &format!("Use {name}[+] to append an element."),
+        ),
+        (Segment::Key(_), parent) => update.error(
+            &format!("{name} is {}, not an object", kind(parent)),
+            &format!("Replace the whole value instead, e.g. --set {name}='{{...}}'."),
+        ),
+        (_, parent) => update.error(
+            &format!("{name} is {}, not an array", kind(parent)),
+            &format!("Replace the whole value instead, e.g. --set {name}='[...]'."),
+        ),
+    }
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Parses a `--json` value: an inline JSON object, `@path` to read one from a file, or `-`
+/// to read one from stdin. Errors in files and stdin point at a line and column.
+pub fn parse_json_object(raw_json: &str, flag_name: &str) -> Result<Map<String, Value>, CliError> {
+    let hint = || {
+        Some(format!(
+            "Pass {flag_name} with a JSON object, e.g. {flag_name} '{{\"priority\":\"P4\"}}', \
+             {flag_name} @payload.json or {flag_name} - to read stdin."
+        ))
+    };
+    let raw_json = raw_json.trim();
+    let (text, source) = match raw_json {
+        "-" => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|error| CliError::InvalidInput {
+                    message: format!("Could not read {flag_name} from stdin: {error}"),
+                    hint: Some(format!(
+                        "Pipe a JSON object in, e.g. cat payload.json | reclaim ... {flag_name} -"
+                    )),
+                })?;
+            (text, Some("stdin".to_string()))
+        }
+        _ => match raw_json.strip_prefix('@') {
+            Some(path) => (
+                read_file(
+                    flag_name,
+                    path,
+                    "Check the path after @, or pass - to read stdin.",
+                )?,
+                Some(path.to_string()),
+            ),
+            None => (raw_json.to_string(), None),
+        },
+    };
+
+    if text.trim().is_empty() {
+        return Err(CliError::InvalidInput {
+            message: match &source {
+                Some(source) => format!("Invalid {flag_name} value: {source} is empty."),
+                None => format!("Invalid {flag_name} value: it cannot be empty."),
+            },
+            hint: hint(),
+        });
+    }
+
+    let parsed: Value = serde_json::from_str(&text).map_err(|error| CliError::InvalidInput {
+        message: match &source {
+            Some(source) => format!(
+                "Invalid {flag_name} JSON in {source} at line {}, column {}: {}",
+                error.line(),
+                error.column(),
+                json_error_reason(&error)
+            ),
+            None => format!("Invalid {flag_name} JSON: {error}"),
+        },
+        hint: hint(),
+    })?;
+
+    match parsed {
+        Value::Object(fields) => Ok(fields),
+        _ => Err(CliError::InvalidInput {
+            message: match &source {
+                Some(source) => {
+                    format!("Invalid {flag_name} value: {source} does not hold a JSON object.")
+                }
+                None => format!("Invalid {flag_name} value: expected a JSON object."),
+            },
+            hint: hint(),
+        }),
+    }
+}
+
+/// The serde_json message without its trailing "at line L column C".
+pub fn json_error_reason(error: &serde_json::Error) -> String {
+    let message = error.to_string();
+    let position = format!(" at line {} column {}", error.line(), error.column());
+    message
+        .strip_suffix(&position)
+        .unwrap_or(&message)
+        .to_string()
+}
+
+fn read_file(flag: &str, path: &str, hint: &str) -> Result<String, CliError> {
+    if path.is_empty() {
+        return Err(CliError::InvalidInput {
+            message: format!("Invalid {flag} value '@': the file path is missing."),
+            hint: Some(hint.to_string()),
+        });
+    }
+
+    fs::read_to_string(path).map_err(|error| CliError::InvalidInput {
+        message: format!("Could not read {flag} file {path}: {error}"),
+        hint: Some(hint.to_string()),
+    })
+}
+
+/// Merges `updates` into `target`: objects merge key by key at any depth, every other value
+/// (arrays and `null` included) replaces what was there.
+pub fn deep_merge(target: &mut Map<String, Value>, updates: Map<String, Value>) {
+    for (key, value) in updates {
+        match (target.get_mut(&key), value) {
+            (Some(Value::Object(existing)), Value::Object(nested)) => deep_merge(existing, nested),
+            (_, value) => {
+                target.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn strings(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(ToString::to_string).collect()
+    }
+
+    fn apply(
+        target: Value,
+        set: &[&str],
+        unset: &[&str],
+        unset_mode: UnsetMode,
+    ) -> Result<Value, CliError> {
+        let Value::Object(mut target) = target else {
+            panic!("target must be an object");
+        };
+        let updates = parse_updates(&strings(set), &[], &strings(unset), None)?;
+        apply_updates(&mut target, updates, unset_mode)?;
+        Ok(Value::Object(target))
+    }
+
+    #[test]
+    fn parse_json_object_requires_object() {
+        let error = parse_json_object("[]", "--json").unwrap_err();
+        assert!(error.to_string().contains("expected a JSON object"));
+    }
+
+    #[test]
+    fn json_and_set_values_read_files() {
+        let dir = std::env::temp_dir().join(format!("reclaim-fields-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let payload = dir.join("payload.json");
+        let notes = dir.join("notes.txt");
+        fs::write(&payload, "{\n  \"priority\": \"P2\"\n}\n").unwrap();
+        let count = dir.join("count.txt");
+        fs::write(&notes, "Line one\nLine two\n").unwrap();
+        fs::write(&count, "42\n").unwrap();
+
+        let parsed = parse_json_object(&format!("@{}", payload.display()), "--json").unwrap();
+        assert_eq!(Value::Object(parsed), json!({ "priority": "P2" }));
+
+        fs::write(&payload, "{\n  \"priority\": P2\n}\n").unwrap();
+        let error = parse_json_object(&format!("@{}", payload.display()), "--json").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Invalid --json JSON in {} at line 2, column 15: expected value",
+                payload.display()
+            )
+        );
+
+        let error = parse_json_object("@missing.json", "--json").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Could not read --json file missing.json:"));
+
+        let updates = parse_updates(
+            &strings(&[
+                &format!("notes=@{}", notes.display()),
+                &format!("title=@{}", count.display()),
+                "location=@@42",
+            ]),
+            &strings(&["title=@@home"]),
+            &[],
+            None,
+        )
+        .unwrap();
+        assert_eq!(updates[0].change, Change::Set(json!("Line one\nLine two")));
+        assert_eq!(updates[1].change, Change::Set(json!("42")));
+        assert_eq!(updates[2].change, Change::Set(json!("@42")));
+        assert_eq!(updates[3].change, Change::Set(json!("@home")));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn parse_set_value_supports_json_literals() {
+        assert_eq!(parse_set_value("true"), json!(true));
+        assert_eq!(parse_set_value("42"), json!(42));
+        assert_eq!(parse_set_value("{\"nested\":1}"), json!({"nested": 1}));
+        assert_eq!(parse_set_value("P4"), json!("P4"));
+    }
+
+    #[test]
+    fn parse_updates_rejects_malformed_entries() {
+        let error = |set: &[&str], unset: &[&str]| {
+            parse_updates(&strings(set), &[], &strings(unset), None)
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(error(&["priority"], &[]).contains("Expected KEY=VALUE"));
+        assert!(error(&["=1"], &[]).contains("key cannot be empty"));
+        assert!(error(&["a..b=1"], &[]).contains("every path segment needs a key"));
+        assert!(error(&["tags[x]=1"], &[]).contains("'[x]' is not an index"));
+        assert!(error(&["tags[0=1"], &[]).contains("unexpected '[0'"));
+        assert!(error(&[], &["tags[+]"]).contains("[+] only works with --set"));
+
+        let updates = parse_updates(&[], &strings(&["title=123"]), &[], None).unwrap();
+        assert_eq!(updates[0].change, Change::Set(json!("123")));
+    }
+
+    #[test]
+    fn parse_updates_keeps_command_line_order() {
+        let matches = clap::Command::new("patch")
+            .args(
+                [
+                    ("set", "set"),
+                    ("set_string", "set-string"),
+                    ("unset", "unset"),
+                ]
+                .map(|(id, long)| {
+                    clap::Arg::new(id)
+                        .long(long)
+                        .action(clap::ArgAction::Append)
+                }),
+            )
+            .try_get_matches_from([
+                "patch",
+                "--unset",
+                "notes",
+                "--set",
+                "notes=later",
+                "--set-string",
+                "title=1",
+                "--set",
+                "title=2",
+            ])
+            .unwrap();
+        let values = |id: &str| {
+            matches
+                .get_many::<String>(id)
+                .map(|values| values.cloned().collect::<Vec<_>>())
+                .unwrap_or_default()
+        };
+
+        let updates = parse_updates(
+            &values("set"),
+            &values("set_string"),
+            &values("unset"),
+            Some(&matches),
+        )
+        .unwrap();
+        let flags = updates.iter().map(|update| update.flag).collect::<Vec<_>>();
+        assert_eq!(flags, ["--unset", "--set", "--set-string", "--set"]);
+
+        let Value::Object(mut body) = json!({}) else {
+            unreachable!()
+        };
+        apply_updates(&mut body, updates, UnsetMode::Null).unwrap();
+        assert_eq!(Value::Object(body), json!({ "notes": "later", "title": 2 }));
+    }
+
+    #[test]
+    fn seed_nested_starts_nested_updates_from_the_current_value() {
+        let Value::Object(current) = json!({
+            "title": "Sync",
+            "dateRange": { "type": "FixedDateTimeRange", "start": "a", "end": "b" },
+            "attendees": [{ "email": "x@y.com" }]
+        }) else {
+            unreachable!()
+        };
+        let Value::Object(mut body) = json!({ "dateRange": { "end": "c" } }) else {
+            unreachable!()
+        };
+        let updates = parse_updates(
+            &strings(&[
+                "dateRange.start=s",
+                "attendees[+].email=z@y.com",
+                "title=New",
+            ]),
+            &[],
+            &[],
+            None,
+        )
+        .unwrap();
+
+        assert!(reaches_below_top_level(&updates));
+        seed_nested(&mut body, &current, &updates);
+        apply_updates(&mut body, updates, UnsetMode::Null).unwrap();
+        assert_eq!(
+            Value::Object(body),
+            json!({
+                "title": "New",
+                "dateRange": { "type": "FixedDateTimeRange", "start": "s", "end": "c" },
+                "attendees": [{ "email": "x@y.com" }, { "email": "z@y.com" }]
+            })
+        );
+    }
+
+    #[test]
+    fn set_paths_create_nested_objects_and_append_to_arrays() {
+        let updated = apply(
+            json!({ "dateRange": { "type": "FixedDateTimeRange", "start": "a" } }),
+            &[
+                "dateRange.start=2026-02-21T18:30:00Z",
+                "attendees[+]={\"email\":\"x@y.com\"}",
+                "attendees[+].email=z@y.com",
+                "attendees[0].optional=true",
+                "assist.locked=false",
+            ],
+            &[],
+            UnsetMode::Remove,
+        )
+        .unwrap();
+
+        assert_eq!(
+            updated,
+            json!({
+                "dateRange": { "type": "FixedDateTimeRange", "start": "2026-02-21T18:30:00Z" },
+                "attendees": [
+                    { "email": "x@y.com", "optional": true },
+                    { "email": "z@y.com" }
+                ],
+                "assist": { "locked": false }
+            })
+        );
+    }
+
+    #[test]
+    fn set_paths_report_type_and_index_mismatches() {
+        let error = apply(
+            json!({ "title": "x" }),
+            &["title.text=y"],
+            &[],
+            UnsetMode::Null,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Cannot apply --set title.text: title is a string, not an object."
+        );
+
+        let error = apply(
+            json!({ "attendees": [{}] }),
+            &["attendees[3].email=x"],
+            &[],
+            UnsetMode::Null,
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("attendees has 1 item, so there is no [3]"));
+        assert_eq!(error.hint(), Some("Use attendees[+] to append an element."));
+    }
+
+    #[test]
+    fn unset_removes_or_nulls_fields_depending_on_mode() {
+        let task = json!({ "title": "x", "notes": "n", "tags": ["a", "b"] });
+
+        let removed = apply(
+            task.clone(),
+            &[],
+            &["notes", "tags[0]", "missing.field"],
+            UnsetMode::Remove,
+        )
+        .unwrap();
+        assert_eq!(removed, json!({ "title": "x", "tags": ["b"] }));
+
+        let nulled = apply(task, &["due=friday"], &["notes", "due"], UnsetMode::Null).unwrap();
+        assert_eq!(
+            nulled,
+            json!({ "title": "x", "notes": null, "tags": ["a", "b"], "due": null })
+        );
+    }
+
+    #[test]
+    fn deep_merge_merges_objects_and_replaces_everything_else() {
+        let Value::Object(mut target) = json!({
+            "dateRange": { "type": "FixedDateTimeRange", "start": "a", "end": "b" },
+            "attendees": [{ "email": "x@y.com" }],
+            "title": "Sync"
+        }) else {
+            unreachable!()
+        };
+        let Value::Object(updates) = json!({
+            "dateRange": { "end": "c" },
+            "attendees": [],
+            "title": null
+        }) else {
+            unreachable!()
+        };
+
+        deep_merge(&mut target, updates);
+        assert_eq!(
+            Value::Object(target),
+            json!({
+                "dateRange": { "type": "FixedDateTimeRange", "start": "a", "end": "c" },
+                "attendees": [],
+                "title": null
+            })
+        );
+    }
+}
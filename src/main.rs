@@ -1,23 +1,60 @@
+mod batch;
+mod cache;
 mod cli;
+mod completion;
+mod config;
+mod credentials;
 mod dashboard;
+mod dashboard_agenda;
+mod dashboard_command;
+mod datetime;
+mod diff;
+mod duration;
+mod edit;
 mod error;
+mod extract;
+mod fields;
+mod output;
 mod reclaim_api;
+mod retry;
+mod task_query;
+#[cfg(test)]
+mod test_support;
 
-use clap::Parser;
+use cache::{CacheMode, CachedApi, TaskCache};
+use clap::{ArgMatches, CommandFactory, FromArgMatches};
 use cli::{
-    Cli, Command, EventsApplyArgs, EventsCommand, EventsCreateArgs, EventsDeleteArgs,
-    EventsUpdateArgs, OutputFormat, PatchArgs, PutArgs, TaskStatusFilter,
+    AuthCommand, AuthLoginArgs, Cli, Command, ConfigCommand, ConfigKey, CreateArgs,
+    EventsApplyArgs, EventsCommand, EventsCreateArgs, EventsDeleteArgs, EventsUpdateArgs,
+    OutputFormat, PatchArgs, PutArgs, TaskStatusFilter,
 };
+use config::{ConfigStore, SettingSource, Settings, DEFAULT_POLICY_ID};
+use credentials::CredentialStore;
+use datetime::TimeContext;
 use error::CliError;
+use extract::Extract;
+use fields::{FieldUpdate, UnsetMode};
+use output::Printer;
 use reclaim_api::{
-    CreateTaskRequest, EventListQuery, HttpReclaimApi, ReclaimApi, Task, TaskFilter,
+    CreateTaskRequest, Event, EventListQuery, HttpReclaimApi, Outcome, PlannedRequest,
+    PlannerAction, ReclaimApi, Task, TaskFilter, TraceLevel,
 };
 use serde_json::{json, Map, Value};
-use std::process::ExitCode;
+use std::{
+    io::{IsTerminal, Read},
+    process::ExitCode,
+};
+use task_query::{SortKey, TaskQuery};
 
-#[tokio::main]
-async fn main() -> ExitCode {
-    match run().await {
+fn main() -> ExitCode {
+    // Completion requests from the shell script exit here, before any runtime threads start.
+    completion::complete_from_env();
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("Failed building the Runtime");
+    match runtime.block_on(run()) {
         Ok(()) => ExitCode::SUCCESS,
         Err(error) => {
             eprintln!("Error: {error}");
@@ -30,24 +67,126 @@ async fn main() -> ExitCode {
 }
 
 async fn run() -> Result<(), CliError> {
-    let cli = Cli::parse();
-    let format = cli.format;
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+    let mut store = ConfigStore::load(cli.config_path.as_deref())?;
+    let is_config_command = matches!(cli.command, Command::Config(_) | Command::Auth(_));
+    let settings =
+        Settings::resolve_with_stored_key(&cli, &matches, &store, !is_config_command, |error| {
+            eprintln!("Warning: ignoring stored credentials. {error}");
+            if let Some(hint) = error.hint() {
+                eprintln!("Hint: {hint}");
+            }
+        })?;
+    let credentials = CredentialStore::new(store.dir());
+    let printer = Printer::new(
+        settings.format,
+        Extract::from_flags(cli.template.as_deref(), cli.query.as_deref())?,
+    );
+    let time = TimeContext::new(settings.timezone);
     let command = cli.command;
 
-    let api = HttpReclaimApi::new(cli.api_key, cli.base_url, cli.timeout_secs)?;
+    if let Command::Config(args) = command {
+        return run_config_command(
+            args.command,
+            &mut store,
+            &settings,
+            &printer,
+            cli.profile.as_deref(),
+        );
+    }
+    if let Command::Auth(args) = command {
+        return run_auth_command(
+            args.command,
+            &mut store,
+            &credentials,
+            &settings,
+            &printer,
+            cli.profile.as_deref(),
+        )
+        .await;
+    }
 
+    if let Command::Completions(args) = command {
+        return completion::print_registration(args.shell);
+    }
+
+    let http = HttpReclaimApi::new(
+        settings.api_key.clone(),
+        settings.base_url.clone(),
+        settings.timeout_secs,
+        settings.retry_policy(),
+    )?
+    .with_trace(settings.trace)
+    .with_dry_run(settings.dry_run);
+    let cache = match TaskCache::open(settings.profile.as_deref(), &settings.base_url) {
+        Ok(cache) => cache,
+        // Cron jobs and containers may have no cache directory; only the cache flags need one.
+        Err(_) if matches!(settings.cache_mode, CacheMode::Online { max_age } if max_age.is_zero()) => {
+            TaskCache::in_memory(&settings.base_url)
+        }
+        Err(error) => return Err(error),
+    };
+    let api = CachedApi::new(http, cache, settings.cache_mode)
+        .with_stale_fallback(!matches!(command, Command::Dashboard(_)));
+
+    run_api_command(
+        command,
+        subcommand_matches(&matches),
+        &api,
+        &settings,
+        &printer,
+        &time,
+    )
+    .await
+}
+
+/// The parsed arguments of the innermost subcommand (`events update`, not `events`).
+fn subcommand_matches(matches: &ArgMatches) -> &ArgMatches {
+    match matches.subcommand() {
+        Some((_, subcommand)) => subcommand_matches(subcommand),
+        None => matches,
+    }
+}
+
+/// Runs a command that talks to Reclaim through `api`. `matches` are the subcommand's
+/// parsed arguments, for the order of `--set`, `--set-string` and `--unset`.
+async fn run_api_command(
+    command: Command,
+    matches: &ArgMatches,
+    api: &impl ReclaimApi,
+    settings: &Settings,
+    printer: &Printer,
+    time: &TimeContext,
+) -> Result<(), CliError> {
     match command {
         Command::List(args) => {
+            let query = TaskQuery::from_args(&args.query, time)?;
+            let sort_keys = SortKey::parse_list(&args.sort)?;
+            let fields = task_query::parse_fields(&args.fields)?;
             let mut tasks = api.list_tasks(TaskFilter::All).await?;
             apply_task_status_filter(&mut tasks, args.filter);
+            query.apply(&mut tasks);
+            task_query::sort_tasks(&mut tasks, &sort_keys);
+            if let Some(limit) = args.limit {
+                tasks.truncate(limit);
+            }
 
-            match format {
-                OutputFormat::Json => print_json(&tasks)?,
-                OutputFormat::Human => print_task_list_human(&tasks, args.filter),
+            match printer.format {
+                OutputFormat::Human if fields.is_empty() => {
+                    print_task_list_human(&tasks, args.filter)
+                }
+                OutputFormat::Human => print_task_fields_human(&tasks, &fields),
+                _ if fields.is_empty() => printer.print_collection(&tasks, || {
+                    task_table(&tasks, &TASK_TABLE_COLUMNS.map(String::from))
+                })?,
+                _ => printer.print_records(&task_query::select_fields(&tasks, &fields), || {
+                    task_table(&tasks, &fields)
+                })?,
             }
         }
         Command::Dashboard(args) => {
-            if matches!(format, OutputFormat::Json) {
+            if printer.format != OutputFormat::Human {
                 return Err(CliError::InvalidInput {
                     message:
                         "The dashboard is an interactive TUI and only supports --format human."
@@ -55,51 +194,131 @@ async fn run() -> Result<(), CliError> {
                     hint: Some("Run: reclaim dashboard".to_string()),
                 });
             }
+            if settings.trace != TraceLevel::Off && std::io::stderr().is_terminal() {
+                return Err(CliError::InvalidInput {
+                    message: "--verbose/--trace output would draw over the dashboard.".to_string(),
+                    hint: Some(
+                        "Redirect stderr to a file, e.g. reclaim dashboard --trace 2> trace.log"
+                            .to_string(),
+                    ),
+                });
+            }
 
-            dashboard::run_dashboard(&api, args.all).await?;
+            let query = TaskQuery::from_args(&args.query, time)?;
+            dashboard::run_dashboard(api, args.all, query, *time, settings.event_category).await?;
         }
         Command::Get(args) => {
             let task = api.get_task(args.task_id).await?;
 
-            match format {
-                OutputFormat::Json => print_json(&task)?,
+            match printer.format {
                 OutputFormat::Human => print_task_human(&task),
+                _ => printer.print_value(&task)?,
             }
         }
         Command::Put(args) => {
-            let request = build_put_payload(&api, &args).await?;
-            let updated = api
+            let updates = task_updates(
+                &args.set,
+                &args.set_string,
+                &args.unset,
+                Some(matches),
+                time,
+            )?;
+            let show_diff = shows_diff(printer, settings, args.diff);
+            let existing = if args.json.is_none() || show_diff {
+                Some(api.get_task(args.task_id).await?)
+            } else {
+                None
+            };
+            let request = build_put_payload(&args, updates, existing.as_ref())?;
+            let outcome = api
                 .put_task(args.task_id, request, args.notification_key.as_deref())
                 .await?;
+            let Some(updated) = sent(printer, outcome)? else {
+                return Ok(());
+            };
 
-            match format {
-                OutputFormat::Json => print_json(&updated)?,
-                OutputFormat::Human => print_mutation_human("Updated (PUT)", &updated),
+            match existing.filter(|_| show_diff) {
+                Some(before) => print_task_diff(printer, "Updated (PUT)", &before, &updated)?,
+                None => match printer.format {
+                    OutputFormat::Human => print_mutation_human("Updated (PUT)", &updated),
+                    _ => printer.print_value(&updated)?,
+                },
             }
         }
         Command::Patch(args) => {
-            let request = build_patch_payload(&args)?;
-            let updated = api
+            let updates = task_updates(
+                &args.set,
+                &args.set_string,
+                &args.unset,
+                Some(matches),
+                time,
+            )?;
+            let show_diff = shows_diff(printer, settings, args.diff);
+            let current = if show_diff || fields::reaches_below_top_level(&updates) {
+                Some(api.get_task(args.task_id).await?)
+            } else {
+                None
+            };
+            let request = build_patch_payload(&args, updates, current.as_ref())?;
+            let before = current.filter(|_| show_diff);
+            let outcome = api
                 .patch_task(args.task_id, request, args.notification_key.as_deref())
                 .await?;
+            let Some(updated) = sent(printer, outcome)? else {
+                return Ok(());
+            };
+
+            match before {
+                Some(before) => print_task_diff(printer, "Updated (PATCH)", &before, &updated)?,
+                None => match printer.format {
+                    OutputFormat::Human => print_mutation_human("Updated (PATCH)", &updated),
+                    _ => printer.print_value(&updated)?,
+                },
+            }
+        }
+        Command::Edit(args) => {
+            let task = api.get_task(args.task_id).await?;
+            let Some(patch) = edit::edit_task(&task, time)? else {
+                match printer.format {
+                    OutputFormat::Human => println!("No changes to task #{}.", task.id),
+                    _ => printer.print_value(&task)?,
+                }
+                return Ok(());
+            };
+            let outcome = api
+                .patch_task(
+                    args.task_id,
+                    Value::Object(patch),
+                    args.notification_key.as_deref(),
+                )
+                .await?;
+            let Some(updated) = sent(printer, outcome)? else {
+                return Ok(());
+            };
 
-            match format {
-                OutputFormat::Json => print_json(&updated)?,
-                OutputFormat::Human => print_mutation_human("Updated (PATCH)", &updated),
+            if shows_diff(printer, settings, args.diff) {
+                print_task_diff(printer, "Updated (PATCH)", &task, &updated)?;
+            } else {
+                match printer.format {
+                    OutputFormat::Human => print_mutation_human("Updated (PATCH)", &updated),
+                    _ => printer.print_value(&updated)?,
+                }
             }
         }
         Command::Delete(args) => {
-            let api_response = api
+            let outcome = api
                 .delete_task(args.task_id, args.notification_key.as_deref())
                 .await?;
+            let Some(api_response) = sent(printer, outcome)? else {
+                return Ok(());
+            };
             let result = DeleteTaskOutput {
                 task_id: args.task_id,
                 deleted: true,
                 api_response,
             };
 
-            match format {
-                OutputFormat::Json => print_json(&result)?,
+            match printer.format {
                 OutputFormat::Human => {
                     println!("Deleted task #{}.", result.task_id);
                     if !result.api_response.is_null() {
@@ -113,23 +332,88 @@ async fn run() -> Result<(), CliError> {
                         println!("API response:\n{rendered}");
                     }
                 }
+                _ => printer.print_value(&result)?,
             }
         }
+        Command::Done(args) => {
+            let outcome = api
+                .run_planner_action(args.task_id, PlannerAction::Done)
+                .await?;
+            let Some(task) = sent(printer, outcome)? else {
+                return Ok(());
+            };
+            print_task_mutation(printer, "Completed", &task)?;
+        }
+        Command::Start(args) => {
+            let outcome = api
+                .run_planner_action(args.task_id, PlannerAction::Start)
+                .await?;
+            let Some(task) = sent(printer, outcome)? else {
+                return Ok(());
+            };
+            print_task_mutation(printer, "Started", &task)?;
+        }
+        Command::Stop(args) => {
+            let outcome = api
+                .run_planner_action(args.task_id, PlannerAction::Stop)
+                .await?;
+            let Some(task) = sent(printer, outcome)? else {
+                return Ok(());
+            };
+            print_task_mutation(printer, "Stopped", &task)?;
+        }
+        Command::Reopen(args) => {
+            let outcome = api
+                .run_planner_action(args.task_id, PlannerAction::Reopen)
+                .await?;
+            let Some(task) = sent(printer, outcome)? else {
+                return Ok(());
+            };
+            print_task_mutation(printer, "Reopened", &task)?;
+        }
+        Command::Snooze(args) => {
+            let until = datetime::normalize_timestamp(time, "--until", &args.until)?;
+            let Some(task) = sent(printer, api.snooze_task(args.task_id, &until).await?)? else {
+                return Ok(());
+            };
+            match printer.format {
+                OutputFormat::Human => {
+                    print_mutation_human("Snoozed", &task);
+                    println!("Until: {until}");
+                }
+                _ => printer.print_value(&task)?,
+            }
+        }
+        Command::Log(args) => {
+            let Some(task) = sent(printer, api.log_work(args.task_id, args.minutes).await?)? else {
+                return Ok(());
+            };
+            let prefix = format!("Logged {} on", duration::format_minutes(args.minutes));
+            print_task_mutation(printer, &prefix, &task)?;
+        }
         Command::Events(args) => match args.command {
             EventsCommand::List(event_args) => {
                 let query = EventListQuery {
                     calendar_ids: event_args.calendar_ids,
                     all_connected: event_args.all_connected.then_some(true),
-                    start: event_args.start,
-                    end: event_args.end,
+                    start: event_args
+                        .start
+                        .as_deref()
+                        .map(|start| datetime::normalize_date(time, "--start", start))
+                        .transpose()?,
+                    end: event_args
+                        .end
+                        .as_deref()
+                        .map(|end| datetime::normalize_date(time, "--end", end))
+                        .transpose()?,
                     source_details: event_args.source_details.then_some(true),
                     thin: event_args.thin.then_some(true),
                 };
                 let events = api.list_events(query).await?;
 
-                match format {
-                    OutputFormat::Json => print_json(&events)?,
+                match printer.format {
                     OutputFormat::Human => print_events_list_human(&events),
+                    _ => printer.print_collection(&events, || event_table(&events))?,
                 }
             }
             EventsCommand::Get(event_args) => {
@@ -142,173 +426,658 @@ async fn run() -> Result<(), CliError> {
                     )
                     .await?;
 
-                match format {
-                    OutputFormat::Json => print_json(&event)?,
+                match printer.format {
                     OutputFormat::Human => print_event_human(&event)?,
+                    _ => printer.print_value(&event)?,
                 }
             }
-            EventsCommand::Create(event_args) => {
-                let request = build_event_create_request(&event_args)?;
-                let response = api.apply_schedule_actions(request).await?;
+            EventsCommand::Create(mut event_args) => {
+                event_args.calendar_id = event_args.calendar_id.or(settings.calendar_id);
+                event_args.policy_id = event_args
+                    .policy_id
+                    .or_else(|| Some(settings.policy_id.clone()));
+                let updates = fields::parse_updates(
+                    &event_args.set,
+                    &event_args.set_string,
+                    &event_args.unset,
+                    Some(matches),
+                )?;
+                let request = build_event_create_request(&event_args, updates, time)?;
+                let outcome = api.apply_schedule_actions(request).await?;
+                let Some(response) = sent(printer, outcome)? else {
+                    return Ok(());
+                };
                 let output = EventsMutationOutput {
                     operation: "create".to_string(),
-                    calendar_id: event_args.calendar_id,
+                    calendar_id: require_calendar_id(event_args.calendar_id)?,
                     event_id: None,
                     response,
                 };
 
-                match format {
-                    OutputFormat::Json => print_json(&output)?,
+                match printer.format {
                     OutputFormat::Human => print_events_mutation_human(&output)?,
+                    _ => printer.print_value(&output)?,
                 }
             }
-            EventsCommand::Update(event_args) => {
-                let request = build_event_update_request(&event_args)?;
-                let response = api.apply_schedule_actions(request).await?;
+            EventsCommand::Update(mut event_args) => {
+                event_args.calendar_id = event_args.calendar_id.or(settings.calendar_id);
+                event_args.policy_id = event_args
+                    .policy_id
+                    .or_else(|| Some(settings.policy_id.clone()));
+                let updates = fields::parse_updates(
+                    &event_args.set,
+                    &event_args.set_string,
+                    &event_args.unset,
+                    Some(matches),
+                )?;
+                let current = if fields::reaches_below_top_level(&updates) {
+                    let calendar_id = require_calendar_id(event_args.calendar_id)?;
+                    Some(
+                        api.get_event(calendar_id, &event_args.event_id, None, None)
+                            .await?,
+                    )
+                } else {
+                    None
+                };
+                let request =
+                    build_event_update_request(&event_args, updates, current.as_ref(), time)?;
+                let outcome = api.apply_schedule_actions(request).await?;
+                let Some(response) = sent(printer, outcome)? else {
+                    return Ok(());
+                };
                 let output = EventsMutationOutput {
                     operation: "update".to_string(),
-                    calendar_id: event_args.calendar_id,
+                    calendar_id: require_calendar_id(event_args.calendar_id)?,
                     event_id: Some(event_args.event_id),
                     response,
                 };
 
-                match format {
-                    OutputFormat::Json => print_json(&output)?,
+                match printer.format {
                     OutputFormat::Human => print_events_mutation_human(&output)?,
+                    _ => printer.print_value(&output)?,
                 }
             }
-            EventsCommand::Delete(event_args) => {
+            EventsCommand::Delete(mut event_args) => {
+                event_args.calendar_id = event_args.calendar_id.or(settings.calendar_id);
+                event_args.policy_id = event_args
+                    .policy_id
+                    .or_else(|| Some(settings.policy_id.clone()));
                 let request = build_event_delete_request(&event_args)?;
-                let response = api.apply_schedule_actions(request).await?;
+                let outcome = api.apply_schedule_actions(request).await?;
+                let Some(response) = sent(printer, outcome)? else {
+                    return Ok(());
+                };
                 let output = EventsMutationOutput {
                     operation: "delete".to_string(),
-                    calendar_id: event_args.calendar_id,
+                    calendar_id: require_calendar_id(event_args.calendar_id)?,
                     event_id: Some(event_args.event_id),
                     response,
                 };
 
-                match format {
-                    OutputFormat::Json => print_json(&output)?,
+                match printer.format {
                     OutputFormat::Human => print_events_mutation_human(&output)?,
+                    _ => printer.print_value(&output)?,
                 }
             }
             EventsCommand::Apply(event_args) => {
                 let request = build_events_apply_request(&event_args)?;
-                let response = api.apply_schedule_actions(request).await?;
+                let outcome = api.apply_schedule_actions(request).await?;
+                let Some(response) = sent(printer, outcome)? else {
+                    return Ok(());
+                };
 
-                match format {
-                    OutputFormat::Json => print_json(&response)?,
+                match printer.format {
                     OutputFormat::Human => print_event_apply_human(&response)?,
+                    _ => printer.print_value(&response)?,
                 }
             }
         },
         Command::Create(args) => {
-            if let Some(due) = &args.due {
-                if due.trim().is_empty() {
-                    return Err(CliError::InvalidInput {
-                        message: "Invalid --due value: it cannot be empty.".to_string(),
-                        hint: Some(
-                            "Use ISO 8601, for example: --due 2026-02-19T15:00:00Z".to_string(),
-                        ),
-                    });
+            let due = args
+                .due
+                .as_deref()
+                .map(|due| datetime::normalize_timestamp(time, "--due", due))
+                .transpose()?;
+
+            let chunks = resolve_create_chunks(&args)?;
+
+            let request = CreateTaskRequest {
+                title: args.title,
+                notes: args.notes,
+                priority: args.priority.map(|priority| priority.as_str().to_owned()),
+                due,
+                time_chunks_required: chunks.time_chunks_required,
+                event_category: Some(
+                    args.event_category
+                        .unwrap_or(settings.event_category)
+                        .as_str()
+                        .to_owned(),
+                ),
+                min_chunk_size: chunks.min_chunk_size,
+                max_chunk_size: chunks.max_chunk_size,
+                always_private: Some(args.always_private),
+            };
+
+            let Some(created) = sent(printer, api.create_task(request).await?)? else {
+                return Ok(());
+            };
+            match printer.format {
+                OutputFormat::Human => {
+                    println!("Created task #{}: {}", created.id, created.title);
+                    if let Some(status) = created.status.as_deref() {
+                        println!("Status: {status}");
+                    }
+                    if let Some(due) = created.due.as_deref() {
+                        println!("Due: {due}");
+                    }
+                    if let Some(duration) = task_duration_summary(&created) {
+                        println!("Duration: {duration}");
+                    }
                 }
+                _ => printer.print_value(&created)?,
             }
+        }
+        Command::Batch(args) => {
+            batch::run_batch(api, &args, settings.event_category, printer, time).await?;
+        }
+        Command::Config(_) | Command::Auth(_) | Command::Completions(_) => {
+            unreachable!("config, auth and completions are handled before the API client")
+        }
+    }
 
-            if (args.min_chunk_size.is_some() || args.max_chunk_size.is_some())
-                && args.time_chunks_required.is_none()
-            {
-                return Err(CliError::InvalidInput {
-                    message: "Invalid chunk options: --min-chunk-size/--max-chunk-size require --time-chunks-required."
-                        .to_string(),
-                    hint: Some(
-                        "Pass --time-chunks-required with chunk size options, e.g. --time-chunks-required 4 --min-chunk-size 2 --max-chunk-size 4"
-                            .to_string(),
-                    ),
-                });
+    Ok(())
+}
+
+/// Returns what a write sent back, or prints the request `--dry-run` kept back and returns
+/// `None` so the command stops there.
+fn sent<T>(printer: &Printer, outcome: Outcome<T>) -> Result<Option<T>, CliError> {
+    match outcome {
+        Outcome::Sent(value) => Ok(Some(value)),
+        Outcome::Planned(request) => {
+            print_dry_run(printer, request)?;
+            Ok(None)
+        }
+    }
+}
+
+fn print_dry_run(printer: &Printer, request: PlannedRequest) -> Result<(), CliError> {
+    match printer.format {
+        OutputFormat::Human => {
+            println!("Dry run: nothing was sent.");
+            println!("{} {}", request.method, request.url);
+            if let Some(body) = request.body.as_ref() {
+                output::print_json(body)?;
             }
+            Ok(())
+        }
+        _ => printer.print_value(&DryRunOutput {
+            dry_run: true,
+            request,
+        }),
+    }
+}
 
-            let mut min_chunk_size = args.min_chunk_size;
-            let mut max_chunk_size = args.max_chunk_size;
+fn run_config_command(
+    command: ConfigCommand,
+    store: &mut ConfigStore,
+    settings: &Settings,
+    printer: &Printer,
+    explicit_profile: Option<&str>,
+) -> Result<(), CliError> {
+    match command {
+        ConfigCommand::Show => {
+            let output = ConfigShowOutput::new(store, settings);
+            match printer.format {
+                OutputFormat::Human => print_config_show_human(&output),
+                _ => printer.print_value(&output)?,
+            }
+        }
+        ConfigCommand::Set(args) => {
+            let profile = store.target_profile_name(explicit_profile);
+            store.set(&profile, args.key, &args.value)?;
+            store.save()?;
 
-            if let Some(total_chunks) = args.time_chunks_required {
-                if min_chunk_size.is_none() {
-                    min_chunk_size = Some(1);
-                }
-                if max_chunk_size.is_none() {
-                    max_chunk_size = Some(total_chunks);
+            match printer.format {
+                OutputFormat::Human => {
+                    if args.key == ConfigKey::DefaultProfile {
+                        println!("Set default_profile in {}.", store.path.display());
+                    } else {
+                        println!(
+                            "Set {} in profile '{profile}' ({}).",
+                            args.key.as_str(),
+                            store.path.display()
+                        );
+                    }
                 }
+                _ => printer.print_value(&json!({
+                    "path": store.path,
+                    "profile": profile,
+                    "key": args.key.as_str(),
+                    "updated": true,
+                }))?,
+            }
+        }
+        ConfigCommand::Unset(args) => {
+            let profile = store.target_profile_name(explicit_profile);
+            let removed = store.unset(&profile, args.key);
+            if removed {
+                store.save()?;
+            }
 
-                if let Some(min) = min_chunk_size {
-                    if min > total_chunks {
-                        return Err(CliError::InvalidInput {
-                            message: format!(
-                                "Invalid --min-chunk-size value: {min} exceeds --time-chunks-required ({total_chunks})."
-                            ),
-                            hint: Some(
-                                "Use a min chunk size less than or equal to --time-chunks-required."
-                                    .to_string(),
-                            ),
-                        });
+            match printer.format {
+                OutputFormat::Human => {
+                    if removed {
+                        println!("Removed {} from profile '{profile}'.", args.key.as_str());
+                    } else {
+                        println!(
+                            "{} was not set in profile '{profile}'; nothing changed.",
+                            args.key.as_str()
+                        );
                     }
                 }
+                _ => printer.print_value(&json!({
+                    "path": store.path,
+                    "profile": profile,
+                    "key": args.key.as_str(),
+                    "updated": removed,
+                }))?,
+            }
+        }
+        ConfigCommand::Profiles => {
+            let active = store.active_profile_name(explicit_profile);
+            let profiles = store
+                .file
+                .profiles
+                .keys()
+                .map(|name| ConfigProfileEntry {
+                    name: name.clone(),
+                    default: store.file.default_profile.as_deref() == Some(name.as_str()),
+                    active: active.as_deref() == Some(name.as_str()),
+                })
+                .collect::<Vec<_>>();
+
+            match printer.format {
+                OutputFormat::Human => {
+                    if profiles.is_empty() {
+                        println!("No profiles in {}.", store.path.display());
+                        println!(
+                            "Create one with: reclaim config set api-key <KEY> --profile work"
+                        );
+                    }
+                    for profile in &profiles {
+                        let marker = if profile.active { "*" } else { " " };
+                        let default = if profile.default { " (default)" } else { "" };
+                        println!("{marker} {}{default}", profile.name);
+                    }
+                }
+                _ => printer.print_value(&profiles)?,
+            }
+        }
+    }
 
-                if let Some(max) = max_chunk_size {
-                    if max > total_chunks {
-                        return Err(CliError::InvalidInput {
-                            message: format!(
-                                "Invalid --max-chunk-size value: {max} exceeds --time-chunks-required ({total_chunks})."
-                            ),
-                            hint: Some(
-                                "Use a max chunk size less than or equal to --time-chunks-required."
-                                    .to_string(),
-                            ),
-                        });
+    Ok(())
+}
+
+async fn run_auth_command(
+    command: AuthCommand,
+    store: &mut ConfigStore,
+    credentials: &CredentialStore,
+    settings: &Settings,
+    printer: &Printer,
+    explicit_profile: Option<&str>,
+) -> Result<(), CliError> {
+    let profile = store.target_profile_name(explicit_profile);
+
+    match command {
+        AuthCommand::Login(args) => {
+            let api_key = read_login_api_key(&args)?;
+            credentials.check_backend(&profile, args.store)?;
+            let api = HttpReclaimApi::new(
+                Some(api_key.clone()),
+                settings.base_url.clone(),
+                settings.timeout_secs,
+                settings.retry_policy(),
+            )?
+            .with_trace(settings.trace);
+            let user = api.current_user().await.map_err(|error| match error {
+                CliError::Api {
+                    status: 401 | 403, ..
+                } => CliError::InvalidInput {
+                    message: "Reclaim rejected this API key; nothing was stored.".to_string(),
+                    hint: Some(
+                        "Copy a fresh key from Reclaim settings and run reclaim auth login again."
+                            .to_string(),
+                    ),
+                },
+                other => other,
+            })?;
+
+            credentials.save(&profile, &api_key, args.store)?;
+            if store.ensure_profile(&profile) {
+                store.save()?;
+            }
+
+            let output = AuthStatusOutput {
+                profile: profile.clone(),
+                source: match args.store {
+                    cli::CredentialBackend::File => SettingSource::CredentialsFile,
+                    cli::CredentialBackend::Keyring => SettingSource::Keyring,
+                },
+                api_key: Some(config::redact_secret(&api_key)),
+                valid: Some(true),
+                user: Some(user),
+                error: None,
+            };
+
+            match printer.format {
+                OutputFormat::Human => {
+                    println!(
+                        "Logged in as {} (profile '{profile}').",
+                        output
+                            .user
+                            .as_ref()
+                            .and_then(|user| user.email.as_deref().or(user.name.as_deref()))
+                            .unwrap_or("<unknown user>")
+                    );
+                    match args.store {
+                        cli::CredentialBackend::File => {
+                            println!("Stored key in {}.", credentials.path().display())
+                        }
+                        cli::CredentialBackend::Keyring => {
+                            println!("Stored key in the OS keyring.")
+                        }
+                    }
+                    if matches!(
+                        settings.source(ConfigKey::ApiKey),
+                        SettingSource::Flag | SettingSource::Env
+                    ) {
+                        println!(
+                            "Note: --api-key/RECLAIM_API_KEY is set and still takes precedence over the stored key."
+                        );
                     }
                 }
+                _ => printer.print_value(&output)?,
             }
+        }
+        AuthCommand::Logout => {
+            let credentials::Removal {
+                removed,
+                mut errors,
+            } = credentials.remove(&profile);
 
-            if let (Some(min), Some(max)) = (min_chunk_size, max_chunk_size) {
-                if min > max {
-                    return Err(CliError::InvalidInput {
-                        message: format!(
-                            "Invalid chunk bounds: --min-chunk-size ({min}) cannot exceed --max-chunk-size ({max})."
-                        ),
-                        hint: Some("Choose chunk sizes where min <= max.".to_string()),
-                    });
+            match printer.format {
+                OutputFormat::Human => {
+                    if removed.is_empty() && errors.is_empty() {
+                        println!("No stored API key for profile '{profile}'; nothing changed.");
+                    } else if !removed.is_empty() {
+                        let sources = removed
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        println!("Removed stored API key for profile '{profile}' ({sources}).");
+                    }
+                    if settings.source(ConfigKey::ApiKey) == SettingSource::Profile {
+                        println!(
+                            "Note: the profile still has a plaintext api_key in the config file."
+                        );
+                    }
                 }
+                _ => printer.print_value(&json!({
+                    "profile": profile,
+                    "removed": removed,
+                }))?,
             }
 
-            let request = CreateTaskRequest {
-                title: args.title,
-                notes: args.notes,
-                priority: args.priority.map(|priority| priority.as_str().to_owned()),
-                due: args.due,
-                time_chunks_required: args.time_chunks_required,
-                event_category: Some(args.event_category.as_str().to_owned()),
-                min_chunk_size,
-                max_chunk_size,
-                always_private: Some(args.always_private),
+            // Report every backend that could not be cleaned; the last one sets the exit code.
+            if let Some(last) = errors.pop() {
+                for error in errors {
+                    eprintln!("Warning: {error}");
+                }
+                return Err(last);
+            }
+        }
+        AuthCommand::Status => {
+            let source = settings.source(ConfigKey::ApiKey);
+            let mut output = AuthStatusOutput {
+                profile,
+                source,
+                api_key: settings.api_key.as_deref().map(config::redact_secret),
+                valid: None,
+                user: None,
+                error: None,
             };
 
-            let created = api.create_task(request).await?;
-            match format {
-                OutputFormat::Json => print_json(&created)?,
-                OutputFormat::Human => {
-                    println!("Created task #{}: {}", created.id, created.title);
-                    if let Some(status) = created.status.as_deref() {
-                        println!("Status: {status}");
+            if settings.api_key.is_some() {
+                let api = HttpReclaimApi::new(
+                    settings.api_key.clone(),
+                    settings.base_url.clone(),
+                    settings.timeout_secs,
+                    settings.retry_policy(),
+                )?
+                .with_trace(settings.trace);
+                match api.current_user().await {
+                    Ok(user) => {
+                        output.valid = Some(true);
+                        output.user = Some(user);
                     }
-                    if let Some(due) = created.due.as_deref() {
-                        println!("Due: {due}");
+                    Err(CliError::Api {
+                        status: 401 | 403, ..
+                    }) => output.valid = Some(false),
+                    Err(error) => {
+                        output.error = error.to_string().lines().next().map(str::to_string);
                     }
                 }
             }
+
+            match printer.format {
+                OutputFormat::Human => print_auth_status_human(&output, credentials),
+                _ => printer.print_value(&output)?,
+            }
         }
     }
 
     Ok(())
 }
 
+fn read_login_api_key(args: &AuthLoginArgs) -> Result<String, CliError> {
+    let raw = if args.with_token {
+        let mut buffer = String::new();
+        std::io::stdin()
+            .read_to_string(&mut buffer)
+            .map_err(|error| CliError::InvalidInput {
+                message: format!("Could not read the API key from stdin: {error}"),
+                hint: Some("Example: echo \"$KEY\" | reclaim auth login --with-token".to_string()),
+            })?;
+        buffer
+    } else {
+        if !std::io::stdin().is_terminal() {
+            return Err(CliError::InvalidInput {
+                message: "Cannot prompt for an API key: stdin is not a terminal.".to_string(),
+                hint: Some(
+                    "Pipe the key instead: reclaim auth login --with-token < key.txt".to_string(),
+                ),
+            });
+        }
+        rpassword::prompt_password("Reclaim API key: ").map_err(|error| CliError::InvalidInput {
+            message: format!("Could not read the API key: {error}"),
+            hint: Some("Retry, or pass the key with --with-token on stdin.".to_string()),
+        })?
+    };
+
+    let api_key = raw.trim().to_string();
+    if api_key.is_empty() {
+        return Err(CliError::InvalidInput {
+            message: "The API key cannot be empty.".to_string(),
+            hint: Some("You can find your key in Reclaim settings.".to_string()),
+        });
+    }
+
+    Ok(api_key)
+}
+
+#[derive(Debug, serde::Serialize)]
+struct AuthStatusOutput {
+    profile: String,
+    source: SettingSource,
+    api_key: Option<String>,
+    valid: Option<bool>,
+    user: Option<reclaim_api::CurrentUser>,
+    error: Option<String>,
+}
+
+fn print_auth_status_human(output: &AuthStatusOutput, credentials: &CredentialStore) {
+    println!("Profile: {}", output.profile);
+
+    let Some(api_key) = output.api_key.as_deref() else {
+        println!("Not logged in: no API key found.");
+        println!("Run: reclaim auth login");
+        return;
+    };
+
+    match output.source {
+        SettingSource::CredentialsFile => println!(
+            "Key: {api_key} (from credentials file {})",
+            credentials.path().display()
+        ),
+        SettingSource::Flag => println!("Key: {api_key} (from --api-key)"),
+        SettingSource::Env => println!("Key: {api_key} (from RECLAIM_API_KEY)"),
+        SettingSource::Profile => println!("Key: {api_key} (from config profile, plaintext)"),
+        source => println!("Key: {api_key} (from {source})"),
+    }
+
+    match (output.valid, output.user.as_ref(), output.error.as_deref()) {
+        (Some(true), Some(user), _) => println!(
+            "Valid: yes, signed in as {}",
+            user.email
+                .as_deref()
+                .or(user.name.as_deref())
+                .unwrap_or("<unknown user>")
+        ),
+        (Some(false), _, _) => println!("Valid: no, Reclaim rejected this key."),
+        (_, _, Some(error)) => println!("Valid: unknown ({error})"),
+        _ => println!("Valid: unknown"),
+    }
+}
+
+fn require_calendar_id(calendar_id: Option<u64>) -> Result<u64, CliError> {
+    calendar_id.ok_or_else(|| CliError::InvalidInput {
+        message: "Missing --calendar-id and no calendar_id is set in the active profile."
+            .to_string(),
+        hint: Some(
+            "Pass --calendar-id <ID>, or set a default with: reclaim config set calendar-id <ID>"
+                .to_string(),
+        ),
+    })
+}
+
+#[derive(Debug, serde::Serialize)]
+struct ConfigProfileEntry {
+    name: String,
+    default: bool,
+    active: bool,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct ConfigSettingOutput {
+    key: &'static str,
+    value: Option<Value>,
+    source: config::SettingSource,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct ConfigShowOutput {
+    path: std::path::PathBuf,
+    exists: bool,
+    profile: Option<String>,
+    settings: Vec<ConfigSettingOutput>,
+}
+
+impl ConfigShowOutput {
+    fn new(store: &ConfigStore, settings: &Settings) -> Self {
+        let entries = [
+            (
+                ConfigKey::ApiKey,
+                settings
+                    .api_key
+                    .as_deref()
+                    .map(|key| json!(config::redact_secret(key))),
+            ),
+            (ConfigKey::BaseUrl, Some(json!(settings.base_url))),
+            (ConfigKey::TimeoutSecs, Some(json!(settings.timeout_secs))),
+            (ConfigKey::Retries, Some(json!(settings.retries))),
+            (
+                ConfigKey::MaxRetryWait,
+                Some(json!(settings.max_retry_wait)),
+            ),
+            (ConfigKey::Format, Some(json!(settings.format.as_str()))),
+            (
+                ConfigKey::EventCategory,
+                Some(json!(settings.event_category.as_str())),
+            ),
+            (ConfigKey::PolicyId, Some(json!(settings.policy_id))),
+            (
+                ConfigKey::CalendarId,
+                settings.calendar_id.map(|id| json!(id)),
+            ),
+            (
+                ConfigKey::Timezone,
+                Some(json!(settings.timezone.to_string())),
+            ),
+        ];
+
+        Self {
+            path: store.path.clone(),
+            exists: store.exists,
+            profile: settings.profile.clone(),
+            settings: entries
+                .into_iter()
+                .map(|(key, value)| ConfigSettingOutput {
+                    key: key.as_str(),
+                    value,
+                    source: settings.source(key),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn print_config_show_human(output: &ConfigShowOutput) {
+    println!(
+        "Config file: {}{}",
+        output.path.display(),
+        if output.exists {
+            ""
+        } else {
+            " (not created yet)"
+        }
+    );
+    println!(
+        "Profile: {}",
+        output
+            .profile
+            .as_deref()
+            .unwrap_or("<none, using built-in defaults>")
+    );
+    println!();
+
+    for setting in &output.settings {
+        let value = match setting.value.as_ref() {
+            Some(Value::String(text)) => text.clone(),
+            Some(value) => value.to_string(),
+            None => "-".to_string(),
+        };
+        println!("{:<15} {value} ({})", setting.key, setting.source);
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+struct DryRunOutput {
+    dry_run: bool,
+    #[serde(flatten)]
+    request: PlannedRequest,
+}
+
 #[derive(Debug, serde::Serialize)]
 struct DeleteTaskOutput {
     task_id: u64,
@@ -326,7 +1095,7 @@ struct EventsMutationOutput {
 }
 
 fn build_events_apply_request(args: &EventsApplyArgs) -> Result<Value, CliError> {
-    let request = Value::Object(parse_json_object_argument(&args.json, "--json")?);
+    let request = Value::Object(fields::parse_json_object(&args.json, "--json")?);
     let has_actions = request
         .get("actionsTaken")
         .and_then(|value| value.as_array())
@@ -348,20 +1117,19 @@ fn build_events_apply_request(args: &EventsApplyArgs) -> Result<Value, CliError>
     Ok(request)
 }
 
-fn build_event_create_request(args: &EventsCreateArgs) -> Result<Value, CliError> {
-    let start = args.start.trim();
-    let end = args.end.trim();
-    if start.is_empty() || end.is_empty() {
-        return Err(CliError::InvalidInput {
-            message: "Invalid event time range: --start and --end are required.".to_string(),
-            hint: Some(
-                "Use ISO 8601 timestamps, e.g. --start 2026-02-21T18:30:00Z --end 2026-02-21T19:00:00Z"
-                    .to_string(),
-            ),
-        });
-    }
+fn build_event_create_request(
+    args: &EventsCreateArgs,
+    updates: Vec<FieldUpdate>,
+    time: &TimeContext,
+) -> Result<Value, CliError> {
+    let (start, end) = normalize_event_range(time, &args.start, &args.end)?;
 
-    let policy_id = args.policy_id.trim();
+    let calendar_id = require_calendar_id(args.calendar_id)?;
+    let policy_id = args
+        .policy_id
+        .as_deref()
+        .unwrap_or(DEFAULT_POLICY_ID)
+        .trim();
     if policy_id.is_empty() {
         return Err(CliError::InvalidInput {
             message: "Invalid --policy-id value: it cannot be empty.".to_string(),
@@ -380,7 +1148,7 @@ fn build_event_create_request(args: &EventsCreateArgs) -> Result<Value, CliError
     action.insert("hash".to_string(), Value::String(String::new()));
     action.insert("policyId".to_string(), Value::String(policy_id.to_string()));
     action.insert("eventKey".to_string(), Value::String(String::new()));
-    action.insert("calendarId".to_string(), json!(args.calendar_id));
+    action.insert("calendarId".to_string(), json!(calendar_id));
     action.insert("title".to_string(), Value::String(args.title.clone()));
     action.insert(
         "dateRange".to_string(),
@@ -450,17 +1218,27 @@ fn build_event_create_request(args: &EventsCreateArgs) -> Result<Value, CliError
     }
 
     if let Some(raw_json) = args.json.as_deref() {
-        let updates = parse_json_object_argument(raw_json, "--json")?;
-        merge_object_fields(&mut action, updates);
+        fields::deep_merge(&mut action, fields::parse_json_object(raw_json, "--json")?);
     }
-    let updates = parse_set_entries(&args.set)?;
-    merge_object_fields(&mut action, updates);
+    fields::apply_updates(&mut action, updates, UnsetMode::Remove)?;
 
     Ok(json!({ "actionsTaken": [Value::Object(action)] }))
 }
 
-fn build_event_update_request(args: &EventsUpdateArgs) -> Result<Value, CliError> {
-    let policy_id = args.policy_id.trim();
+/// Builds the `UpdateEventAction`. `current` is the event as it is now, needed when
+/// `updates` reach below a top-level field (`dateRange.start`, `attendees[+]`).
+fn build_event_update_request(
+    args: &EventsUpdateArgs,
+    updates: Vec<FieldUpdate>,
+    current: Option<&Event>,
+    time: &TimeContext,
+) -> Result<Value, CliError> {
+    let calendar_id = require_calendar_id(args.calendar_id)?;
+    let policy_id = args
+        .policy_id
+        .as_deref()
+        .unwrap_or(DEFAULT_POLICY_ID)
+        .trim();
     if policy_id.is_empty() {
         return Err(CliError::InvalidInput {
             message: "Invalid --policy-id value: it cannot be empty.".to_string(),
@@ -489,7 +1267,7 @@ fn build_event_update_request(args: &EventsUpdateArgs) -> Result<Value, CliError
     );
     action.insert("hash".to_string(), Value::String(String::new()));
     action.insert("policyId".to_string(), Value::String(policy_id.to_string()));
-    action.insert("calendarId".to_string(), json!(args.calendar_id));
+    action.insert("calendarId".to_string(), json!(calendar_id));
     action.insert("eventId".to_string(), Value::String(args.event_id.clone()));
 
     if let Some(title) = args
@@ -539,19 +1317,7 @@ fn build_event_update_request(args: &EventsUpdateArgs) -> Result<Value, CliError
     }
 
     if let (Some(start), Some(end)) = (args.start.as_deref(), args.end.as_deref()) {
-        let start = start.trim();
-        let end = end.trim();
-        if start.is_empty() || end.is_empty() {
-            return Err(CliError::InvalidInput {
-                message: "Invalid date range update: --start and --end cannot be empty."
-                    .to_string(),
-                hint: Some(
-                    "Use ISO 8601 timestamps, e.g. --start 2026-02-21T18:30:00Z --end 2026-02-21T19:00:00Z"
-                        .to_string(),
-                ),
-            });
-        }
-
+        let (start, end) = normalize_event_range(time, start, end)?;
         action.insert(
             "dateRange".to_string(),
             json!({
@@ -563,11 +1329,12 @@ fn build_event_update_request(args: &EventsUpdateArgs) -> Result<Value, CliError
     }
 
     if let Some(raw_json) = args.json.as_deref() {
-        let updates = parse_json_object_argument(raw_json, "--json")?;
-        merge_object_fields(&mut action, updates);
+        fields::deep_merge(&mut action, fields::parse_json_object(raw_json, "--json")?);
+    }
+    if let Some(current) = current {
+        fields::seed_nested(&mut action, &event_update_fields(current)?, &updates);
     }
-    let updates = parse_set_entries(&args.set)?;
-    merge_object_fields(&mut action, updates);
+    fields::apply_updates(&mut action, updates, UnsetMode::Null)?;
 
     let has_update_fields = action.keys().any(|key| {
         !matches!(
@@ -588,8 +1355,70 @@ fn build_event_update_request(args: &EventsUpdateArgs) -> Result<Value, CliError
     Ok(json!({ "actionsTaken": [Value::Object(action)] }))
 }
 
+/// Resolves `--duration`/`--min-chunk`/`--max-chunk` (or their raw chunk-count variants).
+fn resolve_create_chunks(args: &CreateArgs) -> Result<duration::ChunkPlan, CliError> {
+    let chunks = |flag_name: &str, raw: Option<&str>, fallback: Option<u32>| {
+        raw.map(|raw| duration::parse_chunks(flag_name, raw, args.round))
+            .transpose()
+            .map(|parsed| parsed.or(fallback))
+    };
+
+    duration::plan_chunks(
+        chunks(
+            "--duration",
+            args.duration.as_deref(),
+            args.time_chunks_required,
+        )?,
+        chunks(
+            "--min-chunk",
+            args.min_chunk.as_deref(),
+            args.min_chunk_size,
+        )?,
+        chunks(
+            "--max-chunk",
+            args.max_chunk.as_deref(),
+            args.max_chunk_size,
+        )?,
+    )
+}
+
+/// Parses `--start`/`--end` into RFC 3339 UTC and checks that the range is not empty.
+fn normalize_event_range(
+    time: &TimeContext,
+    start: &str,
+    end: &str,
+) -> Result<(String, String), CliError> {
+    let start_at = time
+        .parse_instant(start)
+        .map_err(|reason| datetime::invalid_time_error("--start", start, &reason))?;
+    let end_at = time
+        .parse_instant(end)
+        .map_err(|reason| datetime::invalid_time_error("--end", end, &reason))?;
+
+    if end_at <= start_at {
+        return Err(CliError::InvalidInput {
+            message: format!(
+                "Invalid event time range: --end ({}) must be after --start ({}).",
+                datetime::format_timestamp(end_at),
+                datetime::format_timestamp(start_at)
+            ),
+            hint: Some("Example: --start \"tomorrow 10am\" --end \"tomorrow 10:30\"".to_string()),
+        });
+    }
+
+    Ok((
+        datetime::format_timestamp(start_at),
+        datetime::format_timestamp(end_at),
+    ))
+}
+
 fn build_event_delete_request(args: &EventsDeleteArgs) -> Result<Value, CliError> {
-    let policy_id = args.policy_id.trim();
+    let calendar_id = require_calendar_id(args.calendar_id)?;
+    let policy_id = args
+        .policy_id
+        .as_deref()
+        .unwrap_or(DEFAULT_POLICY_ID)
+        .trim();
     if policy_id.is_empty() {
         return Err(CliError::InvalidInput {
             message: "Invalid --policy-id value: it cannot be empty.".to_string(),
@@ -609,7 +1438,7 @@ fn build_event_delete_request(args: &EventsDeleteArgs) -> Result<Value, CliError
     action.insert("policyId".to_string(), Value::String(policy_id.to_string()));
     action.insert(
         "eventKey".to_string(),
-        Value::String(format!("{}/{}", args.calendar_id, args.event_id)),
+        Value::String(format!("{calendar_id}/{}", args.event_id)),
     );
 
     if let Some(message) = args
@@ -627,10 +1456,21 @@ fn build_event_delete_request(args: &EventsDeleteArgs) -> Result<Value, CliError
     Ok(json!({ "actionsTaken": [Value::Object(action)] }))
 }
 
-async fn build_put_payload(api: &impl ReclaimApi, args: &PutArgs) -> Result<Value, CliError> {
-    if args.json.is_none() && args.set.is_empty() {
+/// Builds the PUT body: `--json` as given, or `existing` (fetched by the caller), with the
+/// `--set` entries applied on top.
+fn build_put_payload(
+    args: &PutArgs,
+    updates: Vec<FieldUpdate>,
+    existing: Option<&Task>,
+) -> Result<Value, CliError> {
+    if args.json.is_none()
+        && args.set.is_empty()
+        && args.set_string.is_empty()
+        && args.unset.is_empty()
+    {
         return Err(CliError::InvalidInput {
-            message: "PUT requires update data. Pass --json and/or one or more --set entries."
+            message:
+                "PUT requires update data. Pass --json and/or --set, --set-string or --unset entries."
                 .to_string(),
             hint: Some(
                 "Examples: --json '{\"title\":\"Plan sprint\"}' or --set priority=P4".to_string(),
@@ -638,37 +1478,33 @@ async fn build_put_payload(api: &impl ReclaimApi, args: &PutArgs) -> Result<Valu
         });
     }
 
-    let mut payload = if let Some(raw_json) = args.json.as_deref() {
-        parse_json_object_argument(raw_json, "--json")?
-    } else {
-        let existing = api.get_task(args.task_id).await?;
-        let existing_json = serde_json::to_value(existing).map_err(|error| {
-            CliError::Output(format!(
-                "Could not serialize existing task for PUT: {error}"
-            ))
-        })?;
-
-        existing_json.as_object().cloned().ok_or_else(|| {
-            CliError::Output(
-                "Could not serialize existing task for PUT: expected object payload.".to_string(),
-            )
-        })?
+    let mut payload = match (args.json.as_deref(), existing) {
+        (Some(raw_json), _) => fields::parse_json_object(raw_json, "--json")?,
+        (None, Some(existing)) => task_fields(existing)?,
+        (None, None) => Map::new(),
     };
 
-    let updates = parse_set_entries(&args.set)?;
-    merge_object_fields(&mut payload, updates);
+    fields::apply_updates(&mut payload, updates, UnsetMode::Remove)?;
 
     Ok(Value::Object(payload))
 }
 
-fn build_patch_payload(args: &PatchArgs) -> Result<Value, CliError> {
+/// Builds the PATCH body. `current` is the task as it is now, needed when `updates` reach
+/// below a top-level field; those fields are sent whole.
+fn build_patch_payload(
+    args: &PatchArgs,
+    updates: Vec<FieldUpdate>,
+    current: Option<&Task>,
+) -> Result<Value, CliError> {
     let mut payload = match args.json.as_deref() {
-        Some(raw_json) => parse_json_object_argument(raw_json, "--json")?,
+        Some(raw_json) => fields::parse_json_object(raw_json, "--json")?,
         None => Map::new(),
     };
 
-    let updates = parse_set_entries(&args.set)?;
-    merge_object_fields(&mut payload, updates);
+    if let Some(current) = current {
+        fields::seed_nested(&mut payload, &task_fields(current)?, &updates);
+    }
+    fields::apply_updates(&mut payload, updates, UnsetMode::Null)?;
 
     if payload.is_empty() {
         return Err(CliError::InvalidInput {
@@ -683,83 +1519,116 @@ fn build_patch_payload(args: &PatchArgs) -> Result<Value, CliError> {
     Ok(Value::Object(payload))
 }
 
-fn parse_json_object_argument(
-    raw_json: &str,
-    flag_name: &str,
-) -> Result<Map<String, Value>, CliError> {
-    let raw_json = raw_json.trim();
-    if raw_json.is_empty() {
-        return Err(CliError::InvalidInput {
-            message: format!("Invalid {flag_name} value: it cannot be empty."),
-            hint: Some(format!(
-                "Pass {flag_name} with a JSON object, e.g. {flag_name} '{{\"priority\":\"P4\"}}'."
-            )),
-        });
-    }
-
-    let parsed: Value = serde_json::from_str(raw_json).map_err(|error| CliError::InvalidInput {
-        message: format!("Invalid {flag_name} JSON: {error}"),
-        hint: Some(format!(
-            "Pass {flag_name} with a JSON object, e.g. {flag_name} '{{\"priority\":\"P4\"}}'."
+/// `task` as the JSON object Reclaim sent.
+fn task_fields(task: &Task) -> Result<Map<String, Value>, CliError> {
+    match serde_json::to_value(task) {
+        Ok(Value::Object(fields)) => Ok(fields),
+        Ok(_) => Err(CliError::Output(
+            "Could not serialize the current task: expected an object.".to_string(),
         )),
-    })?;
-
-    parsed
-        .as_object()
-        .cloned()
-        .ok_or_else(|| CliError::InvalidInput {
-            message: format!("Invalid {flag_name} value: expected a JSON object."),
-            hint: Some(format!(
-                "Pass {flag_name} with a JSON object, e.g. {flag_name} '{{\"priority\":\"P4\"}}'."
-            )),
-        })
-}
-
-fn parse_set_entries(entries: &[String]) -> Result<Map<String, Value>, CliError> {
-    let mut updates = Map::new();
-    for entry in entries {
-        let (key, value) = parse_set_entry(entry)?;
-        updates.insert(key, value);
+        Err(error) => Err(CliError::Output(format!(
+            "Could not serialize the current task: {error}"
+        ))),
     }
-    Ok(updates)
 }
 
-fn parse_set_entry(entry: &str) -> Result<(String, Value), CliError> {
-    let (raw_key, raw_value) = entry
-        .split_once('=')
-        .ok_or_else(|| CliError::InvalidInput {
-            message: format!("Invalid --set value '{entry}'. Expected KEY=VALUE."),
-            hint: Some(
-                "Examples: --set priority=P4 --set snoozeUntil=2026-02-25T17:00:00Z".to_string(),
-            ),
-        })?;
-
-    let key = raw_key.trim();
-    if key.is_empty() {
-        return Err(CliError::InvalidInput {
-            message: format!("Invalid --set value '{entry}': key cannot be empty."),
-            hint: Some("Use a non-empty key, e.g. --set priority=P4".to_string()),
+/// `event` under the names an `UpdateEventAction` uses, so nested `--set` paths start from
+/// its current values; its start and end become `dateRange`.
+fn event_update_fields(event: &Event) -> Result<Map<String, Value>, CliError> {
+    let mut fields = match serde_json::to_value(event) {
+        Ok(Value::Object(fields)) => fields,
+        Ok(_) => {
+            return Err(CliError::Output(
+                "Could not serialize the current event: expected an object.".to_string(),
+            ))
+        }
+        Err(error) => {
+            return Err(CliError::Output(format!(
+                "Could not serialize the current event: {error}"
+            )))
+        }
+    };
+    if let (Some(start), Some(end)) = (event.start(), event.end()) {
+        fields.entry("dateRange").or_insert_with(|| {
+            json!({
+                "type": "FixedDateTimeRange",
+                "start": start,
+                "end": end
+            })
         });
     }
+    Ok(fields)
+}
 
-    Ok((key.to_string(), parse_set_value(raw_value.trim())))
+/// Parses a task command's `--set`, `--set-string` and `--unset` entries and normalizes
+/// the timestamp fields among them.
+fn task_updates(
+    set: &[String],
+    set_string: &[String],
+    unset: &[String],
+    matches: Option<&ArgMatches>,
+    time: &TimeContext,
+) -> Result<Vec<FieldUpdate>, CliError> {
+    let mut updates = fields::parse_updates(set, set_string, unset, matches)?;
+    normalize_task_time_fields(&mut updates, time)?;
+    Ok(updates)
 }
 
-fn parse_set_value(raw_value: &str) -> Value {
-    serde_json::from_str(raw_value).unwrap_or_else(|_| Value::String(raw_value.to_string()))
+/// Task fields that hold timestamps; `--set` values for them accept the same input as `--due`.
+const TASK_TIME_FIELDS: [&str; 3] = ["due", "snoozeUntil", "startAfter"];
+
+fn normalize_task_time_fields(
+    updates: &mut [FieldUpdate],
+    time: &TimeContext,
+) -> Result<(), CliError> {
+    for update in updates {
+        if let Some((field, Value::String(raw))) = update.key_value_mut() {
+            if TASK_TIME_FIELDS.contains(&field) {
+                *raw = datetime::normalize_timestamp(time, &format!("--set {field}"), raw)?;
+            }
+        }
+    }
+
+    Ok(())
 }
 
-fn merge_object_fields(target: &mut Map<String, Value>, updates: Map<String, Value>) {
-    for (key, value) in updates {
-        target.insert(key, value);
+/// Default columns for `reclaim list` in the tabular formats when `--fields` is not given.
+const TASK_TABLE_COLUMNS: [&str; 6] = [
+    "id",
+    "title",
+    "status",
+    "priority",
+    "due",
+    "timeChunksRemaining",
+];
+
+const EVENT_TABLE_COLUMNS: [&str; 6] = ["key", "title", "start", "end", "priority", "task"];
+
+fn task_table(tasks: &[Task], fields: &[String]) -> output::Table {
+    let columns = fields.iter().map(String::as_str).collect::<Vec<_>>();
+    let mut table = output::Table::new(&columns);
+    for row in task_query::select_fields(tasks, fields) {
+        table.push(row.values().map(output::cell_text).collect());
     }
+    table
 }
 
-fn print_json<T: serde::Serialize>(value: &T) -> Result<(), CliError> {
-    let rendered = serde_json::to_string_pretty(value)
-        .map_err(|error| CliError::Output(format!("Could not render JSON output: {error}")))?;
-    println!("{rendered}");
-    Ok(())
+fn event_table(events: &[Event]) -> output::Table {
+    let mut table = output::Table::new(&EVENT_TABLE_COLUMNS);
+    for event in events {
+        table.push(vec![
+            event.display_key().unwrap_or_default(),
+            event.title.clone().unwrap_or_default(),
+            event.start().unwrap_or_default(),
+            event.end().unwrap_or_default(),
+            event.priority.clone().unwrap_or_default(),
+            event
+                .task_id()
+                .map(|task_id| task_id.to_string())
+                .unwrap_or_default(),
+        ]);
+    }
+    table
 }
 
 fn apply_task_status_filter(tasks: &mut Vec<Task>, filter: Option<TaskStatusFilter>) {
@@ -767,16 +1636,22 @@ fn apply_task_status_filter(tasks: &mut Vec<Task>, filter: Option<TaskStatusFilt
         return;
     };
 
-    tasks.retain(|task| match filter {
-        TaskStatusFilter::Open => status_indicates_open(task.status.as_deref()),
-        TaskStatusFilter::Completed => status_indicates_completed(task.status.as_deref()),
-        TaskStatusFilter::New => status_matches_exact(task.status.as_deref(), "NEW"),
-        TaskStatusFilter::Scheduled => status_matches_exact(task.status.as_deref(), "SCHEDULED"),
-        TaskStatusFilter::InProgress => status_matches_exact(task.status.as_deref(), "IN_PROGRESS"),
-        TaskStatusFilter::Complete => status_matches_exact(task.status.as_deref(), "COMPLETE"),
-        TaskStatusFilter::Cancelled => status_matches_exact(task.status.as_deref(), "CANCELLED"),
-        TaskStatusFilter::Archived => status_matches_exact(task.status.as_deref(), "ARCHIVED"),
-    });
+    tasks.retain(|task| task_matches_status(task, filter));
+}
+
+/// Whether `task` falls in a `--filter` bucket; shared with the dashboard filter bar.
+fn task_matches_status(task: &Task, filter: TaskStatusFilter) -> bool {
+    let status = task.status.as_deref();
+    match filter {
+        TaskStatusFilter::Open => status_indicates_open(status),
+        TaskStatusFilter::Completed => status_indicates_completed(status),
+        TaskStatusFilter::New => status_matches_exact(status, "NEW"),
+        TaskStatusFilter::Scheduled => status_matches_exact(status, "SCHEDULED"),
+        TaskStatusFilter::InProgress => status_matches_exact(status, "IN_PROGRESS"),
+        TaskStatusFilter::Complete => status_matches_exact(status, "COMPLETE"),
+        TaskStatusFilter::Cancelled => status_matches_exact(status, "CANCELLED"),
+        TaskStatusFilter::Archived => status_matches_exact(status, "ARCHIVED"),
+    }
 }
 
 fn status_matches_exact(status: Option<&str>, expected: &str) -> bool {
@@ -799,16 +1674,7 @@ fn status_indicates_completed(status: Option<&str>) -> bool {
 
 fn print_task_list_human(tasks: &[Task], task_filter: Option<TaskStatusFilter>) {
     if tasks.is_empty() {
-        if let Some(filter_text) = task_filter.map(|filter| match filter {
-            TaskStatusFilter::Open => "open",
-            TaskStatusFilter::Completed => "completed",
-            TaskStatusFilter::New => "NEW",
-            TaskStatusFilter::Scheduled => "SCHEDULED",
-            TaskStatusFilter::InProgress => "IN_PROGRESS",
-            TaskStatusFilter::Complete => "COMPLETE",
-            TaskStatusFilter::Cancelled => "CANCELLED",
-            TaskStatusFilter::Archived => "ARCHIVED",
-        }) {
+        if let Some(filter_text) = task_filter.map(TaskStatusFilter::as_str) {
             println!("No tasks found with filter '{filter_text}'.");
         } else {
             println!("No tasks found.");
@@ -819,15 +1685,67 @@ fn print_task_list_human(tasks: &[Task], task_filter: Option<TaskStatusFilter>)
     for task in tasks {
         let status = task.status.as_deref().unwrap_or("UNKNOWN");
         let due = task.due.as_deref().unwrap_or("-");
-        println!(
-            "#{: <6} [{: <11}] {} (due: {due})",
+        let mut line = format!(
+            "#{: <6} [{: <11}] {} (due: {due}",
             task.id, status, task.title
         );
+        if let Some(duration) = task_duration_summary(task) {
+            line.push_str(&format!(", {duration}"));
+        }
+        line.push(')');
+        println!("{line}");
     }
 
     println!("\nTip: use --format json for machine-readable output.");
 }
 
+/// Prints the selected `--fields` as aligned columns with a header row.
+fn print_task_fields_human(tasks: &[Task], fields: &[String]) {
+    if tasks.is_empty() {
+        println!("No tasks found.");
+        return;
+    }
+
+    let rows = task_query::select_fields(tasks, fields)
+        .into_iter()
+        .map(|row| {
+            row.values()
+                .map(|value| match value {
+                    Value::Null => "-".to_string(),
+                    value => task_query::value_text(value),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let widths = fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            rows.iter()
+                .map(|row| row[index].chars().count())
+                .chain([field.chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    let render = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{}", render(fields.iter().map(String::as_str).collect()));
+    for row in &rows {
+        println!("{}", render(row.iter().map(String::as_str).collect()));
+    }
+}
+
 fn print_task_human(task: &Task) {
     println!("#{} {}", task.id, task.title);
     if let Some(status) = task.status.as_deref() {
@@ -839,56 +1757,106 @@ fn print_task_human(task: &Task) {
     if let Some(due) = task.due.as_deref() {
         println!("due: {due}");
     }
+    if let Some(duration) = task_duration_summary(task) {
+        println!("duration: {duration}");
+    }
+    if let (Some(min), Some(max)) = (task.min_chunk_size(), task.max_chunk_size()) {
+        println!(
+            "chunks: {} - {}",
+            duration::format_chunks(min),
+            duration::format_chunks(max)
+        );
+    }
     if let Some(notes) = task.notes.as_deref() {
         println!("notes: {notes}");
     }
 }
 
-fn print_events_list_human(events: &[Value]) {
+/// Total duration with the remaining time when some of it is done, e.g. `2h (45m left)`.
+fn task_duration_summary(task: &Task) -> Option<String> {
+    let total = task.time_chunks_required()?;
+    let mut summary = duration::format_chunks(total);
+    if let Some(remaining) = task
+        .time_chunks_remaining()
+        .filter(|remaining| *remaining != total)
+    {
+        summary.push_str(&format!(" ({} left)", duration::format_chunks(remaining)));
+    }
+    Some(summary)
+}
+
+fn print_events_list_human(events: &[Event]) {
     if events.is_empty() {
         println!("No events found.");
         return;
     }
 
     for event in events {
-        let title =
-            json_text_by_pointers(event, &["/title"]).unwrap_or_else(|| "<untitled>".to_string());
-        let key =
-            json_text_by_pointers(event, &["/key", "/eventKey"]).unwrap_or_else(|| "-".to_string());
-        let start = json_text_by_pointers(
-            event,
-            &["/eventDate/start", "/dateRange/start", "/originalStart"],
-        )
-        .unwrap_or_else(|| "-".to_string());
-        let end =
-            json_text_by_pointers(event, &["/eventDate/end", "/dateRange/end", "/originalEnd"])
-                .unwrap_or_else(|| "-".to_string());
-
-        println!("- {title} [{key}] ({start} -> {end})");
+        let title = event.title.as_deref().unwrap_or("<untitled>");
+        let key = event.display_key().unwrap_or_else(|| "-".to_string());
+        let start = event.start().unwrap_or_else(|| "-".to_string());
+        let end = event.end().unwrap_or_else(|| "-".to_string());
+
+        let mut line = format!("- {title} [{key}] ({start} -> {end})");
+        if let Some(priority) = event.priority.as_deref() {
+            line.push_str(&format!(" {priority}"));
+        }
+        if let Some(task_id) = event.task_id() {
+            line.push_str(&format!(" <task #{task_id}>"));
+        } else if event.is_reclaim_managed() {
+            line.push_str(" <reclaim>");
+        }
+        println!("{line}");
     }
 
     println!("\nTip: use --format json for machine-readable output.");
 }
 
-fn print_event_human(event: &Value) -> Result<(), CliError> {
-    let title =
-        json_text_by_pointers(event, &["/title"]).unwrap_or_else(|| "<untitled>".to_string());
-    let key =
-        json_text_by_pointers(event, &["/key", "/eventKey"]).unwrap_or_else(|| "-".to_string());
-    let start = json_text_by_pointers(
-        event,
-        &["/eventDate/start", "/dateRange/start", "/originalStart"],
-    )
-    .unwrap_or_else(|| "-".to_string());
-    let end = json_text_by_pointers(event, &["/eventDate/end", "/dateRange/end", "/originalEnd"])
-        .unwrap_or_else(|| "-".to_string());
-
-    println!("title: {title}");
-    println!("key: {key}");
-    println!("start: {start}");
-    println!("end: {end}");
+fn print_event_human(event: &Event) -> Result<(), CliError> {
+    println!("title: {}", event.title.as_deref().unwrap_or("<untitled>"));
+    println!(
+        "key: {}",
+        event.display_key().unwrap_or_else(|| "-".to_string())
+    );
+    println!(
+        "start: {}",
+        event.start().unwrap_or_else(|| "-".to_string())
+    );
+    println!("end: {}", event.end().unwrap_or_else(|| "-".to_string()));
+    if let Some(status) = event.status.as_deref() {
+        println!("status: {status}");
+    }
+    if let Some(priority) = event.priority.as_deref() {
+        println!("priority: {priority}");
+    }
+    if let Some(category) = event.category.as_deref() {
+        println!("category: {category}");
+    }
+    println!(
+        "reclaim managed: {}",
+        if event.is_reclaim_managed() {
+            "yes"
+        } else {
+            "no"
+        }
+    );
+    if let Some(task_id) = event.task_id() {
+        println!("task: #{task_id}");
+    }
+    let attendees = event
+        .attendees
+        .iter()
+        .flatten()
+        .filter_map(|attendee| attendee.email.as_deref())
+        .collect::<Vec<_>>();
+    if !attendees.is_empty() {
+        println!("attendees: {}", attendees.join(", "));
+    }
+
+    let raw = serde_json::to_value(event)
+        .map_err(|error| CliError::Output(format!("Could not render event JSON: {error}")))?;
     println!("\nRaw event JSON:");
-    println!("{}", render_pretty_json(event)?);
+    println!("{}", render_pretty_json(&raw)?);
 
     Ok(())
 }
@@ -976,6 +1944,48 @@ fn render_pretty_json(value: &Value) -> Result<String, CliError> {
         .map_err(|error| CliError::Output(format!("Could not render JSON output: {error}")))
 }
 
+fn print_task_mutation(printer: &Printer, prefix: &str, task: &Task) -> Result<(), CliError> {
+    match printer.format {
+        OutputFormat::Human => print_mutation_human(prefix, task),
+        _ => printer.print_value(task)?,
+    }
+    Ok(())
+}
+
+/// `--diff` output: every task field that differs between `before` and `after`.
+/// `put`, `patch` and `edit` print a diff in human output, and in JSON or YAML with
+/// `--diff`. A dry run writes nothing, so there is no task to fetch for comparison.
+fn shows_diff(printer: &Printer, settings: &Settings, diff: bool) -> bool {
+    !settings.dry_run && (diff || printer.format == OutputFormat::Human)
+}
+
+fn print_task_diff(
+    printer: &Printer,
+    prefix: &str,
+    before: &Task,
+    after: &Task,
+) -> Result<(), CliError> {
+    let to_json = |task: &Task| {
+        serde_json::to_value(task)
+            .map_err(|error| CliError::Output(format!("Could not serialize task: {error}")))
+    };
+    let changes = diff::diff_fields(&to_json(before)?, &to_json(after)?);
+
+    match printer.format {
+        OutputFormat::Human => {
+            println!("{prefix} task #{}: {}", after.id, after.title);
+            if changes.is_empty() {
+                println!("No fields changed.");
+            }
+            for change in &changes {
+                println!("  {}: {} -> {}", change.field, change.before, change.after);
+            }
+            Ok(())
+        }
+        _ => printer.print_value(&changes),
+    }
+}
+
 fn print_mutation_human(prefix: &str, task: &Task) {
     println!("{prefix} task #{}: {}", task.id, task.title);
     if let Some(status) = task.status.as_deref() {
@@ -987,41 +1997,25 @@ fn print_mutation_human(prefix: &str, task: &Task) {
     if let Some(due) = task.due.as_deref() {
         println!("Due: {due}");
     }
+    if let Some(duration) = task_duration_summary(task) {
+        println!("Duration: {duration}");
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::test_time;
     use serde_json::json;
 
-    #[test]
-    fn parse_set_value_supports_json_literals() {
-        assert_eq!(parse_set_value("true"), json!(true));
-        assert_eq!(parse_set_value("42"), json!(42));
-        assert_eq!(parse_set_value("{\"nested\":1}"), json!({"nested": 1}));
-        assert_eq!(parse_set_value("P4"), json!("P4"));
-    }
-
-    #[test]
-    fn parse_json_object_argument_requires_object() {
-        let error = parse_json_object_argument("[]", "--json").unwrap_err();
-        assert!(error.to_string().contains("expected a JSON object"));
-    }
-
-    #[test]
-    fn parse_set_entry_requires_equals_sign() {
-        let error = parse_set_entry("priority").unwrap_err();
-        assert!(error.to_string().contains("Expected KEY=VALUE"));
-    }
-
     #[test]
     fn build_event_create_request_wraps_add_event_action() {
         let args = EventsCreateArgs {
-            calendar_id: 829105,
+            calendar_id: Some(829105),
             title: "Team sync".to_string(),
             start: "2026-02-21T18:30:00Z".to_string(),
             end: "2026-02-21T19:00:00Z".to_string(),
-            policy_id: "00000000-0000-0000-0000-000000000000".to_string(),
+            policy_id: None,
             attendees: vec!["person@example.com".to_string()],
             description: None,
             location: None,
@@ -1033,9 +2027,11 @@ mod tests {
             guests_can_see_other_guests: true,
             json: None,
             set: vec![],
+            set_string: vec![],
+            unset: vec![],
         };
 
-        let request = build_event_create_request(&args).unwrap();
+        let request = build_event_create_request(&args, Vec::new(), &test_time()).unwrap();
         let action = request
             .pointer("/actionsTaken/0")
             .and_then(|value| value.as_object())
@@ -1054,9 +2050,9 @@ mod tests {
     #[test]
     fn build_event_update_request_requires_mutation_fields() {
         let args = EventsUpdateArgs {
-            calendar_id: 829105,
+            calendar_id: Some(829105),
             event_id: "abc123".to_string(),
-            policy_id: "00000000-0000-0000-0000-000000000000".to_string(),
+            policy_id: None,
             title: None,
             description: None,
             location: None,
@@ -1067,14 +2063,251 @@ mod tests {
             end: None,
             json: None,
             set: vec![],
+            set_string: vec![],
+            unset: vec![],
         };
 
-        let error = build_event_update_request(&args).unwrap_err();
+        let error = build_event_update_request(&args, Vec::new(), None, &test_time()).unwrap_err();
         assert!(error
             .to_string()
             .contains("requires at least one field change"));
     }
 
+    #[test]
+    fn build_event_update_request_normalizes_relative_range() {
+        let args = EventsUpdateArgs {
+            calendar_id: Some(829105),
+            event_id: "abc123".to_string(),
+            policy_id: None,
+            title: None,
+            description: None,
+            location: None,
+            priority: None,
+            visibility: None,
+            transparency: None,
+            start: Some("tomorrow 10am".to_string()),
+            end: Some("tomorrow 10:30".to_string()),
+            json: None,
+            set: vec![],
+            set_string: vec![],
+            unset: vec![],
+        };
+
+        let request = build_event_update_request(&args, Vec::new(), None, &test_time()).unwrap();
+        assert_eq!(
+            request.pointer("/actionsTaken/0/dateRange/start"),
+            Some(&json!("2026-02-19T15:00:00Z"))
+        );
+        assert_eq!(
+            request.pointer("/actionsTaken/0/dateRange/end"),
+            Some(&json!("2026-02-19T15:30:00Z"))
+        );
+
+        let args = EventsUpdateArgs {
+            end: Some("tomorrow 9am".to_string()),
+            ..args
+        };
+        let error = build_event_update_request(&args, Vec::new(), None, &test_time()).unwrap_err();
+        assert!(error.to_string().contains("must be after --start"));
+    }
+
+    #[test]
+    fn build_event_update_request_deep_merges_json_and_applies_set_paths() {
+        let args = EventsUpdateArgs {
+            calendar_id: Some(829105),
+            event_id: "abc123".to_string(),
+            policy_id: None,
+            title: None,
+            description: None,
+            location: None,
+            priority: None,
+            visibility: None,
+            transparency: None,
+            start: Some("2026-02-21T18:30:00Z".to_string()),
+            end: Some("2026-02-21T19:00:00Z".to_string()),
+            json: Some("{\"dateRange\":{\"end\":\"2026-02-21T19:30:00Z\"}}".to_string()),
+            set: vec![
+                "attendees[+]={\"email\":\"x@y.com\"}".to_string(),
+                "dateRange.start=2026-02-21T18:45:00Z".to_string(),
+            ],
+            set_string: vec![],
+            unset: vec!["location".to_string()],
+        };
+
+        let updates =
+            fields::parse_updates(&args.set, &args.set_string, &args.unset, None).unwrap();
+        let request = build_event_update_request(&args, updates, None, &test_time()).unwrap();
+        let action = &request["actionsTaken"][0];
+        assert_eq!(
+            action["dateRange"],
+            json!({
+                "type": "FixedDateTimeRange",
+                "start": "2026-02-21T18:45:00Z",
+                "end": "2026-02-21T19:30:00Z"
+            })
+        );
+        assert_eq!(action["attendees"], json!([{ "email": "x@y.com" }]));
+        assert_eq!(action["location"], Value::Null);
+        assert!(action.as_object().unwrap().contains_key("location"));
+    }
+
+    #[test]
+    fn build_patch_payload_normalizes_time_fields_from_set() {
+        let args = PatchArgs {
+            task_id: 1,
+            json: Some("{\"due\":\"not touched\"}".to_string()),
+            set: vec!["snoozeUntil=+2h".to_string(), "title=eod".to_string()],
+            set_string: vec!["notes=42".to_string()],
+            unset: vec!["startAfter".to_string()],
+            notification_key: None,
+            diff: false,
+        };
+
+        let updates =
+            task_updates(&args.set, &args.set_string, &args.unset, None, &test_time()).unwrap();
+        let payload = build_patch_payload(&args, updates, None).unwrap();
+        assert_eq!(payload["snoozeUntil"], json!("2026-02-18T17:00:00Z"));
+        assert_eq!(payload["title"], json!("eod"));
+        assert_eq!(payload["due"], json!("not touched"));
+        assert_eq!(payload["notes"], json!("42"));
+        assert_eq!(payload["startAfter"], Value::Null);
+
+        let args = PatchArgs {
+            set: vec!["due=someday".to_string()],
+            ..args
+        };
+        let error =
+            task_updates(&args.set, &args.set_string, &args.unset, None, &test_time()).unwrap_err();
+        assert!(error.to_string().contains("Invalid --set due value"));
+    }
+
+    #[test]
+    fn patch_applies_updates_in_command_line_order_on_the_current_task() {
+        let matches = Cli::command()
+            .try_get_matches_from([
+                "reclaim",
+                "patch",
+                "7",
+                "--unset",
+                "notes",
+                "--set",
+                "assist.locked=true",
+                "--set-string",
+                "notes=42",
+            ])
+            .unwrap();
+        let Command::Patch(args) = Cli::from_arg_matches(&matches).unwrap().command else {
+            panic!("expected patch command");
+        };
+        let updates = task_updates(
+            &args.set,
+            &args.set_string,
+            &args.unset,
+            Some(subcommand_matches(&matches)),
+            &test_time(),
+        )
+        .unwrap();
+        assert!(fields::reaches_below_top_level(&updates));
+
+        let mut current = crate::test_support::task(7, "Ship it");
+        current.extra.insert(
+            "assist".to_string(),
+            json!({ "type": "TASK", "locked": false }),
+        );
+        let payload = build_patch_payload(&args, updates, Some(&current)).unwrap();
+        assert_eq!(
+            payload,
+            json!({ "notes": "42", "assist": { "type": "TASK", "locked": true } })
+        );
+    }
+
+    #[test]
+    fn build_event_update_request_starts_nested_paths_from_the_current_event() {
+        let args = EventsUpdateArgs {
+            calendar_id: Some(829105),
+            event_id: "abc123".to_string(),
+            policy_id: None,
+            title: None,
+            description: None,
+            location: None,
+            priority: None,
+            visibility: None,
+            transparency: None,
+            start: None,
+            end: None,
+            json: None,
+            set: vec![
+                "dateRange.end=2026-02-21T19:30:00Z".to_string(),
+                "attendees[+].email=z@y.com".to_string(),
+            ],
+            set_string: vec![],
+            unset: vec![],
+        };
+        let current: Event = serde_json::from_value(json!({
+            "eventId": "abc123",
+            "title": "Team sync",
+            "eventStart": "2026-02-21T18:30:00Z",
+            "eventEnd": "2026-02-21T19:00:00Z",
+            "attendees": [{ "email": "x@y.com", "responseStatus": "Accepted" }]
+        }))
+        .unwrap();
+
+        let updates =
+            fields::parse_updates(&args.set, &args.set_string, &args.unset, None).unwrap();
+        let request =
+            build_event_update_request(&args, updates, Some(&current), &test_time()).unwrap();
+        let action = &request["actionsTaken"][0];
+        assert_eq!(
+            action["dateRange"],
+            json!({
+                "type": "FixedDateTimeRange",
+                "start": "2026-02-21T18:30:00Z",
+                "end": "2026-02-21T19:30:00Z"
+            })
+        );
+        assert_eq!(
+            action["attendees"],
+            json!([
+                { "email": "x@y.com", "responseStatus": "Accepted" },
+                { "email": "z@y.com" }
+            ])
+        );
+        assert!(action.get("title").is_none());
+    }
+
+    #[test]
+    fn resolve_create_chunks_converts_durations() {
+        let matches = Cli::command()
+            .try_get_matches_from([
+                "reclaim",
+                "create",
+                "--title",
+                "Write doc",
+                "--duration",
+                "2h30m",
+                "--min-chunk",
+                "30m",
+                "--max-chunk-size",
+                "4",
+            ])
+            .unwrap();
+        let Command::Create(args) = Cli::from_arg_matches(&matches).unwrap().command else {
+            panic!("expected create command");
+        };
+
+        let plan = resolve_create_chunks(&args).unwrap();
+        assert_eq!(plan.time_chunks_required, Some(10));
+        assert_eq!(plan.min_chunk_size, Some(2));
+        assert_eq!(plan.max_chunk_size, Some(4));
+
+        let args = CreateArgs {
+            duration: Some("50m".to_string()),
+            ..args
+        };
+        let error = resolve_create_chunks(&args).unwrap_err();
+        assert!(error.to_string().contains("Invalid --duration value '50m'"));
+    }
+
     #[test]
     fn build_events_apply_request_requires_actions_taken() {
         let args = EventsApplyArgs {
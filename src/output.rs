@@ -0,0 +1,554 @@
+use crate::{cli::OutputFormat, error::CliError, extract::Extract};
+use serde::{ser::SerializeMap, Serialize};
+use serde_json::Value;
+use std::io::IsTerminal;
+
+/// Narrowest a table column is squeezed to before the table is allowed to overflow.
+const MIN_COLUMN_WIDTH: usize = 6;
+const COLUMN_GAP: &str = "  ";
+
+/// Rows for the tabular formats (`table`, `csv`, `tsv`) of list-like commands.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(columns: &[&str]) -> Self {
+        Self {
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Builds a table from JSON values: arrays of objects become one row per element with
+    /// the union of their keys as columns; a single object becomes `field`/`value` rows.
+    pub fn from_value(value: &Value) -> Self {
+        match value {
+            Value::Array(items) if items.iter().all(Value::is_object) && !items.is_empty() => {
+                let mut columns: Vec<String> = Vec::new();
+                for item in items.iter().filter_map(Value::as_object) {
+                    for key in item.keys() {
+                        if !columns.contains(key) {
+                            columns.push(key.clone());
+                        }
+                    }
+                }
+                let rows = items
+                    .iter()
+                    .map(|item| {
+                        columns
+                            .iter()
+                            .map(|column| cell_text(item.get(column).unwrap_or(&Value::Null)))
+                            .collect()
+                    })
+                    .collect();
+                Self { columns, rows }
+            }
+            Value::Object(object) => Self {
+                columns: vec!["field".to_string(), "value".to_string()],
+                rows: object
+                    .iter()
+                    .map(|(key, value)| vec![key.clone(), cell_text(value)])
+                    .collect(),
+            },
+            Value::Array(items) => Self {
+                columns: vec!["value".to_string()],
+                rows: items.iter().map(|item| vec![cell_text(item)]).collect(),
+            },
+            scalar => Self {
+                columns: vec!["value".to_string()],
+                rows: vec![vec![cell_text(scalar)]],
+            },
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+}
+
+/// A JSON object whose keys print in insertion order, e.g. a `--fields` row.
+/// `serde_json::Map` sorts its keys, so this serializes itself as an ordered map.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Record(pub Vec<(String, Value)>);
+
+impl Record {
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        self.0.iter().map(|(_, value)| value)
+    }
+}
+
+impl Serialize for Record {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Text for one table cell: strings unquoted, `null` empty, nested values as compact JSON.
+pub fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// How a command prints machine-readable results: the `--format`, or `--template`/`--query`
+/// extraction, which replaces the format when given.
+#[derive(Debug, Clone)]
+pub struct Printer {
+    pub format: OutputFormat,
+    extract: Option<Extract>,
+}
+
+impl Printer {
+    pub fn new(format: OutputFormat, extract: Option<Extract>) -> Self {
+        // Extraction works on the JSON structures, so commands take their JSON path.
+        let format = if extract.is_some() {
+            OutputFormat::Json
+        } else {
+            format
+        };
+        Self { format, extract }
+    }
+
+    /// Prints a single result (or any serializable structure) in a non-human format.
+    pub fn print_value<T: Serialize>(&self, value: &T) -> Result<(), CliError> {
+        let value = to_value(value)?;
+        if let Some(extract) = &self.extract {
+            for line in extract.render(&value)? {
+                println!("{line}");
+            }
+            return Ok(());
+        }
+
+        match self.format {
+            OutputFormat::Human | OutputFormat::Json => print_json(&value),
+            OutputFormat::Ndjson => print_ndjson(&value),
+            OutputFormat::Yaml => {
+                print!("{}", render_yaml(&value));
+                Ok(())
+            }
+            OutputFormat::Table | OutputFormat::Csv | OutputFormat::Tsv => {
+                print_table(self.format, &Table::from_value(&value));
+                Ok(())
+            }
+        }
+    }
+
+    /// Prints a list-like result. Tabular formats use `table` so columns stay consistent
+    /// across `table`, `csv` and `tsv`; the other formats serialize `items` as-is.
+    pub fn print_collection<T: Serialize>(
+        &self,
+        items: &[T],
+        table: impl FnOnce() -> Table,
+    ) -> Result<(), CliError> {
+        match self.format {
+            OutputFormat::Table | OutputFormat::Csv | OutputFormat::Tsv => {
+                print_table(self.format, &table());
+                Ok(())
+            }
+            _ => self.print_value(&items),
+        }
+    }
+
+    /// Like `print_collection`, but keeps each record's key order in JSON, NDJSON and YAML.
+    /// Extraction reads the records as plain JSON values.
+    pub fn print_records(
+        &self,
+        records: &[Record],
+        table: impl FnOnce() -> Table,
+    ) -> Result<(), CliError> {
+        match self.format {
+            _ if self.extract.is_some() => self.print_value(&records),
+            OutputFormat::Human | OutputFormat::Json => print_json(&records),
+            OutputFormat::Ndjson => records.iter().try_for_each(|record| {
+                let rendered = serde_json::to_string(record).map_err(|error| {
+                    CliError::Output(format!("Could not render NDJSON output: {error}"))
+                })?;
+                println!("{rendered}");
+                Ok(())
+            }),
+            OutputFormat::Yaml => {
+                print!("{}", render_yaml_records(records));
+                Ok(())
+            }
+            OutputFormat::Table | OutputFormat::Csv | OutputFormat::Tsv => {
+                print_table(self.format, &table());
+                Ok(())
+            }
+        }
+    }
+}
+
+pub fn print_json<T: Serialize>(value: &T) -> Result<(), CliError> {
+    let rendered = serde_json::to_string_pretty(value)
+        .map_err(|error| CliError::Output(format!("Could not render JSON output: {error}")))?;
+    println!("{rendered}");
+    Ok(())
+}
+
+fn print_ndjson(value: &Value) -> Result<(), CliError> {
+    let items = match value {
+        Value::Array(items) => items.iter().collect::<Vec<_>>(),
+        value => vec![value],
+    };
+
+    for item in items {
+        let rendered = serde_json::to_string(item).map_err(|error| {
+            CliError::Output(format!("Could not render NDJSON output: {error}"))
+        })?;
+        println!("{rendered}");
+    }
+    Ok(())
+}
+
+fn print_table(format: OutputFormat, table: &Table) {
+    let rendered = match format {
+        OutputFormat::Csv => render_delimited(table, ','),
+        OutputFormat::Tsv => render_delimited(table, '\t'),
+        _ => {
+            let stdout = std::io::stdout();
+            let max_width = stdout
+                .is_terminal()
+                .then(|| crossterm::terminal::size().ok())
+                .flatten()
+                .map(|(columns, _)| usize::from(columns));
+            render_table(table, max_width)
+        }
+    };
+    print!("{rendered}");
+}
+
+fn to_value<T: Serialize>(value: &T) -> Result<Value, CliError> {
+    serde_json::to_value(value)
+        .map_err(|error| CliError::Output(format!("Could not serialize output: {error}")))
+}
+
+/// Renders aligned columns. With `max_width`, the widest columns are narrowed (down to
+/// `MIN_COLUMN_WIDTH`) and their cells truncated with `…` until the table fits.
+pub fn render_table(table: &Table, max_width: Option<usize>) -> String {
+    let single_line = |text: &str| text.replace(['\n', '\r', '\t'], " ");
+    let header = table
+        .columns
+        .iter()
+        .map(|column| column.to_uppercase())
+        .collect::<Vec<_>>();
+    let rows = table
+        .rows
+        .iter()
+        .map(|row| row.iter().map(|cell| single_line(cell)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let mut widths = header
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            rows.iter()
+                .filter_map(|row| row.get(index))
+                .map(|cell| cell.chars().count())
+                .chain([column.chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    if let Some(max_width) = max_width {
+        fit_widths(&mut widths, max_width);
+    }
+
+    let render_row = |cells: &[String]| {
+        let line = widths
+            .iter()
+            .enumerate()
+            .map(|(index, width)| {
+                let cell = truncate(cells.get(index).map(String::as_str).unwrap_or(""), *width);
+                format!("{cell:<width$}")
+            })
+            .collect::<Vec<_>>()
+            .join(COLUMN_GAP);
+        format!("{}\n", line.trim_end())
+    };
+
+    let mut rendered = render_row(&header);
+    for row in &rows {
+        rendered.push_str(&render_row(row));
+    }
+    rendered
+}
+
+fn fit_widths(widths: &mut [usize], max_width: usize) {
+    let gaps = COLUMN_GAP.len() * widths.len().saturating_sub(1);
+    while widths.iter().sum::<usize>() + gaps > max_width {
+        let Some(widest) = widths
+            .iter_mut()
+            .filter(|width| **width > MIN_COLUMN_WIDTH)
+            .max_by_key(|width| **width)
+        else {
+            return;
+        };
+        *widest -= 1;
+    }
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+
+    let mut truncated = text.chars().take(width - 1).collect::<String>();
+    truncated.push('…');
+    truncated
+}
+
+/// CSV follows RFC 4180 quoting; TSV escapes tabs, newlines and backslashes.
+pub fn render_delimited(table: &Table, delimiter: char) -> String {
+    let escape = |cell: &str| {
+        if delimiter == '\t' {
+            cell.replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r")
+        } else if cell.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell.to_string()
+        }
+    };
+    let render_row = |cells: &[String]| {
+        let line = cells
+            .iter()
+            .map(|cell| escape(cell))
+            .collect::<Vec<_>>()
+            .join(&delimiter.to_string());
+        format!("{line}\n")
+    };
+
+    let mut rendered = render_row(&table.columns);
+    for row in &table.rows {
+        rendered.push_str(&render_row(row));
+    }
+    rendered
+}
+
+/// Minimal YAML emitter for JSON values (block style, double-quoted strings when needed).
+pub fn render_yaml(value: &Value) -> String {
+    let mut rendered = String::new();
+    match value {
+        Value::Array(items) if !items.is_empty() => write_yaml_sequence(&mut rendered, items, 0),
+        Value::Object(object) if !object.is_empty() => write_yaml_mapping(&mut rendered, object, 0),
+        scalar => {
+            rendered.push_str(&yaml_scalar(scalar));
+            rendered.push('\n');
+        }
+    }
+    rendered
+}
+
+/// A YAML sequence of records, keeping each record's key order.
+pub fn render_yaml_records(records: &[Record]) -> String {
+    if records.is_empty() {
+        return "[]\n".to_string();
+    }
+
+    let mut rendered = String::new();
+    for record in records {
+        let mut nested = String::new();
+        write_yaml_mapping(
+            &mut nested,
+            record.0.iter().map(|(key, value)| (key, value)),
+            2,
+        );
+        rendered.push('-');
+        if nested.is_empty() {
+            rendered.push_str(" {}\n");
+        } else {
+            rendered.push(' ');
+            rendered.push_str(nested.trim_start());
+        }
+    }
+    rendered
+}
+
+fn write_yaml_mapping<'a>(
+    out: &mut String,
+    entries: impl IntoIterator<Item = (&'a String, &'a Value)>,
+    indent: usize,
+) {
+    for (key, value) in entries {
+        out.push_str(&" ".repeat(indent));
+        out.push_str(&yaml_string(key));
+        out.push(':');
+        write_yaml_child(out, value, indent);
+    }
+}
+
+fn write_yaml_sequence(out: &mut String, items: &[Value], indent: usize) {
+    for item in items {
+        out.push_str(&" ".repeat(indent));
+        out.push('-');
+        match item {
+            Value::Object(object) if !object.is_empty() => {
+                // Put the first key on the dash line, the rest aligned below it.
+                let mut nested = String::new();
+                write_yaml_mapping(&mut nested, object, indent + 2);
+                out.push(' ');
+                out.push_str(nested.trim_start());
+            }
+            value => write_yaml_child(out, value, indent),
+        }
+    }
+}
+
+fn write_yaml_child(out: &mut String, value: &Value, indent: usize) {
+    match value {
+        Value::Object(object) if !object.is_empty() => {
+            out.push('\n');
+            write_yaml_mapping(out, object, indent + 2);
+        }
+        Value::Array(items) if !items.is_empty() => {
+            out.push('\n');
+            write_yaml_sequence(out, items, indent + 2);
+        }
+        scalar => {
+            out.push(' ');
+            out.push_str(&yaml_scalar(scalar));
+            out.push('\n');
+        }
+    }
+}
+
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        Value::Number(number) => number.to_string(),
+        Value::String(text) => yaml_string(text),
+        Value::Array(_) => "[]".to_string(),
+        Value::Object(_) => "{}".to_string(),
+    }
+}
+
+/// Leaves simple strings plain and double-quotes anything YAML could read as another type
+/// or as syntax. JSON string escapes are valid in YAML double-quoted scalars.
+fn yaml_string(text: &str) -> String {
+    let reserved = matches!(
+        text.to_ascii_lowercase().as_str(),
+        "" | "~" | "null" | "true" | "false" | "yes" | "no" | "on" | "off" | "y" | "n"
+    );
+    // Leading digits cover numbers, dates and times, which YAML 1.1 readers may convert.
+    let numeric = text.starts_with(|ch: char| ch.is_ascii_digit() || matches!(ch, '.' | '+' | '-'));
+    // A plain scalar cannot start with an indicator character.
+    let indicator = text.starts_with(|ch: char| "-?:,[]{}#&*!|>'\"%@`".contains(ch));
+    let plain = text.chars().all(|ch| {
+        ch.is_alphanumeric() || matches!(ch, ' ' | '_' | '-' | '.' | '/' | '@' | '(' | ')')
+    }) && !text.starts_with(' ')
+        && !text.ends_with(' ');
+
+    if plain && !reserved && !numeric && !indicator {
+        text.to_string()
+    } else {
+        serde_json::to_string(text).unwrap_or_else(|_| format!("\"{text}\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_table() -> Table {
+        let mut table = Table::new(&["id", "title"]);
+        table.push(vec!["1".to_string(), "Plan, then \"ship\"".to_string()]);
+        table.push(vec!["22".to_string(), "Tabs\there".to_string()]);
+        table
+    }
+
+    #[test]
+    fn render_table_aligns_and_truncates_to_width() {
+        assert_eq!(
+            render_table(&sample_table(), None),
+            "ID  TITLE\n1   Plan, then \"ship\"\n22  Tabs here\n"
+        );
+        assert_eq!(
+            render_table(&sample_table(), Some(12)),
+            "ID  TITLE\n1   Plan, t…\n22  Tabs he…\n"
+        );
+    }
+
+    #[test]
+    fn render_delimited_escapes_cells() {
+        assert_eq!(
+            render_delimited(&sample_table(), ','),
+            "id,title\n1,\"Plan, then \"\"ship\"\"\"\n22,Tabs\there\n"
+        );
+        assert_eq!(
+            render_delimited(&sample_table(), '\t'),
+            "id\ttitle\n1\tPlan, then \"ship\"\n22\tTabs\\there\n"
+        );
+    }
+
+    #[test]
+    fn table_from_value_handles_lists_and_objects() {
+        let table = Table::from_value(&json!([{ "id": 1 }, { "id": 2, "title": "x" }]));
+        assert_eq!(table.columns, vec!["id", "title"]);
+        assert_eq!(table.rows[0], vec!["1", ""]);
+
+        let table = Table::from_value(&json!({ "deleted": true, "response": { "ok": 1 } }));
+        assert_eq!(table.columns, vec!["field", "value"]);
+        assert_eq!(table.rows[1], vec!["response", "{\"ok\":1}"]);
+    }
+
+    #[test]
+    fn render_yaml_emits_block_style_and_quotes_ambiguous_strings() {
+        let value = json!([
+            {
+                "id": 7,
+                "title": "Ship it: now",
+                "priority": "P1",
+                "done": false,
+                "notes": null,
+                "tags": ["a", "yes"],
+                "assist": { "taskId": 7 },
+                "empty": []
+            }
+        ]);
+
+        assert_eq!(
+            render_yaml(&value),
+            "- assist:\n    taskId: 7\n  done: false\n  empty: []\n  id: 7\n  notes: null\n  priority: P1\n  tags:\n    - a\n    - \"yes\"\n  title: \"Ship it: now\"\n"
+        );
+        assert_eq!(render_yaml(&json!("2026-02-21")), "\"2026-02-21\"\n");
+        assert_eq!(render_yaml(&json!("me@home")), "me@home\n");
+        for text in ["@home", "`cmd`", "&anchor", "!tag"] {
+            assert_eq!(render_yaml(&json!(text)), format!("{:?}\n", text));
+        }
+    }
+
+    #[test]
+    fn records_keep_their_key_order() {
+        let records = [Record(vec![
+            ("title".to_string(), json!("Ship it")),
+            ("id".to_string(), json!(7)),
+            ("assist.type".to_string(), Value::Null),
+        ])];
+
+        assert_eq!(
+            serde_json::to_string(&records).unwrap(),
+            r#"[{"title":"Ship it","id":7,"assist.type":null}]"#
+        );
+        assert_eq!(
+            render_yaml_records(&records),
+            "- title: Ship it\n  id: 7\n  assist.type: null\n"
+        );
+        assert_eq!(render_yaml_records(&[]), "[]\n");
+    }
+}
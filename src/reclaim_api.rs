@@ -1,48 +1,98 @@
-use crate::error::CliError;
+use crate::{
+    cache::CachedList,
+    error::CliError,
+    retry::{self, RetryPolicy},
+};
+use chrono::Utc;
 use reqwest::{
-    header::{self, HeaderMap},
-    Client, Method, RequestBuilder, Url,
+    header::{self, HeaderMap, HeaderName, HeaderValue},
+    Client, Method, RequestBuilder, Response, Url,
+};
+use serde::{
+    de::{self, DeserializeOwned},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
 };
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{collections::HashMap, time::Duration};
 
 const USER_AGENT: &str = concat!("reclaim-cli/", env!("CARGO_PKG_VERSION"));
 const DEBUG_BODY_LIMIT: usize = 8_192;
 const DEBUG_SUMMARY_LIMIT: usize = 512;
 
 pub trait ReclaimApi {
+    async fn current_user(&self) -> Result<CurrentUser, CliError>;
     async fn list_tasks(&self, filter: TaskFilter) -> Result<Vec<Task>, CliError>;
+    /// Tasks kept from an earlier fetch, to show while a fresh list loads. Clients without
+    /// a local cache have none.
+    async fn cached_tasks(&self, _filter: TaskFilter) -> Option<CachedList<Task>> {
+        None
+    }
     async fn get_task(&self, task_id: u64) -> Result<Task, CliError>;
-    async fn create_task(&self, request: CreateTaskRequest) -> Result<Task, CliError>;
-    async fn list_events(&self, query: EventListQuery) -> Result<Vec<serde_json::Value>, CliError>;
+    async fn create_task(&self, request: CreateTaskRequest) -> Result<Outcome<Task>, CliError>;
+    async fn list_events(&self, query: EventListQuery) -> Result<Vec<Event>, CliError>;
     async fn get_event(
         &self,
         calendar_id: u64,
         event_id: &str,
         source_details: Option<bool>,
         thin: Option<bool>,
-    ) -> Result<serde_json::Value, CliError>;
+    ) -> Result<Event, CliError>;
     async fn apply_schedule_actions(
         &self,
         request: serde_json::Value,
-    ) -> Result<serde_json::Value, CliError>;
+    ) -> Result<Outcome<serde_json::Value>, CliError>;
     async fn put_task(
         &self,
         task_id: u64,
         request: serde_json::Value,
         notification_key: Option<&str>,
-    ) -> Result<Task, CliError>;
+    ) -> Result<Outcome<Task>, CliError>;
     async fn patch_task(
         &self,
         task_id: u64,
         request: serde_json::Value,
         notification_key: Option<&str>,
-    ) -> Result<Task, CliError>;
+    ) -> Result<Outcome<Task>, CliError>;
     async fn delete_task(
         &self,
         task_id: u64,
         notification_key: Option<&str>,
-    ) -> Result<serde_json::Value, CliError>;
+    ) -> Result<Outcome<serde_json::Value>, CliError>;
+    async fn run_planner_action(
+        &self,
+        task_id: u64,
+        action: PlannerAction,
+    ) -> Result<Outcome<Task>, CliError>;
+    /// Snoozes through the planner so Reclaim reschedules the task; `until` is RFC 3339.
+    async fn snooze_task(&self, task_id: u64, until: &str) -> Result<Outcome<Task>, CliError>;
+    async fn log_work(&self, task_id: u64, minutes: u32) -> Result<Outcome<Task>, CliError>;
+}
+
+/// What a write did: `Sent` with Reclaim's answer, or only `Planned` because `--dry-run`
+/// stopped it before it left the client.
+#[derive(Debug)]
+pub enum Outcome<T> {
+    Sent(T),
+    Planned(PlannedRequest),
+}
+
+impl<T> Outcome<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Outcome<U> {
+        match self {
+            Outcome::Sent(value) => Outcome::Sent(f(value)),
+            Outcome::Planned(request) => Outcome::Planned(request),
+        }
+    }
+}
+
+/// A request `--dry-run` kept back: the method, full URL and JSON body that would be sent.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlannedRequest {
+    pub method: String,
+    pub url: String,
+    pub body: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -51,6 +101,35 @@ pub enum TaskFilter {
     All,
 }
 
+impl TaskFilter {
+    pub fn includes(self, task: &Task) -> bool {
+        match self {
+            TaskFilter::Active => is_active_task(task),
+            TaskFilter::All => true,
+        }
+    }
+}
+
+/// Task actions exposed by Reclaim's planner (`/planner/{action}/task/{id}`).
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PlannerAction {
+    Done,
+    Start,
+    Stop,
+    Reopen,
+}
+
+impl PlannerAction {
+    fn path_segment(self) -> &'static str {
+        match self {
+            PlannerAction::Done => "done",
+            PlannerAction::Start => "start",
+            PlannerAction::Stop => "stop",
+            PlannerAction::Reopen => "unarchive",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct EventListQuery {
     pub calendar_ids: Vec<u64>,
@@ -61,8 +140,8 @@ pub struct EventListQuery {
     pub thin: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct CreateTaskRequest {
     pub title: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -98,10 +177,210 @@ pub struct Task {
     pub extra: HashMap<String, serde_json::Value>,
 }
 
+/// Calendar event as returned by `/events`.
+///
+/// Optional fields are skipped when absent so that serializing an `Event` reproduces the
+/// API payload; anything not modeled here, and any field the API sent as `null`, is kept
+/// in `extra`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", remote = "Self")]
+pub struct Event {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calendar_id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_start: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_end: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reclaim_managed: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reclaim_event_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assist: Option<EventAssist>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attendees: Option<Vec<EventAttendee>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", remote = "Self")]
+pub struct EventAssist {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub habit_id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", remote = "Self")]
+pub struct EventAttendee {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_status: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+// The derives above are `remote = "Self"`, which turns them into inherent functions; these
+// impls wrap them so explicit `null`s survive a round trip.
+macro_rules! keep_explicit_nulls {
+    ($($model:ty),+) => {$(
+        impl Serialize for $model {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                <$model>::serialize(self, serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $model {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let (fields, nulls) = split_nulls(deserializer)?;
+                let mut model = <$model>::deserialize(fields).map_err(de::Error::custom)?;
+                model
+                    .extra
+                    .extend(nulls.into_iter().map(|key| (key, serde_json::Value::Null)));
+                Ok(model)
+            }
+        }
+    )+};
+}
+
+keep_explicit_nulls!(Event, EventAssist, EventAttendee);
+
+/// Reads a JSON object and holds back its `null` members. A modeled `Option` field would
+/// otherwise read `null` as `None` and then skip it on output.
+fn split_nulls<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<(serde_json::Value, Vec<String>), D::Error> {
+    let mut fields = serde_json::Map::deserialize(deserializer)?;
+    let nulls = fields
+        .iter()
+        .filter(|(_, value)| value.is_null())
+        .map(|(key, _)| key.clone())
+        .collect();
+    fields.retain(|_, value| !value.is_null());
+    Ok((serde_json::Value::Object(fields), nulls))
+}
+
+impl Task {
+    /// Total scheduled time in 15-minute chunks (`timeChunksRequired`).
+    pub fn time_chunks_required(&self) -> Option<u32> {
+        self.extra_chunks("timeChunksRequired")
+    }
+
+    pub fn time_chunks_remaining(&self) -> Option<u32> {
+        self.extra_chunks("timeChunksRemaining")
+    }
+
+    pub fn min_chunk_size(&self) -> Option<u32> {
+        self.extra_chunks("minChunkSize")
+    }
+
+    pub fn max_chunk_size(&self) -> Option<u32> {
+        self.extra_chunks("maxChunkSize")
+    }
+
+    fn extra_chunks(&self, field: &str) -> Option<u32> {
+        self.extra
+            .get(field)
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|chunks| u32::try_from(chunks).ok())
+    }
+}
+
+impl Event {
+    /// Event key, falling back to `eventKey` or `calendarId/eventId`.
+    pub fn display_key(&self) -> Option<String> {
+        self.key
+            .clone()
+            .or_else(|| self.extra_text(&["/eventKey"]))
+            .or_else(|| match (self.calendar_id, self.event_id.as_deref()) {
+                (Some(calendar_id), Some(event_id)) => Some(format!("{calendar_id}/{event_id}")),
+                _ => None,
+            })
+    }
+
+    pub fn start(&self) -> Option<String> {
+        self.event_start.clone().or_else(|| {
+            self.extra_text(&["/eventDate/start", "/dateRange/start", "/originalStart"])
+        })
+    }
+
+    pub fn end(&self) -> Option<String> {
+        self.event_end
+            .clone()
+            .or_else(|| self.extra_text(&["/eventDate/end", "/dateRange/end", "/originalEnd"]))
+    }
+
+    pub fn is_reclaim_managed(&self) -> bool {
+        self.reclaim_managed.unwrap_or(false)
+    }
+
+    /// Task this event was scheduled for, when it is a Reclaim task block.
+    pub fn task_id(&self) -> Option<u64> {
+        self.assist.as_ref().and_then(|assist| assist.task_id)
+    }
+
+    fn extra_text(&self, pointers: &[&str]) -> Option<String> {
+        pointers.iter().find_map(|pointer| {
+            let pointer = pointer.trim_start_matches('/');
+            let (field, rest) = match pointer.split_once('/') {
+                Some((field, rest)) => (field, format!("/{rest}")),
+                None => (pointer, String::new()),
+            };
+            self.extra
+                .get(field)?
+                .pointer(&rest)?
+                .as_str()
+                .map(str::to_string)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrentUser {
+    pub email: Option<String>,
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// How much HTTP traffic `HttpReclaimApi` prints to stderr.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TraceLevel {
+    #[default]
+    Off,
+    /// Method, URL, status and latency of every attempt.
+    Verbose,
+    /// Also headers (credentials redacted) and truncated bodies.
+    Trace,
+}
+
 pub struct HttpReclaimApi {
     client: Client,
     base_url: Url,
     api_key: String,
+    retry: RetryPolicy,
+    trace: TraceLevel,
+    dry_run: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -111,11 +390,51 @@ struct RequestDebugInfo {
     body: Option<String>,
 }
 
+/// How a request fared across retries, added to the error context when it fails.
+#[derive(Debug, Clone, Default)]
+struct RetryReport {
+    attempts: u32,
+    /// Why a retryable failure was not retried.
+    skipped: Option<String>,
+}
+
+impl RetryReport {
+    fn annotate(&self, error: CliError) -> CliError {
+        let line = match &self.skipped {
+            Some(reason) => format!("Attempts: {} ({reason})", self.attempts),
+            None if self.attempts > 1 => format!(
+                "Attempts: {} ({} retries)",
+                self.attempts,
+                self.attempts - 1
+            ),
+            None => return error,
+        };
+
+        match error {
+            CliError::Api {
+                status,
+                message,
+                hint,
+            } => CliError::Api {
+                status,
+                message: format!("{message}\n{line}"),
+                hint,
+            },
+            CliError::Transport { message, hint } => CliError::Transport {
+                message: format!("{message}\n{line}"),
+                hint,
+            },
+            error => error,
+        }
+    }
+}
+
 impl HttpReclaimApi {
     pub fn new(
         api_key: Option<String>,
         base_url: String,
         timeout_secs: u64,
+        retry: RetryPolicy,
     ) -> Result<Self, CliError> {
         let api_key = api_key
             .map(|value| value.trim().to_string())
@@ -138,6 +457,45 @@ impl HttpReclaimApi {
             client,
             base_url,
             api_key,
+            retry,
+            trace: TraceLevel::Off,
+            dry_run: false,
+        })
+    }
+
+    pub fn with_trace(mut self, trace: TraceLevel) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// With `--dry-run`, describes any request but a GET instead of letting it be sent.
+    fn planned(&self, request: &RequestBuilder) -> Option<PlannedRequest> {
+        if !self.dry_run {
+            return None;
+        }
+        let request = request.try_clone()?.build().ok()?;
+        if request.method() == Method::GET {
+            return None;
+        }
+
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .filter(|bytes| !bytes.is_empty())
+            .map(|bytes| {
+                serde_json::from_slice(bytes).unwrap_or_else(|_| {
+                    serde_json::Value::String(String::from_utf8_lossy(bytes).into_owned())
+                })
+            });
+        Some(PlannedRequest {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            body,
         })
     }
 
@@ -171,12 +529,151 @@ impl HttpReclaimApi {
         }
     }
 
+    /// Sends `request`, repeating it on 429, 502/503/504 and connection failures as the
+    /// retry policy allows. Returns the last outcome and how many attempts were made.
+    async fn execute(
+        &self,
+        request: RequestBuilder,
+    ) -> (Result<Response, reqwest::Error>, RetryReport) {
+        let method = request
+            .try_clone()
+            .and_then(|request| request.build().ok())
+            .map(|request| request.method().clone());
+        let method_allowed = method
+            .as_ref()
+            .is_some_and(|method| self.retry.allows_method(method));
+
+        let mut report = RetryReport::default();
+        let mut request = request;
+        loop {
+            report.attempts += 1;
+            let next = request.try_clone();
+            self.trace_request(&request);
+            let started = Instant::now();
+            let outcome = request.send().await;
+            self.trace_outcome(&outcome, started.elapsed());
+
+            let (allowed, retry_after) = match &outcome {
+                Ok(response) if retry::is_retryable_status(response.status()) => (
+                    method_allowed,
+                    retry::retry_after(response.headers(), Utc::now()),
+                ),
+                // Nothing reached Reclaim, so any method is safe to repeat.
+                Err(error) if error.is_connect() => (true, None),
+                Err(error) if error.is_timeout() => (method_allowed, None),
+                _ => return (outcome, report),
+            };
+            let Some(next) = next.filter(|_| report.attempts <= self.retry.retries) else {
+                return (outcome, report);
+            };
+            if !allowed {
+                if let Some(method) = method {
+                    report.skipped = Some(format!(
+                        "{method} requests are not retried automatically; pass --retry-non-idempotent"
+                    ));
+                }
+                return (outcome, report);
+            }
+            let Some(delay) = self.retry.delay(report.attempts, retry_after) else {
+                report.skipped = Some(format!(
+                    "Retry-After of {}s exceeds --max-retry-wait {}s",
+                    retry_after.unwrap_or_default().as_secs(),
+                    self.retry.max_wait.as_secs()
+                ));
+                return (outcome, report);
+            };
+
+            if self.trace != TraceLevel::Off {
+                eprintln!(
+                    "* retrying in {:.1}s (attempt {} of {})",
+                    delay.as_secs_f64(),
+                    report.attempts + 1,
+                    self.retry.retries + 1
+                );
+            }
+            tokio::time::sleep(delay).await;
+            request = next;
+        }
+    }
+
+    fn trace_request(&self, request: &RequestBuilder) {
+        if self.trace == TraceLevel::Off {
+            return;
+        }
+        let Some(request) = request.try_clone().and_then(|request| request.build().ok()) else {
+            return;
+        };
+
+        eprintln!("> {} {}", request.method(), request.url());
+        if self.trace == TraceLevel::Trace {
+            for line in format_headers('>', request.headers()) {
+                eprintln!("{line}");
+            }
+            if let Some(body) = request.body().and_then(|body| body.as_bytes()) {
+                self.trace_body('>', &String::from_utf8_lossy(body));
+            }
+        }
+    }
+
+    fn trace_outcome(&self, outcome: &Result<Response, reqwest::Error>, elapsed: Duration) {
+        if self.trace == TraceLevel::Off {
+            return;
+        }
+
+        match outcome {
+            Ok(response) => {
+                eprintln!("< {} ({} ms)", response.status(), elapsed.as_millis());
+                if self.trace == TraceLevel::Trace {
+                    for line in format_headers('<', response.headers()) {
+                        eprintln!("{line}");
+                    }
+                }
+            }
+            Err(error) => eprintln!("* failed after {} ms: {error}", elapsed.as_millis()),
+        }
+    }
+
+    fn trace_body(&self, prefix: char, body: &str) {
+        let body = body.trim();
+        if self.trace == TraceLevel::Trace && !body.is_empty() {
+            eprintln!("{prefix} {}", truncate_debug_text(body, DEBUG_BODY_LIMIT));
+        }
+    }
+
+    /// Sends a request that changes data, unless `--dry-run` turns it into a plan.
+    async fn send_write<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<Outcome<T>, CliError> {
+        match self.planned(&request) {
+            Some(planned) => Ok(Outcome::Planned(planned)),
+            None => self.send_json(request).await.map(Outcome::Sent),
+        }
+    }
+
+    /// Runs a planner endpoint and returns the task it changed, re-fetching it when the
+    /// response does not carry it.
+    async fn send_planner_write(
+        &self,
+        task_id: u64,
+        request: RequestBuilder,
+    ) -> Result<Outcome<Task>, CliError> {
+        if let Some(planned) = self.planned(&request) {
+            return Ok(Outcome::Planned(planned));
+        }
+        let response = self.send_json_value_or_null(request).await?;
+
+        match task_from_planner_response(response) {
+            Some(task) => Ok(Outcome::Sent(task)),
+            None => self.get_task(task_id).await.map(Outcome::Sent),
+        }
+    }
+
     async fn send_json<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, CliError> {
         let request_debug = capture_request_debug(&request);
-        let response = request
-            .send()
-            .await
-            .map_err(|error| map_transport_error(error, request_debug.as_ref()))?;
+        let (response, report) = self.execute(request).await;
+        let response = response
+            .map_err(|error| report.annotate(map_transport_error(error, request_debug.as_ref())))?;
         let status = response.status();
         let response_url = response.url().to_string();
         let response_headers = response.headers().clone();
@@ -190,6 +687,7 @@ impl HttpReclaimApi {
                     .to_string(),
             ),
         })?;
+        self.trace_body('<', &response_body);
 
         if status.is_success() {
             serde_json::from_str::<T>(&response_body).map_err(|error| {
@@ -224,13 +722,13 @@ impl HttpReclaimApi {
                 }
             })
         } else {
-            Err(parse_api_error(
+            Err(report.annotate(parse_api_error(
                 status.as_u16(),
                 &response_body,
                 &response_url,
                 &response_headers,
                 request_debug.as_ref(),
-            ))
+            )))
         }
     }
 
@@ -239,10 +737,9 @@ impl HttpReclaimApi {
         request: RequestBuilder,
     ) -> Result<serde_json::Value, CliError> {
         let request_debug = capture_request_debug(&request);
-        let response = request
-            .send()
-            .await
-            .map_err(|error| map_transport_error(error, request_debug.as_ref()))?;
+        let (response, report) = self.execute(request).await;
+        let response = response
+            .map_err(|error| report.annotate(map_transport_error(error, request_debug.as_ref())))?;
         let status = response.status();
         let response_url = response.url().to_string();
         let response_headers = response.headers().clone();
@@ -256,6 +753,7 @@ impl HttpReclaimApi {
                     .to_string(),
             ),
         })?;
+        self.trace_body('<', &response_body);
 
         if status.is_success() {
             let body = response_body.trim();
@@ -290,24 +788,26 @@ impl HttpReclaimApi {
                 }
             })
         } else {
-            Err(parse_api_error(
+            Err(report.annotate(parse_api_error(
                 status.as_u16(),
                 &response_body,
                 &response_url,
                 &response_headers,
                 request_debug.as_ref(),
-            ))
+            )))
         }
     }
 }
 
 impl ReclaimApi for HttpReclaimApi {
+    async fn current_user(&self) -> Result<CurrentUser, CliError> {
+        self.send_json(self.request(Method::GET, "users/current"))
+            .await
+    }
+
     async fn list_tasks(&self, filter: TaskFilter) -> Result<Vec<Task>, CliError> {
         let mut tasks: Vec<Task> = self.send_json(self.request(Method::GET, "tasks")).await?;
-
-        if matches!(filter, TaskFilter::Active) {
-            tasks.retain(is_active_task);
-        }
+        tasks.retain(|task| filter.includes(task));
 
         Ok(tasks)
     }
@@ -317,12 +817,12 @@ impl ReclaimApi for HttpReclaimApi {
             .await
     }
 
-    async fn create_task(&self, request: CreateTaskRequest) -> Result<Task, CliError> {
-        self.send_json(self.request(Method::POST, "tasks").json(&request))
+    async fn create_task(&self, request: CreateTaskRequest) -> Result<Outcome<Task>, CliError> {
+        self.send_write(self.request(Method::POST, "tasks").json(&request))
             .await
     }
 
-    async fn list_events(&self, query: EventListQuery) -> Result<Vec<serde_json::Value>, CliError> {
+    async fn list_events(&self, query: EventListQuery) -> Result<Vec<Event>, CliError> {
         let mut request = self.request(Method::GET, "events");
         let mut query_pairs: Vec<(String, String)> = Vec::new();
 
@@ -373,7 +873,7 @@ impl ReclaimApi for HttpReclaimApi {
         event_id: &str,
         source_details: Option<bool>,
         thin: Option<bool>,
-    ) -> Result<serde_json::Value, CliError> {
+    ) -> Result<Event, CliError> {
         let mut request = self.request(Method::GET, &format!("events/{calendar_id}/{event_id}"));
         let mut query_pairs: Vec<(String, String)> = Vec::new();
 
@@ -394,8 +894,8 @@ impl ReclaimApi for HttpReclaimApi {
     async fn apply_schedule_actions(
         &self,
         request: serde_json::Value,
-    ) -> Result<serde_json::Value, CliError> {
-        self.send_json(
+    ) -> Result<Outcome<serde_json::Value>, CliError> {
+        self.send_write(
             self.request(Method::POST, "schedule-actions/apply-actions")
                 .json(&request),
         )
@@ -407,8 +907,8 @@ impl ReclaimApi for HttpReclaimApi {
         task_id: u64,
         request: serde_json::Value,
         notification_key: Option<&str>,
-    ) -> Result<Task, CliError> {
-        self.send_json(
+    ) -> Result<Outcome<Task>, CliError> {
+        self.send_write(
             self.request_with_notification_key(
                 Method::PUT,
                 &format!("tasks/{task_id}"),
@@ -424,8 +924,8 @@ impl ReclaimApi for HttpReclaimApi {
         task_id: u64,
         request: serde_json::Value,
         notification_key: Option<&str>,
-    ) -> Result<Task, CliError> {
-        self.send_json(
+    ) -> Result<Outcome<Task>, CliError> {
+        self.send_write(
             self.request_with_notification_key(
                 Method::PATCH,
                 &format!("tasks/{task_id}"),
@@ -440,16 +940,79 @@ impl ReclaimApi for HttpReclaimApi {
         &self,
         task_id: u64,
         notification_key: Option<&str>,
-    ) -> Result<serde_json::Value, CliError> {
-        self.send_json_value_or_null(self.request_with_notification_key(
+    ) -> Result<Outcome<serde_json::Value>, CliError> {
+        let request = self.request_with_notification_key(
             Method::DELETE,
             &format!("tasks/{task_id}"),
             notification_key,
-        ))
+        );
+        match self.planned(&request) {
+            Some(planned) => Ok(Outcome::Planned(planned)),
+            None => self
+                .send_json_value_or_null(request)
+                .await
+                .map(Outcome::Sent),
+        }
+    }
+
+    async fn run_planner_action(
+        &self,
+        task_id: u64,
+        action: PlannerAction,
+    ) -> Result<Outcome<Task>, CliError> {
+        self.send_planner_write(
+            task_id,
+            self.request(
+                Method::POST,
+                &format!("planner/{}/task/{task_id}", action.path_segment()),
+            ),
+        )
+        .await
+    }
+
+    async fn snooze_task(&self, task_id: u64, until: &str) -> Result<Outcome<Task>, CliError> {
+        let until = chrono::DateTime::parse_from_rfc3339(until).map_err(|error| {
+            CliError::InvalidInput {
+                message: format!("Invalid snooze time '{until}': {error}."),
+                hint: None,
+            }
+        })?;
+        // The planner only snoozes by preset offsets from a reference time, so ask for
+        // "15 minutes from" a quarter hour before `until`.
+        let relative_from = (until.with_timezone(&Utc) - chrono::Duration::minutes(15))
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        self.send_planner_write(
+            task_id,
+            self.request(Method::POST, &format!("planner/task/{task_id}/snooze"))
+                .query(&[
+                    ("snoozeOption", "FROM_NOW_15M"),
+                    ("relativeFrom", relative_from.as_str()),
+                ]),
+        )
+        .await
+    }
+
+    async fn log_work(&self, task_id: u64, minutes: u32) -> Result<Outcome<Task>, CliError> {
+        self.send_planner_write(
+            task_id,
+            self.request(Method::POST, &format!("planner/log-work/task/{task_id}"))
+                .query(&[("minutes", minutes)]),
+        )
         .await
     }
 }
 
+/// Planner actions answer with `{ "taskOrHabit": {...}, "events": [...] }`; older responses
+/// may be empty, in which case the caller re-fetches the task.
+fn task_from_planner_response(response: serde_json::Value) -> Option<Task> {
+    let task = match response {
+        serde_json::Value::Object(mut object) => object.remove("taskOrHabit")?,
+        _ => return None,
+    };
+
+    serde_json::from_value(task).ok()
+}
+
 fn normalize_base_url(raw: &str) -> Result<Url, CliError> {
     let mut url = Url::parse(raw).map_err(|_| CliError::InvalidBaseUrl(raw.to_string()))?;
 
@@ -465,6 +1028,28 @@ fn is_active_task(task: &Task) -> bool {
     !task.deleted && !matches!(task.status.as_deref(), Some("ARCHIVED" | "CANCELLED"))
 }
 
+/// Header lines for `--trace`. Credentials are always redacted; for `Authorization` only
+/// the scheme is kept.
+fn format_headers(prefix: char, headers: &HeaderMap) -> Vec<String> {
+    headers
+        .iter()
+        .map(|(name, value)| format!("{prefix} {name}: {}", redacted_header_value(name, value)))
+        .collect()
+}
+
+fn redacted_header_value(name: &HeaderName, value: &HeaderValue) -> String {
+    let text = value.to_str().unwrap_or("<binary>");
+    match name.as_str() {
+        "authorization" | "proxy-authorization" => match text.split_once(' ') {
+            Some((scheme, _)) => format!("{scheme} <redacted>"),
+            None => "<redacted>".to_string(),
+        },
+        "cookie" | "set-cookie" => "<redacted>".to_string(),
+        _ if value.is_sensitive() => "<redacted>".to_string(),
+        _ => text.to_string(),
+    }
+}
+
 fn capture_request_debug(request: &RequestBuilder) -> Option<RequestDebugInfo> {
     let request = request.try_clone()?.build().ok()?;
     let body = request
@@ -673,10 +1258,13 @@ fn hint_for_status(status: u16) -> Option<String> {
                 .to_string(),
         ),
         401 | 403 => {
-            Some("Set a valid API key with RECLAIM_API_KEY or --api-key, then retry.".to_string())
+            Some("Set a valid API key with RECLAIM_API_KEY, --api-key, or `reclaim auth login`, then retry.".to_string())
         }
         404 => Some("Verify the task ID exists in your Reclaim account.".to_string()),
-        429 => Some("Rate limited by Reclaim. Wait a few seconds and retry.".to_string()),
+        429 => Some(
+            "Rate limited by Reclaim. Wait a few seconds and retry, or raise --retries/--max-retry-wait."
+                .to_string(),
+        ),
         500..=599 => Some(
             "Reclaim returned a 5xx. This can be an outage OR a rejected payload surfaced as internal_error. Compare the request payload above with a known-good request."
                 .to_string(),
@@ -762,12 +1350,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn event_round_trips_without_losing_fields() {
+        let raw = serde_json::json!({
+            "key": "829105/abc",
+            "eventId": "abc",
+            "calendarId": 829105,
+            "title": "Focus: write spec",
+            "eventStart": "2026-02-21T18:30:00Z",
+            "eventEnd": "2026-02-21T19:00:00Z",
+            "reclaimManaged": true,
+            "assist": { "taskId": 42, "conferenceBuffer": false },
+            "attendees": [{ "email": "me@example.com", "organizer": true }],
+            "color": "LAVENDER"
+        });
+
+        let event: Event = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(event.task_id(), Some(42));
+        assert!(event.is_reclaim_managed());
+        assert_eq!(serde_json::to_value(&event).unwrap(), raw);
+    }
+
+    #[test]
+    fn event_round_trip_keeps_explicit_nulls() {
+        let raw = serde_json::json!({
+            "key": "829105/abc",
+            "title": null,
+            "eventStart": "2026-02-21T18:30:00Z",
+            "priority": null,
+            "assist": { "taskId": 42, "habitId": null },
+            "attendees": [{ "email": "me@example.com", "responseStatus": null }],
+            "location": null
+        });
+
+        let event: Event = serde_json::from_value(raw.clone()).unwrap();
+        assert_eq!(event.title, None);
+        assert_eq!(event.task_id(), Some(42));
+        assert_eq!(serde_json::to_value(&event).unwrap(), raw);
+    }
+
+    #[test]
+    fn event_start_falls_back_to_nested_date_fields() {
+        let event: Event = serde_json::from_value(serde_json::json!({
+            "eventKey": "1/x",
+            "dateRange": { "start": "2026-02-21T18:30:00Z", "end": "2026-02-21T19:00:00Z" }
+        }))
+        .unwrap();
+
+        assert_eq!(event.start().as_deref(), Some("2026-02-21T18:30:00Z"));
+        assert_eq!(event.end().as_deref(), Some("2026-02-21T19:00:00Z"));
+        assert_eq!(event.display_key().as_deref(), Some("1/x"));
+    }
+
+    #[test]
+    fn task_from_planner_response_reads_task_or_habit() {
+        let task = task_from_planner_response(serde_json::json!({
+            "taskOrHabit": { "id": 7, "title": "Ship it", "status": "COMPLETE" },
+            "events": []
+        }))
+        .expect("task should be extracted");
+
+        assert_eq!(task.id, 7);
+        assert_eq!(task.status.as_deref(), Some("COMPLETE"));
+        assert!(task_from_planner_response(serde_json::Value::Null).is_none());
+        assert!(task_from_planner_response(serde_json::json!({ "events": [] })).is_none());
+    }
+
     #[test]
     fn request_with_notification_key_adds_query_param() {
         let api = HttpReclaimApi::new(
             Some("key".to_string()),
             "https://api.app.reclaim.ai/api".to_string(),
             15,
+            RetryPolicy::default(),
         )
         .expect("api should initialize");
 
@@ -788,6 +1443,7 @@ mod tests {
             Some("key".to_string()),
             "https://api.app.reclaim.ai/api".to_string(),
             15,
+            RetryPolicy::default(),
         )
         .expect("api should initialize");
 
@@ -801,4 +1457,218 @@ mod tests {
             "https://api.app.reclaim.ai/api/tasks/123"
         );
     }
+
+    #[test]
+    fn trace_headers_redact_credentials() {
+        let api = HttpReclaimApi::new(
+            Some("secret-key-1234".to_string()),
+            "https://api.app.reclaim.ai/api".to_string(),
+            15,
+            RetryPolicy::default(),
+        )
+        .expect("api should initialize");
+        let mut request = api
+            .request(Method::GET, "tasks")
+            .header(header::COOKIE, "session=abc")
+            .build()
+            .expect("request should build");
+        request
+            .headers_mut()
+            .insert("x-api-key", HeaderValue::from_static("k"));
+        request
+            .headers_mut()
+            .get_mut("x-api-key")
+            .unwrap()
+            .set_sensitive(true);
+
+        let lines = format_headers('>', request.headers());
+        assert!(lines.contains(&"> authorization: Bearer <redacted>".to_string()));
+        assert!(lines.contains(&"> cookie: <redacted>".to_string()));
+        assert!(lines.contains(&"> x-api-key: <redacted>".to_string()));
+        assert!(lines.contains(&"> accept: application/json".to_string()));
+        assert!(lines.iter().all(|line| !line.contains("secret-key")));
+    }
+
+    /// Serves the canned responses in order, one connection each, and returns the request
+    /// lines it saw once all were served.
+    fn serve(responses: Vec<String>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let base_url = format!("http://{}/api", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut seen = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().expect("accept");
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 4096];
+                while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                    let read = stream.read(&mut buffer).expect("read request");
+                    if read == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buffer[..read]);
+                }
+                let request = String::from_utf8_lossy(&request).to_string();
+                seen.push(request.lines().next().unwrap_or_default().to_string());
+                stream
+                    .write_all(response.as_bytes())
+                    .expect("write response");
+            }
+            seen
+        });
+        (base_url, handle)
+    }
+
+    fn response(status: &str, headers: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {status}\r\ncontent-type: application/json\r\n{headers}content-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    fn mock_api(base_url: String, retry: RetryPolicy) -> HttpReclaimApi {
+        let retry = RetryPolicy {
+            base_delay: Duration::from_millis(1),
+            ..retry
+        };
+        HttpReclaimApi::new(Some("key".to_string()), base_url, 5, retry)
+            .expect("api should initialize")
+    }
+
+    #[tokio::test]
+    async fn get_retries_gateway_errors_and_honors_retry_after() {
+        let (base_url, server) = serve(vec![
+            response("503 Service Unavailable", "retry-after: 0\r\n", "{}"),
+            response("429 Too Many Requests", "", "{}"),
+            response("200 OK", "", r#"{"id":7,"title":"Ship it"}"#),
+        ]);
+        let api = mock_api(base_url, RetryPolicy::default());
+
+        let task = api.get_task(7).await.expect("third attempt succeeds");
+        assert_eq!(task.title, "Ship it");
+        assert_eq!(server.join().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn exhausted_retries_are_reported_in_the_error() {
+        let busy = response("429 Too Many Requests", "", r#"{"message":"slow down"}"#);
+        let (base_url, server) = serve(vec![busy.clone(), busy.clone(), busy]);
+        let api = mock_api(
+            base_url,
+            RetryPolicy {
+                retries: 2,
+                ..RetryPolicy::default()
+            },
+        );
+
+        let error = api.get_task(7).await.unwrap_err().to_string();
+        assert!(error.contains("HTTP 429"));
+        assert!(error.contains("Attempts: 3 (2 retries)"), "{error}");
+        assert_eq!(server.join().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn post_is_only_retried_when_opted_in() {
+        let (base_url, server) = serve(vec![response("502 Bad Gateway", "", "{}")]);
+        let api = mock_api(base_url, RetryPolicy::default());
+        let error = api
+            .apply_schedule_actions(serde_json::json!({ "actionsTaken": [] }))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("pass --retry-non-idempotent"), "{error}");
+        assert_eq!(server.join().unwrap().len(), 1);
+
+        let (base_url, server) = serve(vec![
+            response("502 Bad Gateway", "", "{}"),
+            response("200 OK", "", r#"{"applied":true}"#),
+        ]);
+        let api = mock_api(
+            base_url,
+            RetryPolicy {
+                retry_non_idempotent: true,
+                ..RetryPolicy::default()
+            },
+        );
+        let Ok(Outcome::Sent(applied)) = api
+            .apply_schedule_actions(serde_json::json!({ "actionsTaken": [] }))
+            .await
+        else {
+            panic!("retried POST succeeds");
+        };
+        assert_eq!(applied["applied"], true);
+        assert_eq!(
+            server.join().unwrap(),
+            vec!["POST /api/schedule-actions/apply-actions HTTP/1.1"; 2]
+        );
+    }
+
+    #[tokio::test]
+    async fn dry_run_sends_reads_but_stops_writes() {
+        let (base_url, server) = serve(vec![response(
+            "200 OK",
+            "",
+            r#"{"id":7,"title":"Ship it"}"#,
+        )]);
+        let api = mock_api(base_url.clone(), RetryPolicy::default()).with_dry_run(true);
+
+        assert_eq!(api.get_task(7).await.unwrap().title, "Ship it");
+        let outcome = api
+            .patch_task(7, serde_json::json!({ "priority": "P1" }), Some("note"))
+            .await
+            .unwrap();
+        let Outcome::Planned(planned) = outcome else {
+            panic!("expected a planned request, got {outcome:?}");
+        };
+        assert_eq!(
+            planned,
+            PlannedRequest {
+                method: "PATCH".to_string(),
+                url: format!("{base_url}/tasks/7?notificationKey=note"),
+                body: Some(serde_json::json!({ "priority": "P1" })),
+            }
+        );
+        assert!(matches!(
+            api.delete_task(7, None).await,
+            Ok(Outcome::Planned(_))
+        ));
+        assert_eq!(server.join().unwrap(), ["GET /api/tasks/7 HTTP/1.1"]);
+    }
+
+    #[tokio::test]
+    async fn snooze_goes_through_the_planner() {
+        let (base_url, server) = serve(vec![response(
+            "200 OK",
+            "",
+            r#"{"taskOrHabit":{"id":7,"title":"Ship it","snoozeUntil":"2026-02-21T09:00:00Z"}}"#,
+        )]);
+        let api = mock_api(base_url, RetryPolicy::default());
+
+        let Ok(Outcome::Sent(task)) = api.snooze_task(7, "2026-02-21T09:00:00Z").await else {
+            panic!("expected the snoozed task");
+        };
+        assert_eq!(task.extra["snoozeUntil"], "2026-02-21T09:00:00Z");
+        assert_eq!(
+            server.join().unwrap(),
+            ["POST /api/planner/task/7/snooze?snoozeOption=FROM_NOW_15M&relativeFrom=2026-02-21T08%3A45%3A00Z HTTP/1.1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_after_beyond_the_cap_stops_retrying() {
+        let (base_url, server) = serve(vec![response(
+            "503 Service Unavailable",
+            "retry-after: 120\r\n",
+            "{}",
+        )]);
+        let api = mock_api(base_url, RetryPolicy::default());
+
+        let error = api.get_task(7).await.unwrap_err().to_string();
+        assert!(
+            error.contains("Retry-After of 120s exceeds --max-retry-wait 30s"),
+            "{error}"
+        );
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
 }
@@ -0,0 +1,160 @@
+use chrono::{DateTime, Utc};
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    Method, StatusCode,
+};
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+pub const DEFAULT_RETRIES: u32 = 3;
+pub const DEFAULT_MAX_RETRY_WAIT_SECS: u64 = 30;
+const BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// When and how long `HttpReclaimApi` waits before repeating a failed request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts after the first one; 0 disables retries.
+    pub retries: u32,
+    /// Longest single wait. A longer `Retry-After` ends retrying instead of being shortened.
+    pub max_wait: Duration,
+    /// Also retry POST and PATCH, which can apply twice if the first attempt reached Reclaim.
+    pub retry_non_idempotent: bool,
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            retries: DEFAULT_RETRIES,
+            max_wait: Duration::from_secs(DEFAULT_MAX_RETRY_WAIT_SECS),
+            retry_non_idempotent: false,
+            base_delay: BASE_DELAY,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn allows_method(&self, method: &Method) -> bool {
+        self.retry_non_idempotent || method.is_idempotent()
+    }
+
+    /// Wait before the retry that follows `attempt` failed attempts. Uses `Retry-After` when
+    /// the server sent one, and `None` means it asked for longer than `max_wait`.
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        match retry_after {
+            Some(wait) if wait > self.max_wait => None,
+            Some(wait) => Some(wait),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+
+    /// Exponential backoff (`base_delay * 2^(attempt - 1)`, capped at `max_wait`) with
+    /// jitter that picks a wait between half and all of it.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        let ceiling = self.base_delay.saturating_mul(factor).min(self.max_wait);
+        let half = ceiling / 2;
+        half + half.mul_f64(jitter())
+    }
+}
+
+/// Statuses worth retrying: rate limiting and gateway errors. Other 5xx responses often
+/// mean a rejected payload and fail the same way again.
+pub fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(status.as_u16(), 429 | 502 | 503 | 504)
+}
+
+/// Reads `Retry-After` as delay seconds or an HTTP date.
+pub fn retry_after(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// A value in `[0, 1)`. Each `RandomState` gets fresh keys, which is enough randomness to
+/// keep concurrent clients from retrying in lockstep.
+fn jitter() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn backoff_grows_with_jitter_and_respects_the_cap() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_secs(1),
+            max_wait: Duration::from_secs(5),
+            ..RetryPolicy::default()
+        };
+
+        for (attempt, ceiling) in [(1, 1000), (2, 2000), (3, 4000), (4, 5000), (40, 5000)] {
+            let delay = policy.delay(attempt, None).unwrap().as_millis();
+            assert!(
+                (ceiling / 2..=ceiling).contains(&delay),
+                "attempt {attempt}: {delay}ms"
+            );
+        }
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(4))),
+            Some(Duration::from_secs(4))
+        );
+        assert_eq!(policy.delay(1, Some(Duration::from_secs(6))), None);
+    }
+
+    #[test]
+    fn retry_after_accepts_seconds_and_http_dates() {
+        let now = DateTime::parse_from_rfc3339("2026-02-18T15:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers, now), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        assert_eq!(retry_after(&headers, now), Some(Duration::from_secs(7)));
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 18 Feb 2026 15:00:12 GMT"),
+        );
+        assert_eq!(retry_after(&headers, now), Some(Duration::from_secs(12)));
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 18 Feb 2026 14:00:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers, now), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn only_idempotent_methods_retry_by_default() {
+        let policy = RetryPolicy::default();
+        assert!(policy.allows_method(&Method::GET));
+        assert!(policy.allows_method(&Method::PUT));
+        assert!(policy.allows_method(&Method::DELETE));
+        assert!(!policy.allows_method(&Method::POST));
+        assert!(!policy.allows_method(&Method::PATCH));
+
+        let opted_in = RetryPolicy {
+            retry_non_idempotent: true,
+            ..policy
+        };
+        assert!(opted_in.allows_method(&Method::POST));
+    }
+}
@@ -0,0 +1,610 @@
+use crate::{
+    cli::TaskQueryArgs,
+    datetime::{self, TimeContext},
+    error::CliError,
+    output::Record,
+    reclaim_api::Task,
+};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::cmp::Ordering;
+
+const WHERE_HINT: &str = "Use FIELD OP VALUE with one of = != > >= < <= ~, e.g. --where 'timeChunksRemaining>4' or --where 'eventCategory=WORK'. Nested fields use dots: --where 'assist.type=TASK'.";
+
+/// Combinable client-side task filters; a task must satisfy all of them.
+#[derive(Debug, Clone, Default)]
+pub struct TaskQuery {
+    priorities: Vec<String>,
+    category: Option<String>,
+    due_before: Option<DateTime<Utc>>,
+    due_after: Option<DateTime<Utc>>,
+    overdue_at: Option<DateTime<Utc>>,
+    title_contains: Option<String>,
+    notes_contains: Option<String>,
+    ids: Vec<u64>,
+    conditions: Vec<Condition>,
+}
+
+impl TaskQuery {
+    pub fn from_args(args: &TaskQueryArgs, time: &TimeContext) -> Result<Self, CliError> {
+        let parse_time = |flag_name: &str, raw: &Option<String>| {
+            raw.as_deref()
+                .map(|raw| {
+                    time.parse_instant(raw)
+                        .map_err(|reason| datetime::invalid_time_error(flag_name, raw, &reason))
+                })
+                .transpose()
+        };
+
+        Ok(Self {
+            priorities: args
+                .priority
+                .iter()
+                .map(|priority| priority.as_str().to_string())
+                .collect(),
+            category: args.category.map(|category| category.as_str().to_string()),
+            due_before: parse_time("--due-before", &args.due_before)?,
+            due_after: parse_time("--due-after", &args.due_after)?,
+            overdue_at: args.overdue.then(|| time.now()),
+            title_contains: normalized_needle(args.title_contains.as_deref()),
+            notes_contains: normalized_needle(args.notes_contains.as_deref()),
+            ids: args.ids.clone(),
+            conditions: args
+                .conditions
+                .iter()
+                .map(|raw| Condition::parse(raw))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.priorities.is_empty()
+            && self.category.is_none()
+            && self.due_before.is_none()
+            && self.due_after.is_none()
+            && self.overdue_at.is_none()
+            && self.title_contains.is_none()
+            && self.notes_contains.is_none()
+            && self.ids.is_empty()
+            && self.conditions.is_empty()
+    }
+
+    pub fn apply(&self, tasks: &mut Vec<Task>) {
+        if !self.is_empty() {
+            tasks.retain(|task| self.matches(task));
+        }
+    }
+
+    pub fn matches(&self, task: &Task) -> bool {
+        if !self.ids.is_empty() && !self.ids.contains(&task.id) {
+            return false;
+        }
+
+        if !self.priorities.is_empty()
+            && !task.priority.as_deref().is_some_and(|priority| {
+                self.priorities
+                    .iter()
+                    .any(|wanted| wanted.eq_ignore_ascii_case(priority))
+            })
+        {
+            return false;
+        }
+
+        if let Some(category) = self.category.as_deref() {
+            let task_category = task.extra.get("eventCategory").and_then(Value::as_str);
+            if !task_category.is_some_and(|value| value.eq_ignore_ascii_case(category)) {
+                return false;
+            }
+        }
+
+        if self.due_before.is_some() || self.due_after.is_some() || self.overdue_at.is_some() {
+            let Some(due) = task.due.as_deref().and_then(parse_timestamp) else {
+                return false;
+            };
+            if self.due_before.is_some_and(|before| due >= before)
+                || self.due_after.is_some_and(|after| due <= after)
+            {
+                return false;
+            }
+            if let Some(now) = self.overdue_at {
+                if due >= now || !is_open(task) {
+                    return false;
+                }
+            }
+        }
+
+        if let Some(needle) = self.title_contains.as_deref() {
+            if !task.title.to_lowercase().contains(needle) {
+                return false;
+            }
+        }
+
+        if let Some(needle) = self.notes_contains.as_deref() {
+            if !task
+                .notes
+                .as_deref()
+                .is_some_and(|notes| notes.to_lowercase().contains(needle))
+            {
+                return false;
+            }
+        }
+
+        if !self.conditions.is_empty() {
+            let Ok(document) = serde_json::to_value(task) else {
+                return false;
+            };
+            if !self
+                .conditions
+                .iter()
+                .all(|condition| condition.matches(&document))
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn normalized_needle(raw: Option<&str>) -> Option<String> {
+    raw.map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_lowercase)
+}
+
+fn is_open(task: &Task) -> bool {
+    !task.deleted
+        && !matches!(
+            task.status
+                .as_deref()
+                .map(str::to_ascii_uppercase)
+                .as_deref(),
+            Some("COMPLETE" | "ARCHIVED" | "CANCELLED")
+        )
+}
+
+fn parse_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw.trim())
+        .ok()
+        .map(|value| value.with_timezone(&Utc))
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Operator {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Contains,
+}
+
+/// One `--where` expression, e.g. `timeChunksRemaining>4`.
+#[derive(Debug, Clone)]
+struct Condition {
+    path: Vec<String>,
+    operator: Operator,
+    value: String,
+}
+
+impl Condition {
+    fn parse(raw: &str) -> Result<Self, CliError> {
+        const OPERATORS: [(&str, Operator); 7] = [
+            ("!=", Operator::Ne),
+            (">=", Operator::Ge),
+            ("<=", Operator::Le),
+            ("=", Operator::Eq),
+            (">", Operator::Gt),
+            ("<", Operator::Lt),
+            ("~", Operator::Contains),
+        ];
+
+        let invalid = |reason: &str| CliError::InvalidInput {
+            message: format!("Invalid --where expression '{raw}': {reason}."),
+            hint: Some(WHERE_HINT.to_string()),
+        };
+
+        let (index, token, operator) = OPERATORS
+            .iter()
+            .filter_map(|(token, operator)| raw.find(token).map(|index| (index, *token, *operator)))
+            // Earliest operator wins; at the same position the two-character form is listed first.
+            .min_by_key(|(index, _, _)| *index)
+            .ok_or_else(|| invalid("missing operator"))?;
+
+        let field = raw[..index].trim();
+        if field.is_empty() {
+            return Err(invalid("missing field name"));
+        }
+        let path = parse_field_path(field).ok_or_else(|| {
+            invalid("field names may only contain letters, digits, '_', '-' and '.'")
+        })?;
+
+        Ok(Self {
+            path,
+            operator,
+            value: raw[index + token.len()..].trim().to_string(),
+        })
+    }
+
+    fn matches(&self, document: &Value) -> bool {
+        let actual = lookup(document, &self.path);
+
+        let Some(actual) = actual else {
+            // Missing fields only satisfy `!=` (and `=null`).
+            let wants_null = self.value.eq_ignore_ascii_case("null");
+            return match self.operator {
+                Operator::Eq => wants_null,
+                Operator::Ne => !wants_null,
+                _ => false,
+            };
+        };
+
+        let actual_text = value_text(actual);
+
+        match self.operator {
+            Operator::Contains => actual_text
+                .to_lowercase()
+                .contains(&self.value.to_lowercase()),
+            Operator::Eq => compare(actual, &actual_text, &self.value) == Some(Ordering::Equal),
+            Operator::Ne => compare(actual, &actual_text, &self.value) != Some(Ordering::Equal),
+            Operator::Gt => compare(actual, &actual_text, &self.value) == Some(Ordering::Greater),
+            Operator::Ge => matches!(
+                compare(actual, &actual_text, &self.value),
+                Some(Ordering::Greater | Ordering::Equal)
+            ),
+            Operator::Lt => compare(actual, &actual_text, &self.value) == Some(Ordering::Less),
+            Operator::Le => matches!(
+                compare(actual, &actual_text, &self.value),
+                Some(Ordering::Less | Ordering::Equal)
+            ),
+        }
+    }
+}
+
+/// Sort order for `--sort`, e.g. `due,-priority,title`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey {
+    path: Vec<String>,
+    descending: bool,
+}
+
+impl SortKey {
+    pub fn parse_list(raw: &[String]) -> Result<Vec<Self>, CliError> {
+        raw.iter()
+            .map(|entry| entry.trim())
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (descending, field) = match entry.strip_prefix('-') {
+                    Some(field) => (true, field),
+                    None => (false, entry.strip_prefix('+').unwrap_or(entry)),
+                };
+                let path = parse_field_path(field).ok_or_else(|| CliError::InvalidInput {
+                    message: format!("Invalid --sort key '{entry}'."),
+                    hint: Some(
+                        "Use comma-separated field names, prefixed with - for descending, e.g. --sort due,-priority,title"
+                            .to_string(),
+                    ),
+                })?;
+                Ok(Self { path, descending })
+            })
+            .collect()
+    }
+}
+
+/// Stable multi-key sort. Tasks missing a sort field go last in either direction.
+/// `priority` ranks P1 highest, so `-priority` lists the most important tasks first.
+pub fn sort_tasks(tasks: &mut Vec<Task>, keys: &[SortKey]) {
+    if keys.is_empty() {
+        return;
+    }
+
+    let mut keyed = std::mem::take(tasks)
+        .into_iter()
+        .map(|task| (serde_json::to_value(&task).unwrap_or(Value::Null), task))
+        .collect::<Vec<_>>();
+
+    keyed.sort_by(|(left, _), (right, _)| {
+        keys.iter()
+            .map(
+                |key| match (lookup(left, &key.path), lookup(right, &key.path)) {
+                    (None, None) => Ordering::Equal,
+                    (None, Some(_)) => Ordering::Greater,
+                    (Some(_), None) => Ordering::Less,
+                    (Some(left), Some(right)) => {
+                        let ordering = compare_values(&key.path, left, right);
+                        if key.descending {
+                            ordering.reverse()
+                        } else {
+                            ordering
+                        }
+                    }
+                },
+            )
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+
+    tasks.extend(keyed.into_iter().map(|(_, task)| task));
+}
+
+/// Validates `--fields` entries and splits them into lookup paths.
+pub fn parse_fields(raw: &[String]) -> Result<Vec<String>, CliError> {
+    raw.iter()
+        .map(|field| field.trim())
+        .filter(|field| !field.is_empty())
+        .map(|field| {
+            parse_field_path(field)
+                .map(|_| field.to_string())
+                .ok_or_else(|| CliError::InvalidInput {
+                    message: format!("Invalid --fields entry '{field}'."),
+                    hint: Some(
+                        "Use comma-separated field names, e.g. --fields id,title,due,timeChunksRemaining"
+                            .to_string(),
+                    ),
+                })
+        })
+        .collect()
+}
+
+/// Projects each task onto `fields`, keeping their order. Missing fields become `null`.
+pub fn select_fields(tasks: &[Task], fields: &[String]) -> Vec<Record> {
+    tasks
+        .iter()
+        .map(|task| {
+            let document = serde_json::to_value(task).unwrap_or(Value::Null);
+            let row = fields
+                .iter()
+                .map(|field| {
+                    let path = field.split('.').map(str::to_string).collect::<Vec<_>>();
+                    let value = lookup(&document, &path).cloned().unwrap_or(Value::Null);
+                    (field.clone(), value)
+                })
+                .collect();
+            Record(row)
+        })
+        .collect()
+}
+
+pub fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn parse_field_path(field: &str) -> Option<Vec<String>> {
+    let valid = !field.is_empty()
+        && field.split('.').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-'))
+        });
+    valid.then(|| field.split('.').map(str::to_string).collect())
+}
+
+fn lookup<'a>(document: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter()
+        .try_fold(document, |value, key| value.get(key.as_str()))
+        .filter(|value| !value.is_null())
+}
+
+fn compare_values(path: &[String], left: &Value, right: &Value) -> Ordering {
+    if path.len() == 1 && path[0] == "priority" {
+        if let (Some(left), Some(right)) = (priority_rank(left), priority_rank(right)) {
+            return left.cmp(&right);
+        }
+    }
+
+    compare(left, &value_text(left), &value_text(right)).unwrap_or(Ordering::Equal)
+}
+
+/// Higher is more important: P1 -> 4, P4 -> 1.
+fn priority_rank(value: &Value) -> Option<u8> {
+    match value.as_str()?.to_ascii_uppercase().as_str() {
+        "P1" => Some(4),
+        "P2" => Some(3),
+        "P3" => Some(2),
+        "P4" => Some(1),
+        _ => None,
+    }
+}
+
+/// Compares numerically, then as timestamps, then as case-insensitive text.
+fn compare(actual: &Value, actual_text: &str, expected: &str) -> Option<Ordering> {
+    if let (Some(left), Ok(right)) = (actual.as_f64(), expected.parse::<f64>()) {
+        return left.partial_cmp(&right);
+    }
+
+    if let (Some(left), Some(right)) = (parse_timestamp(actual_text), parse_timestamp(expected)) {
+        return Some(left.cmp(&right));
+    }
+
+    Some(actual_text.to_lowercase().cmp(&expected.to_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cli::Priority, test_support};
+
+    fn context() -> TimeContext {
+        test_support::utc_time("2026-02-18T15:00:00Z")
+    }
+
+    fn task(id: u64, title: &str, priority: &str, due: Option<&str>, extra: Value) -> Task {
+        Task {
+            due: due.map(str::to_string),
+            priority: Some(priority.to_string()),
+            notes: Some("Draft for Alice".to_string()),
+            extra: serde_json::from_value(extra).unwrap(),
+            ..test_support::task(id, title)
+        }
+    }
+
+    fn tasks() -> Vec<Task> {
+        vec![
+            task(
+                1,
+                "Write spec",
+                "P1",
+                Some("2026-02-17T17:00:00Z"),
+                serde_json::json!({ "eventCategory": "WORK", "timeChunksRemaining": 6 }),
+            ),
+            task(
+                2,
+                "Groceries",
+                "P3",
+                Some("2026-02-20T17:00:00Z"),
+                serde_json::json!({ "eventCategory": "PERSONAL", "timeChunksRemaining": 2 }),
+            ),
+            task(
+                3,
+                "Review spec",
+                "P2",
+                None,
+                serde_json::json!({ "eventCategory": "WORK", "assist": { "type": "TASK" } }),
+            ),
+        ]
+    }
+
+    fn ids(args: TaskQueryArgs) -> Vec<u64> {
+        let query = TaskQuery::from_args(&args, &context()).unwrap();
+        let mut tasks = tasks();
+        query.apply(&mut tasks);
+        tasks.into_iter().map(|task| task.id).collect()
+    }
+
+    #[test]
+    fn filters_combine_with_and() {
+        assert_eq!(ids(TaskQueryArgs::default()), vec![1, 2, 3]);
+        assert_eq!(
+            ids(TaskQueryArgs {
+                priority: vec![Priority::P1, Priority::P2],
+                ..Default::default()
+            }),
+            vec![1, 3]
+        );
+        assert_eq!(
+            ids(TaskQueryArgs {
+                priority: vec![Priority::P1, Priority::P2],
+                title_contains: Some("REVIEW".to_string()),
+                ..Default::default()
+            }),
+            vec![3]
+        );
+        assert_eq!(
+            ids(TaskQueryArgs {
+                category: Some(crate::cli::EventCategory::Personal),
+                ..Default::default()
+            }),
+            vec![2]
+        );
+        assert_eq!(
+            ids(TaskQueryArgs {
+                ids: vec![2, 3],
+                notes_contains: Some("alice".to_string()),
+                ..Default::default()
+            }),
+            vec![2, 3]
+        );
+    }
+
+    #[test]
+    fn due_filters_use_time_input() {
+        assert_eq!(
+            ids(TaskQueryArgs {
+                overdue: true,
+                ..Default::default()
+            }),
+            vec![1]
+        );
+        assert_eq!(
+            ids(TaskQueryArgs {
+                due_after: Some("today".to_string()),
+                due_before: Some("+7d".to_string()),
+                ..Default::default()
+            }),
+            vec![2]
+        );
+
+        let error = TaskQuery::from_args(
+            &TaskQueryArgs {
+                due_before: Some("someday".to_string()),
+                ..Default::default()
+            },
+            &context(),
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("Invalid --due-before value"));
+    }
+
+    #[test]
+    fn where_conditions_compare_numbers_text_and_nested_fields() {
+        let by_where = |conditions: &[&str]| {
+            ids(TaskQueryArgs {
+                conditions: conditions.iter().map(|value| value.to_string()).collect(),
+                ..Default::default()
+            })
+        };
+
+        assert_eq!(by_where(&["timeChunksRemaining>4"]), vec![1]);
+        assert_eq!(by_where(&["timeChunksRemaining<=2"]), vec![2]);
+        assert_eq!(by_where(&["eventCategory=work"]), vec![1, 3]);
+        assert_eq!(by_where(&["eventCategory!=WORK"]), vec![2]);
+        assert_eq!(by_where(&["title~spec", "priority=P2"]), vec![3]);
+        assert_eq!(by_where(&["assist.type=TASK"]), vec![3]);
+        assert_eq!(by_where(&["due=null"]), vec![3]);
+        assert_eq!(by_where(&["due<2026-02-19T00:00:00Z"]), vec![1]);
+    }
+
+    #[test]
+    fn sort_tasks_supports_multiple_keys_and_descending_order() {
+        let sorted = |keys: &[&str]| {
+            let keys =
+                SortKey::parse_list(&keys.iter().map(|key| key.to_string()).collect::<Vec<_>>())
+                    .unwrap();
+            let mut tasks = tasks();
+            sort_tasks(&mut tasks, &keys);
+            tasks.into_iter().map(|task| task.id).collect::<Vec<_>>()
+        };
+
+        assert_eq!(sorted(&["-priority"]), vec![1, 3, 2]);
+        assert_eq!(sorted(&["priority"]), vec![2, 3, 1]);
+        assert_eq!(sorted(&["-due"]), vec![2, 1, 3]);
+        assert_eq!(sorted(&["eventCategory", "-title"]), vec![2, 1, 3]);
+        assert_eq!(sorted(&["timeChunksRemaining"]), vec![2, 1, 3]);
+
+        let error = SortKey::parse_list(&["-".to_string()]).unwrap_err();
+        assert!(error.to_string().contains("Invalid --sort key"));
+    }
+
+    #[test]
+    fn select_fields_keeps_requested_order_and_nulls_missing_fields() {
+        let fields = parse_fields(&[
+            "id".to_string(),
+            "timeChunksRemaining".to_string(),
+            "assist.type".to_string(),
+        ])
+        .unwrap();
+        let rows = select_fields(&tasks()[2..], &fields);
+
+        assert_eq!(
+            rows[0],
+            Record(vec![
+                ("id".to_string(), serde_json::json!(3)),
+                ("timeChunksRemaining".to_string(), Value::Null),
+                ("assist.type".to_string(), serde_json::json!("TASK")),
+            ])
+        );
+    }
+
+    #[test]
+    fn invalid_where_expressions_are_rejected() {
+        for raw in ["timeChunksRemaining", ">4", "bad field=1"] {
+            let error = Condition::parse(raw).unwrap_err();
+            assert!(error.to_string().contains("Invalid --where expression"));
+        }
+    }
+}
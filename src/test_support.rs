@@ -0,0 +1,299 @@
+//! Fixtures and an in-memory `ReclaimApi` shared by the unit tests.
+
+use crate::{
+    datetime::{TimeContext, Zone},
+    error::CliError,
+    reclaim_api::{
+        CreateTaskRequest, CurrentUser, Event, EventListQuery, Outcome, PlannedRequest,
+        PlannerAction, ReclaimApi, Task, TaskFilter,
+    },
+};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+/// Wednesday 2026-02-18, 10:00 in New York (15:00 UTC).
+pub fn test_time() -> TimeContext {
+    TimeContext::fixed(
+        "2026-02-18T15:00:00Z".parse().unwrap(),
+        Zone::parse("America/New_York").unwrap(),
+    )
+}
+
+/// `instant` with UTC as the local zone, for tests that read times back off the screen.
+pub fn utc_time(instant: &str) -> TimeContext {
+    TimeContext::fixed(instant.parse().unwrap(), Zone::parse("UTC").unwrap())
+}
+
+/// A fresh, empty directory under the system temp dir, unique to this process and call so
+/// parallel tests and test binaries never share one.
+pub fn temp_dir(name: &str) -> PathBuf {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let dir = env::temp_dir().join(format!(
+        "reclaim-test-{}-{}-{name}",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// A scheduled task with only an id and a title.
+pub fn task(id: u64, title: &str) -> Task {
+    Task {
+        id,
+        title: title.to_string(),
+        status: Some("SCHEDULED".to_string()),
+        due: None,
+        priority: None,
+        notes: None,
+        deleted: false,
+        extra: HashMap::new(),
+    }
+}
+
+/// Keeps a task list in memory and records each call as `METHOD target` (`GET tasks`,
+/// `PATCH 7`, `DONE 7`). Unknown task ids answer 404; events and schedule actions are not
+/// modeled and answer with an error.
+#[derive(Default)]
+pub struct FakeApi {
+    tasks: Mutex<Vec<Task>>,
+    calls: Mutex<Vec<String>>,
+    unreachable: bool,
+    dry_run: bool,
+}
+
+impl FakeApi {
+    pub fn new(tasks: Vec<Task>) -> Self {
+        Self {
+            tasks: Mutex::new(tasks),
+            ..Self::default()
+        }
+    }
+
+    /// Fails every call like a network error.
+    pub fn unreachable(mut self) -> Self {
+        self.unreachable = true;
+        self
+    }
+
+    /// Stops writes like `--dry-run`.
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    pub fn tasks(&self) -> Vec<Task> {
+        self.tasks.lock().unwrap().clone()
+    }
+
+    fn record(&self, call: String) -> Result<(), CliError> {
+        self.calls.lock().unwrap().push(call);
+        if self.unreachable {
+            return Err(CliError::Transport {
+                message: "Could not connect to the Reclaim API.".to_string(),
+                hint: None,
+            });
+        }
+        Ok(())
+    }
+
+    /// Records a write to task `task_id`, or returns the request a dry run would plan.
+    fn write(
+        &self,
+        method: &str,
+        task_id: u64,
+        body: Option<Value>,
+    ) -> Result<Option<PlannedRequest>, CliError> {
+        if self.dry_run {
+            return Ok(Some(PlannedRequest {
+                method: method.to_string(),
+                url: format!("https://api.test/api/tasks/{task_id}"),
+                body,
+            }));
+        }
+        self.record(format!("{method} {task_id}"))?;
+        Ok(None)
+    }
+
+    /// Applies `change` to task `task_id` and returns the result, or 404.
+    fn update(&self, task_id: u64, change: impl FnOnce(&mut Task)) -> Result<Task, CliError> {
+        let mut tasks = self.tasks.lock().unwrap();
+        let task = tasks
+            .iter_mut()
+            .find(|task| task.id == task_id)
+            .ok_or_else(not_found)?;
+        change(task);
+        Ok(task.clone())
+    }
+
+    /// Sets top-level fields from a PATCH or PUT body.
+    fn merge(&self, task_id: u64, request: Value) -> Result<Task, CliError> {
+        self.update(task_id, |task| {
+            let mut document = serde_json::to_value(&*task).unwrap();
+            if let (Value::Object(document), Value::Object(fields)) = (&mut document, request) {
+                document.extend(fields);
+            }
+            *task = serde_json::from_value(document).unwrap();
+        })
+    }
+}
+
+fn not_found() -> CliError {
+    CliError::Api {
+        status: 404,
+        message: "Task not found".to_string(),
+        hint: None,
+    }
+}
+
+fn unsupported(what: &str) -> CliError {
+    CliError::Output(format!("FakeApi does not model {what}."))
+}
+
+impl ReclaimApi for FakeApi {
+    async fn current_user(&self) -> Result<CurrentUser, CliError> {
+        Err(unsupported("the current user"))
+    }
+
+    async fn list_tasks(&self, filter: TaskFilter) -> Result<Vec<Task>, CliError> {
+        self.record("GET tasks".to_string())?;
+        let mut tasks = self.tasks();
+        tasks.retain(|task| filter.includes(task));
+        Ok(tasks)
+    }
+
+    async fn get_task(&self, task_id: u64) -> Result<Task, CliError> {
+        self.record(format!("GET {task_id}"))?;
+        self.update(task_id, |_| {})
+    }
+
+    async fn create_task(&self, request: CreateTaskRequest) -> Result<Outcome<Task>, CliError> {
+        if self.dry_run {
+            return Ok(Outcome::Planned(PlannedRequest {
+                method: "POST".to_string(),
+                url: "https://api.test/api/tasks".to_string(),
+                body: serde_json::to_value(&request).ok(),
+            }));
+        }
+        self.record(format!("POST {}", request.title))?;
+        let mut tasks = self.tasks.lock().unwrap();
+        let id = tasks.iter().map(|task| task.id).max().unwrap_or(0) + 1;
+        let created = Task {
+            status: Some("NEW".to_string()),
+            notes: request.notes,
+            priority: request.priority,
+            due: request.due,
+            ..task(id, &request.title)
+        };
+        tasks.push(created.clone());
+        Ok(Outcome::Sent(created))
+    }
+
+    async fn list_events(&self, _query: EventListQuery) -> Result<Vec<Event>, CliError> {
+        Err(unsupported("events"))
+    }
+
+    async fn get_event(
+        &self,
+        _calendar_id: u64,
+        _event_id: &str,
+        _source_details: Option<bool>,
+        _thin: Option<bool>,
+    ) -> Result<Event, CliError> {
+        Err(unsupported("events"))
+    }
+
+    async fn apply_schedule_actions(&self, _request: Value) -> Result<Outcome<Value>, CliError> {
+        Err(unsupported("schedule actions"))
+    }
+
+    async fn put_task(
+        &self,
+        task_id: u64,
+        request: Value,
+        _notification_key: Option<&str>,
+    ) -> Result<Outcome<Task>, CliError> {
+        if let Some(planned) = self.write("PUT", task_id, Some(request.clone()))? {
+            return Ok(Outcome::Planned(planned));
+        }
+        self.merge(task_id, request).map(Outcome::Sent)
+    }
+
+    async fn patch_task(
+        &self,
+        task_id: u64,
+        request: Value,
+        _notification_key: Option<&str>,
+    ) -> Result<Outcome<Task>, CliError> {
+        if let Some(planned) = self.write("PATCH", task_id, Some(request.clone()))? {
+            return Ok(Outcome::Planned(planned));
+        }
+        self.merge(task_id, request).map(Outcome::Sent)
+    }
+
+    async fn delete_task(
+        &self,
+        task_id: u64,
+        _notification_key: Option<&str>,
+    ) -> Result<Outcome<Value>, CliError> {
+        if let Some(planned) = self.write("DELETE", task_id, None)? {
+            return Ok(Outcome::Planned(planned));
+        }
+        let mut tasks = self.tasks.lock().unwrap();
+        let index = tasks
+            .iter()
+            .position(|task| task.id == task_id)
+            .ok_or_else(not_found)?;
+        tasks.remove(index);
+        Ok(Outcome::Sent(Value::Null))
+    }
+
+    async fn run_planner_action(
+        &self,
+        task_id: u64,
+        action: PlannerAction,
+    ) -> Result<Outcome<Task>, CliError> {
+        let (method, status) = match action {
+            PlannerAction::Done => ("DONE", "COMPLETE"),
+            PlannerAction::Start => ("START", "IN_PROGRESS"),
+            PlannerAction::Stop => ("STOP", "SCHEDULED"),
+            PlannerAction::Reopen => ("REOPEN", "SCHEDULED"),
+        };
+        if let Some(planned) = self.write(method, task_id, None)? {
+            return Ok(Outcome::Planned(planned));
+        }
+        self.update(task_id, |task| task.status = Some(status.to_string()))
+            .map(Outcome::Sent)
+    }
+
+    async fn snooze_task(&self, task_id: u64, until: &str) -> Result<Outcome<Task>, CliError> {
+        if let Some(planned) = self.write("SNOOZE", task_id, None)? {
+            return Ok(Outcome::Planned(planned));
+        }
+        self.update(task_id, |task| {
+            task.extra
+                .insert("snoozeUntil".to_string(), Value::String(until.to_string()));
+        })
+        .map(Outcome::Sent)
+    }
+
+    async fn log_work(&self, task_id: u64, _minutes: u32) -> Result<Outcome<Task>, CliError> {
+        if let Some(planned) = self.write("LOG", task_id, None)? {
+            return Ok(Outcome::Planned(planned));
+        }
+        self.update(task_id, |_| {}).map(Outcome::Sent)
+    }
+}